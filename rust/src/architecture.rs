@@ -20,6 +20,7 @@ use std::borrow::{Borrow, Cow};
 use std::collections::HashMap;
 use std::ffi::{CStr, CString};
 use std::hash::Hash;
+use std::marker::PhantomData;
 use std::mem::zeroed;
 use std::ops;
 use std::ops::Drop;
@@ -27,7 +28,9 @@ use std::ptr;
 use std::slice;
 
 use crate::callingconvention::CallingConvention;
+use crate::databuffer::DataBuffer;
 use crate::platform::Platform;
+use crate::types::{Conf, NameAndType, Type};
 use crate::{BranchType, Endianness};
 
 use crate::llil::{get_default_flag_cond_llil, get_default_flag_write_llil};
@@ -382,17 +385,79 @@ pub trait FlagGroup: Sized + Clone + Copy {
     fn flag_conditions(&self) -> HashMap<Self::FlagClass, FlagCondition>;
 }
 
+pub trait RegisterStackInfo: Sized {
+    type RegStackType: RegisterStack<InfoType = Self>;
+    type RegType: Register<InfoType = Self::RegInfoType>;
+    type RegInfoType: RegisterInfo<RegType = Self::RegType>;
+
+    /// First register backing the stack's storage and the number of storage registers;
+    /// storage registers *MUST* have consecutive ids
+    fn storage_regs(&self) -> (Self::RegType, u32);
+
+    /// First register used to address entries relative to the top of the stack (`st0`, `st1`,
+    /// ... on x87) and the number of such registers, if the stack has any
+    fn top_relative_regs(&self) -> Option<(Self::RegType, u32)>;
+
+    /// Register holding the index of the current top of the stack
+    fn stack_top_reg(&self) -> Self::RegType;
+}
+
+pub trait RegisterStack: Sized + Clone + Copy {
+    type InfoType: RegisterStackInfo<
+        RegType = Self::RegType,
+        RegInfoType = Self::RegInfoType,
+        RegStackType = Self,
+    >;
+    type RegType: Register<InfoType = Self::RegInfoType>;
+    type RegInfoType: RegisterInfo<RegType = Self::RegType>;
+
+    fn name(&self) -> Cow<str>;
+    fn info(&self) -> Self::InfoType;
+
+    /// Unique identifier for this `RegisterStack`.
+    ///
+    /// *MUST* be in the range [0, 0x7fff_ffff]
+    fn id(&self) -> u32;
+}
+
+pub trait Intrinsic: Sized + Clone + Copy {
+    fn name(&self) -> Cow<str>;
+
+    /// Unique identifier for this `Intrinsic`.
+    ///
+    /// *MUST* be in the range [0, 0x7fff_ffff]
+    fn id(&self) -> u32;
+
+    /// Names and types of the values the intrinsic consumes, in order
+    fn inputs(&self) -> Vec<NameAndType<String>>;
+
+    /// Types of the values the intrinsic produces, in order
+    fn outputs(&self) -> Vec<Conf<Ref<Type>>>;
+}
+
 pub trait Architecture: 'static + Sized + AsRef<CoreArchitecture> {
     type Handle: Borrow<Self> + Clone;
 
     type RegisterInfo: RegisterInfo<RegType = Self::Register>;
     type Register: Register<InfoType = Self::RegisterInfo>;
+    type RegisterStackInfo: RegisterStackInfo<
+        RegType = Self::Register,
+        RegInfoType = Self::RegisterInfo,
+        RegStackType = Self::RegisterStack,
+    >;
+    type RegisterStack: RegisterStack<
+        InfoType = Self::RegisterStackInfo,
+        RegType = Self::Register,
+        RegInfoType = Self::RegisterInfo,
+    >;
 
     type Flag: Flag<FlagClass = Self::FlagClass>;
     type FlagWrite: FlagWrite<FlagType = Self::Flag, FlagClass = Self::FlagClass>;
     type FlagClass: FlagClass;
     type FlagGroup: FlagGroup<FlagType = Self::Flag, FlagClass = Self::FlagClass>;
 
+    type Intrinsic: Intrinsic;
+
     type InstructionTextContainer: Into<Vec<InstructionTextToken>>;
 
     fn endianness(&self) -> Endianness;
@@ -488,19 +553,74 @@ pub trait Architecture: 'static + Sized + AsRef<CoreArchitecture> {
     fn registers_global(&self) -> Vec<Self::Register>;
     fn registers_system(&self) -> Vec<Self::Register>;
 
+    fn register_stacks(&self) -> Vec<Self::RegisterStack>;
+
     fn flags(&self) -> Vec<Self::Flag>;
     fn flag_write_types(&self) -> Vec<Self::FlagWrite>;
     fn flag_classes(&self) -> Vec<Self::FlagClass>;
     fn flag_groups(&self) -> Vec<Self::FlagGroup>;
 
+    fn intrinsics(&self) -> Vec<Self::Intrinsic>;
+
     fn stack_pointer_reg(&self) -> Option<Self::Register>;
     fn link_reg(&self) -> Option<Self::Register>;
 
     fn register_from_id(&self, id: u32) -> Option<Self::Register>;
+    fn register_stack_from_id(&self, id: u32) -> Option<Self::RegisterStack>;
     fn flag_from_id(&self, id: u32) -> Option<Self::Flag>;
     fn flag_write_from_id(&self, id: u32) -> Option<Self::FlagWrite>;
     fn flag_class_from_id(&self, id: u32) -> Option<Self::FlagClass>;
     fn flag_group_from_id(&self, id: u32) -> Option<Self::FlagGroup>;
+    fn intrinsic_from_id(&self, id: u32) -> Option<Self::Intrinsic>;
+
+    fn can_assemble(&self) -> bool {
+        false
+    }
+
+    /// Assembles `code` as if it were located at `addr`, returning the machine code or an
+    /// error message suitable for display to the user
+    fn assemble(&self, _code: &str, _addr: u64) -> Result<Vec<u8>, String> {
+        Err("Assemble unsupported".into())
+    }
+
+    fn is_never_branch_patch_available(&self, _data: &[u8], _addr: u64) -> bool {
+        false
+    }
+
+    fn is_always_branch_patch_available(&self, _data: &[u8], _addr: u64) -> bool {
+        false
+    }
+
+    fn is_invert_branch_patch_available(&self, _data: &[u8], _addr: u64) -> bool {
+        false
+    }
+
+    fn is_skip_and_return_zero_patch_available(&self, _data: &[u8], _addr: u64) -> bool {
+        false
+    }
+
+    fn is_skip_and_return_value_patch_available(&self, _data: &[u8], _addr: u64) -> bool {
+        false
+    }
+
+    /// Overwrites the instruction in `data` with no-ops, returning whether it succeeded
+    fn convert_to_nop(&self, _data: &mut [u8], _addr: u64) -> bool {
+        false
+    }
+
+    /// Rewrites the conditional branch in `data` to be unconditional
+    fn always_branch(&self, _data: &mut [u8], _addr: u64) -> bool {
+        false
+    }
+
+    fn invert_branch(&self, _data: &mut [u8], _addr: u64) -> bool {
+        false
+    }
+
+    /// Rewrites the call in `data` to instead return `value` as the call's result
+    fn skip_and_return_value(&self, _data: &mut [u8], _addr: u64, _value: u64) -> bool {
+        false
+    }
 
     fn handle(&self) -> Self::Handle;
 }
@@ -562,6 +682,69 @@ impl Register for CoreRegister {
     }
 }
 
+pub struct CoreRegisterStackInfo(*mut BNArchitecture, BNRegisterStackInfo);
+impl RegisterStackInfo for CoreRegisterStackInfo {
+    type RegStackType = CoreRegisterStack;
+    type RegType = CoreRegister;
+    type RegInfoType = CoreRegisterInfo;
+
+    fn storage_regs(&self) -> (CoreRegister, u32) {
+        (
+            CoreRegister(self.0, self.1.firstStorageReg),
+            self.1.storageCount,
+        )
+    }
+
+    fn top_relative_regs(&self) -> Option<(CoreRegister, u32)> {
+        if self.1.topRelativeCount == 0 {
+            None
+        } else {
+            Some((
+                CoreRegister(self.0, self.1.firstTopRelativeReg),
+                self.1.topRelativeCount,
+            ))
+        }
+    }
+
+    fn stack_top_reg(&self) -> CoreRegister {
+        CoreRegister(self.0, self.1.stackTopReg)
+    }
+}
+
+#[derive(Copy, Clone, Eq, PartialEq, Hash)]
+pub struct CoreRegisterStack(*mut BNArchitecture, u32);
+impl RegisterStack for CoreRegisterStack {
+    type InfoType = CoreRegisterStackInfo;
+    type RegType = CoreRegister;
+    type RegInfoType = CoreRegisterInfo;
+
+    fn name(&self) -> Cow<str> {
+        unsafe {
+            let name = BNGetArchitectureRegisterStackName(self.0, self.1);
+
+            // We need to guarantee ownership, as if we're still
+            // a Borrowed variant we're about to free the underlying
+            // memory.
+            let res = CStr::from_ptr(name);
+            let res = res.to_string_lossy().into_owned().into();
+
+            BNFreeString(name);
+
+            res
+        }
+    }
+
+    fn info(&self) -> CoreRegisterStackInfo {
+        CoreRegisterStackInfo(self.0, unsafe {
+            BNGetArchitectureRegisterStackInfo(self.0, self.1)
+        })
+    }
+
+    fn id(&self) -> u32 {
+        self.1
+    }
+}
+
 #[derive(Copy, Clone, Eq, PartialEq, Hash)]
 pub struct CoreFlag(*mut BNArchitecture, u32);
 impl Flag for CoreFlag {
@@ -750,6 +933,74 @@ impl FlagGroup for CoreFlagGroup {
     }
 }
 
+#[derive(Copy, Clone, Eq, PartialEq, Hash)]
+pub struct CoreIntrinsic(*mut BNArchitecture, u32);
+impl Intrinsic for CoreIntrinsic {
+    fn name(&self) -> Cow<str> {
+        unsafe {
+            let name = BNGetArchitectureIntrinsicName(self.0, self.1);
+
+            // We need to guarantee ownership, as if we're still
+            // a Borrowed variant we're about to free the underlying
+            // memory.
+            let res = CStr::from_ptr(name);
+            let res = res.to_string_lossy().into_owned().into();
+
+            BNFreeString(name);
+
+            res
+        }
+    }
+
+    fn id(&self) -> u32 {
+        self.1
+    }
+
+    fn inputs(&self) -> Vec<NameAndType<String>> {
+        unsafe {
+            let mut count: usize = 0;
+            let inputs = BNGetArchitectureIntrinsicInputs(self.0, self.1, &mut count as *mut _);
+
+            // the list keeps its own type references, which BNFreeNameAndTypeList releases
+            let ret = slice::from_raw_parts_mut(inputs, count)
+                .iter()
+                .map(|input| {
+                    NameAndType::new(
+                        raw_to_string(input.name).unwrap_or_default(),
+                        &Type::ref_from_raw(BNNewTypeReference(input.type_)),
+                        input.typeConfidence,
+                    )
+                })
+                .collect();
+
+            BNFreeNameAndTypeList(inputs, count);
+
+            ret
+        }
+    }
+
+    fn outputs(&self) -> Vec<Conf<Ref<Type>>> {
+        unsafe {
+            let mut count: usize = 0;
+            let outputs = BNGetArchitectureIntrinsicOutputs(self.0, self.1, &mut count as *mut _);
+
+            let ret = slice::from_raw_parts_mut(outputs, count)
+                .iter()
+                .map(|output| {
+                    Conf::new(
+                        Type::ref_from_raw(BNNewTypeReference(output.type_)),
+                        output.confidence,
+                    )
+                })
+                .collect();
+
+            BNFreeOutputTypeList(outputs, count);
+
+            ret
+        }
+    }
+}
+
 pub struct CoreArchitectureList(*mut *mut BNArchitecture, usize);
 impl ops::Deref for CoreArchitectureList {
     type Target = [CoreArchitecture];
@@ -837,10 +1088,13 @@ impl Architecture for CoreArchitecture {
 
     type RegisterInfo = CoreRegisterInfo;
     type Register = CoreRegister;
+    type RegisterStackInfo = CoreRegisterStackInfo;
+    type RegisterStack = CoreRegisterStack;
     type Flag = CoreFlag;
     type FlagWrite = CoreFlagWrite;
     type FlagClass = CoreFlagClass;
     type FlagGroup = CoreFlagGroup;
+    type Intrinsic = CoreIntrinsic;
 
     type InstructionTextContainer = InstructionTextTokenList;
 
@@ -920,38 +1174,79 @@ impl Architecture for CoreArchitecture {
 
     fn instruction_llil(
         &self,
-        _data: &[u8],
-        _addr: u64,
-        _il: &mut Lifter<Self>,
+        data: &[u8],
+        addr: u64,
+        il: &mut Lifter<Self>,
     ) -> Option<(usize, bool)> {
-        None
+        let mut size = data.len();
+        let success = unsafe {
+            BNGetInstructionLowLevelIL(self.0, data.as_ptr(), addr, &mut size as *mut _, il.handle)
+        };
+
+        Some((size, success))
     }
 
     fn flag_write_llil<'a>(
         &self,
-        _flag: Self::Flag,
-        _flag_write: Self::FlagWrite,
-        _op: FlagWriteOp<Self::Register>,
-        _il: &'a mut Lifter<Self>,
+        flag: Self::Flag,
+        flag_write: Self::FlagWrite,
+        op: FlagWriteOp<Self::Register>,
+        il: &'a mut Lifter<Self>,
     ) -> Option<LiftedExpr<'a, Self>> {
-        None
+        let (size, operation) = op.size_and_op();
+        let (count, mut operands) = op.api_operands();
+
+        let expr_idx = unsafe {
+            BNGetArchitectureFlagWriteLowLevelIL(
+                self.0,
+                operation,
+                size,
+                flag_write.id(),
+                flag.id(),
+                operands.as_mut_ptr(),
+                count,
+                il.handle,
+            )
+        };
+
+        Some(LiftedExpr {
+            function: il,
+            expr_idx,
+            _ty: PhantomData,
+        })
     }
 
     fn flag_cond_llil<'a>(
         &self,
-        _cond: FlagCondition,
-        _class: Option<Self::FlagClass>,
-        _il: &'a mut Lifter<Self>,
+        cond: FlagCondition,
+        class: Option<Self::FlagClass>,
+        il: &'a mut Lifter<Self>,
     ) -> Option<LiftedExpr<'a, Self>> {
-        None
+        let class_id = class.map(|c| c.id()).unwrap_or(0);
+
+        let expr_idx =
+            unsafe { BNGetArchitectureFlagConditionLowLevelIL(self.0, cond, class_id, il.handle) };
+
+        Some(LiftedExpr {
+            function: il,
+            expr_idx,
+            _ty: PhantomData,
+        })
     }
 
     fn flag_group_llil<'a>(
         &self,
-        _group: Self::FlagGroup,
-        _il: &'a mut Lifter<Self>,
+        group: Self::FlagGroup,
+        il: &'a mut Lifter<Self>,
     ) -> Option<LiftedExpr<'a, Self>> {
-        None
+        let expr_idx =
+            unsafe { BNGetArchitectureSemanticFlagGroupLowLevelIL(self.0, group.id(), il.handle) };
+
+        Some(LiftedExpr {
+            function: il,
+            expr_idx,
+            _ty: PhantomData,
+        })
     }
 
     fn registers_all(&self) -> Vec<CoreRegister> {
@@ -1018,6 +1313,22 @@ impl Architecture for CoreArchitecture {
         }
     }
 
+    fn register_stacks(&self) -> Vec<CoreRegisterStack> {
+        unsafe {
+            let mut count: usize = 0;
+            let regs = BNGetAllArchitectureRegisterStacks(self.0, &mut count as *mut _);
+
+            let ret = slice::from_raw_parts_mut(regs, count)
+                .iter()
+                .map(|reg| CoreRegisterStack(self.0, *reg))
+                .collect();
+
+            BNFreeRegisterList(regs);
+
+            ret
+        }
+    }
+
     fn flags(&self) -> Vec<CoreFlag> {
         unsafe {
             let mut count: usize = 0;
@@ -1082,6 +1393,22 @@ impl Architecture for CoreArchitecture {
         }
     }
 
+    fn intrinsics(&self) -> Vec<CoreIntrinsic> {
+        unsafe {
+            let mut count: usize = 0;
+            let intrinsics = BNGetAllArchitectureIntrinsics(self.0, &mut count as *mut _);
+
+            let ret = slice::from_raw_parts_mut(intrinsics, count)
+                .iter()
+                .map(|intrinsic| CoreIntrinsic(self.0, *intrinsic))
+                .collect();
+
+            BNFreeRegisterList(intrinsics);
+
+            ret
+        }
+    }
+
     fn flags_required_for_flag_condition(
         &self,
         condition: FlagCondition,
@@ -1128,6 +1455,11 @@ impl Architecture for CoreArchitecture {
         Some(CoreRegister(self.0, id))
     }
 
+    fn register_stack_from_id(&self, id: u32) -> Option<CoreRegisterStack> {
+        // TODO validate in debug builds
+        Some(CoreRegisterStack(self.0, id))
+    }
+
     fn flag_from_id(&self, id: u32) -> Option<CoreFlag> {
         // TODO validate in debug builds
         Some(CoreFlag(self.0, id))
@@ -1148,6 +1480,89 @@ impl Architecture for CoreArchitecture {
         Some(CoreFlagGroup(self.0, id))
     }
 
+    fn intrinsic_from_id(&self, id: u32) -> Option<CoreIntrinsic> {
+        // TODO validate in debug builds
+        Some(CoreIntrinsic(self.0, id))
+    }
+
+    fn can_assemble(&self) -> bool {
+        unsafe { BNCanArchitectureAssemble(self.0) }
+    }
+
+    fn assemble(&self, code: &str, addr: u64) -> Result<Vec<u8>, String> {
+        let code = CString::new(code).map_err(|_| "Invalid encoding in code string".to_string())?;
+        let result = DataBuffer::new(&[]).map_err(|_| "Unable to allocate buffer".to_string())?;
+        let mut errors = ptr::null_mut();
+
+        let success =
+            unsafe { BNAssemble(self.0, code.as_ptr(), addr, result.as_raw(), &mut errors) };
+
+        let errors = if errors.is_null() {
+            String::new()
+        } else {
+            unsafe { BnString::from_raw(errors) }.to_string()
+        };
+
+        if success {
+            Ok(result.get_data().to_vec())
+        } else {
+            Err(errors)
+        }
+    }
+
+    fn is_never_branch_patch_available(&self, data: &[u8], addr: u64) -> bool {
+        unsafe {
+            BNIsArchitectureNeverBranchPatchAvailable(self.0, data.as_ptr(), addr, data.len())
+        }
+    }
+
+    fn is_always_branch_patch_available(&self, data: &[u8], addr: u64) -> bool {
+        unsafe {
+            BNIsArchitectureAlwaysBranchPatchAvailable(self.0, data.as_ptr(), addr, data.len())
+        }
+    }
+
+    fn is_invert_branch_patch_available(&self, data: &[u8], addr: u64) -> bool {
+        unsafe {
+            BNIsArchitectureInvertBranchPatchAvailable(self.0, data.as_ptr(), addr, data.len())
+        }
+    }
+
+    fn is_skip_and_return_zero_patch_available(&self, data: &[u8], addr: u64) -> bool {
+        unsafe {
+            BNIsArchitectureSkipAndReturnZeroPatchAvailable(self.0, data.as_ptr(), addr, data.len())
+        }
+    }
+
+    fn is_skip_and_return_value_patch_available(&self, data: &[u8], addr: u64) -> bool {
+        unsafe {
+            BNIsArchitectureSkipAndReturnValuePatchAvailable(
+                self.0,
+                data.as_ptr(),
+                addr,
+                data.len(),
+            )
+        }
+    }
+
+    fn convert_to_nop(&self, data: &mut [u8], addr: u64) -> bool {
+        unsafe { BNArchitectureConvertToNop(self.0, data.as_mut_ptr(), addr, data.len()) }
+    }
+
+    fn always_branch(&self, data: &mut [u8], addr: u64) -> bool {
+        unsafe { BNArchitectureAlwaysBranch(self.0, data.as_mut_ptr(), addr, data.len()) }
+    }
+
+    fn invert_branch(&self, data: &mut [u8], addr: u64) -> bool {
+        unsafe { BNArchitectureInvertBranch(self.0, data.as_mut_ptr(), addr, data.len()) }
+    }
+
+    fn skip_and_return_value(&self, data: &mut [u8], addr: u64, value: u64) -> bool {
+        unsafe {
+            BNArchitectureSkipAndReturnValue(self.0, data.as_mut_ptr(), addr, data.len(), value)
+        }
+    }
+
     fn handle(&self) -> CoreArchitecture {
         *self
     }
@@ -1245,6 +1660,19 @@ where
     S: BnStrCompatible,
     A: 'static + Architecture<Handle = CustomArchitectureHandle<A>> + Send + Sync + Sized,
     F: FnOnce(CustomArchitectureHandle<A>, CoreArchitecture) -> A,
+{
+    let name = name.as_bytes_with_nul();
+
+    register_custom_architecture(func, |custom_arch| unsafe {
+        BNRegisterArchitecture(name.as_ref().as_ptr() as *mut _, custom_arch)
+    })
+}
+
+fn register_custom_architecture<A, F, R>(func: F, register: R) -> &'static A
+where
+    A: 'static + Architecture<Handle = CustomArchitectureHandle<A>> + Send + Sync + Sized,
+    F: FnOnce(CustomArchitectureHandle<A>, CoreArchitecture) -> A,
+    R: FnOnce(*mut BNCustomArchitecture) -> *mut BNArchitecture,
 {
     use std::mem;
     use std::os::raw::{c_char, c_void};
@@ -1897,155 +2325,357 @@ where
         }
     }
 
-    extern "C" fn cb_reg_stack_name<A>(ctxt: *mut c_void, _stack: u32) -> *mut c_char
+    extern "C" fn cb_reg_stack_name<A>(ctxt: *mut c_void, stack: u32) -> *mut c_char
     where
         A: 'static + Architecture<Handle = CustomArchitectureHandle<A>> + Send + Sync,
     {
-        let _custom_arch = unsafe { &*(ctxt as *mut A) };
-        BnString::new("reg_stack").into_raw()
-    }
+        let custom_arch = unsafe { &*(ctxt as *mut A) };
+
+        match custom_arch.register_stack_from_id(stack) {
+            Some(stack) => BnString::new(stack.name().as_ref()).into_raw(),
+            None => BnString::new("invalid_reg_stack").into_raw(),
+        }
+    }
 
     extern "C" fn cb_reg_stacks<A>(ctxt: *mut c_void, count: *mut usize) -> *mut u32
     where
         A: 'static + Architecture<Handle = CustomArchitectureHandle<A>> + Send + Sync,
     {
-        let _custom_arch = unsafe { &*(ctxt as *mut A) };
+        let custom_arch = unsafe { &*(ctxt as *mut A) };
+        let stacks = custom_arch.register_stacks();
 
-        unsafe {
-            *count = 0;
-        }
-        ptr::null_mut()
+        alloc_register_list(stacks.iter().map(|s| s.id()), unsafe { &mut *count })
     }
 
     extern "C" fn cb_reg_stack_info<A>(
         ctxt: *mut c_void,
-        _stack: u32,
-        _info: *mut BNRegisterStackInfo,
+        stack: u32,
+        result: *mut BNRegisterStackInfo,
     ) where
         A: 'static + Architecture<Handle = CustomArchitectureHandle<A>> + Send + Sync,
     {
-        let _custom_arch = unsafe { &*(ctxt as *mut A) };
+        let custom_arch = unsafe { &*(ctxt as *mut A) };
+        let result = unsafe { &mut *result };
+
+        if let Some(stack) = custom_arch.register_stack_from_id(stack) {
+            let info = stack.info();
+
+            let (storage_reg, storage_count) = info.storage_regs();
+            result.firstStorageReg = storage_reg.id();
+            result.storageCount = storage_count;
+
+            if let Some((top_reg, top_count)) = info.top_relative_regs() {
+                result.firstTopRelativeReg = top_reg.id();
+                result.topRelativeCount = top_count;
+            } else {
+                result.firstTopRelativeReg = 0xffff_ffff;
+                result.topRelativeCount = 0;
+            }
+
+            result.stackTopReg = info.stack_top_reg().id();
+        }
     }
 
-    extern "C" fn cb_intrinsic_name<A>(ctxt: *mut c_void, _intrinsic: u32) -> *mut c_char
+    extern "C" fn cb_intrinsic_name<A>(ctxt: *mut c_void, intrinsic: u32) -> *mut c_char
     where
         A: 'static + Architecture<Handle = CustomArchitectureHandle<A>> + Send + Sync,
     {
-        let _custom_arch = unsafe { &*(ctxt as *mut A) };
-        BnString::new("intrinsic").into_raw()
+        let custom_arch = unsafe { &*(ctxt as *mut A) };
+
+        match custom_arch.intrinsic_from_id(intrinsic) {
+            Some(intrinsic) => BnString::new(intrinsic.name().as_ref()).into_raw(),
+            None => BnString::new("invalid_intrinsic").into_raw(),
+        }
     }
 
     extern "C" fn cb_intrinsics<A>(ctxt: *mut c_void, count: *mut usize) -> *mut u32
     where
         A: 'static + Architecture<Handle = CustomArchitectureHandle<A>> + Send + Sync,
     {
-        let _custom_arch = unsafe { &*(ctxt as *mut A) };
+        let custom_arch = unsafe { &*(ctxt as *mut A) };
+        let intrinsics = custom_arch.intrinsics();
 
-        unsafe {
-            *count = 0;
-        }
-        ptr::null_mut()
+        alloc_register_list(intrinsics.iter().map(|i| i.id()), unsafe { &mut *count })
     }
 
     extern "C" fn cb_intrinsic_inputs<A>(
         ctxt: *mut c_void,
-        _intrinsic: u32,
+        intrinsic: u32,
         count: *mut usize,
     ) -> *mut BNNameAndType
     where
         A: 'static + Architecture<Handle = CustomArchitectureHandle<A>> + Send + Sync,
     {
-        let _custom_arch = unsafe { &*(ctxt as *mut A) };
+        let custom_arch = unsafe { &*(ctxt as *mut A) };
+
+        let inputs = match custom_arch.intrinsic_from_id(intrinsic) {
+            Some(intrinsic) => intrinsic.inputs(),
+            None => Vec::new(),
+        };
 
         unsafe {
-            *count = 0;
+            *count = inputs.len();
         }
-        ptr::null_mut()
+
+        if inputs.is_empty() {
+            return ptr::null_mut();
+        }
+
+        let raw: Box<[BNNameAndType]> = inputs
+            .iter()
+            .map(|input| {
+                let t = input.type_with_confidence();
+
+                BNNameAndType {
+                    name: BnString::new(input.name.as_str()).into_raw(),
+                    type_: unsafe { BNNewTypeReference(t.contents.handle) },
+                    typeConfidence: t.confidence,
+                }
+            })
+            .collect();
+
+        Box::into_raw(raw) as *mut BNNameAndType
     }
 
     extern "C" fn cb_free_name_and_types<A>(
-        ctxt: *mut c_void,
-        _nt: *mut BNNameAndType,
-        _count: usize,
+        _ctxt: *mut c_void,
+        nt: *mut BNNameAndType,
+        count: usize,
     ) where
         A: 'static + Architecture<Handle = CustomArchitectureHandle<A>> + Send + Sync,
     {
-        let _custom_arch = unsafe { &*(ctxt as *mut A) };
+        if nt.is_null() {
+            return;
+        }
+
+        unsafe {
+            let raw = Box::from_raw(ptr::slice_from_raw_parts_mut(nt, count));
+
+            for input in raw.iter() {
+                let _name = BnString::from_raw(input.name);
+                BNFreeType(input.type_);
+            }
+        }
     }
 
     extern "C" fn cb_intrinsic_outputs<A>(
         ctxt: *mut c_void,
-        _intrinsic: u32,
+        intrinsic: u32,
         count: *mut usize,
     ) -> *mut BNTypeWithConfidence
     where
         A: 'static + Architecture<Handle = CustomArchitectureHandle<A>> + Send + Sync,
     {
-        let _custom_arch = unsafe { &*(ctxt as *mut A) };
+        let custom_arch = unsafe { &*(ctxt as *mut A) };
+
+        let outputs = match custom_arch.intrinsic_from_id(intrinsic) {
+            Some(intrinsic) => intrinsic.outputs(),
+            None => Vec::new(),
+        };
 
         unsafe {
-            *count = 0;
+            *count = outputs.len();
         }
-        ptr::null_mut()
+
+        if outputs.is_empty() {
+            return ptr::null_mut();
+        }
+
+        let raw: Box<[BNTypeWithConfidence]> = outputs
+            .iter()
+            .map(|output| BNTypeWithConfidence {
+                type_: unsafe { BNNewTypeReference(output.contents.handle) },
+                confidence: output.confidence,
+            })
+            .collect();
+
+        Box::into_raw(raw) as *mut BNTypeWithConfidence
     }
 
     extern "C" fn cb_free_type_list<A>(
-        ctxt: *mut c_void,
-        _tl: *mut BNTypeWithConfidence,
-        _count: usize,
+        _ctxt: *mut c_void,
+        tl: *mut BNTypeWithConfidence,
+        count: usize,
     ) where
         A: 'static + Architecture<Handle = CustomArchitectureHandle<A>> + Send + Sync,
     {
-        let _custom_arch = unsafe { &*(ctxt as *mut A) };
+        if tl.is_null() {
+            return;
+        }
+
+        unsafe {
+            let raw = Box::from_raw(ptr::slice_from_raw_parts_mut(tl, count));
+
+            for output in raw.iter() {
+                BNFreeType(output.type_);
+            }
+        }
     }
 
-    // TODO : I have no idea what I'm doing and this is likely wrong!
-    extern "C" fn cb_can_assemble(_ctxt: *mut c_void) -> bool {
-        false
+    extern "C" fn cb_can_assemble<A>(ctxt: *mut c_void) -> bool
+    where
+        A: 'static + Architecture<Handle = CustomArchitectureHandle<A>> + Send + Sync,
+    {
+        let custom_arch = unsafe { &*(ctxt as *mut A) };
+        custom_arch.can_assemble()
     }
 
-    extern "C" fn cb_assemble(
-        _ctxt: *mut c_void,
-        _code: *const c_char,
-        _addr: u64,
-        _result: *mut BNDataBuffer,
+    extern "C" fn cb_assemble<A>(
+        ctxt: *mut c_void,
+        code: *const c_char,
+        addr: u64,
+        result: *mut BNDataBuffer,
         errors: *mut *mut c_char,
-    ) -> bool {
-        unsafe {
-            *errors = ptr::null_mut();
+    ) -> bool
+    where
+        A: 'static + Architecture<Handle = CustomArchitectureHandle<A>> + Send + Sync,
+    {
+        let custom_arch = unsafe { &*(ctxt as *mut A) };
+        let code = unsafe { CStr::from_ptr(code) }.to_string_lossy();
+
+        match custom_arch.assemble(&code, addr) {
+            Ok(mut bytes) => {
+                unsafe {
+                    BNSetDataBufferContents(result, bytes.as_mut_ptr() as *mut _, bytes.len());
+                    *errors = BnString::new("").into_raw();
+                }
+                true
+            }
+            Err(message) => {
+                unsafe {
+                    *errors = BnString::new(message).into_raw();
+                }
+                false
+            }
         }
-        false
     }
 
-    extern "C" fn cb_patch_unavailable(
-        _ctxt: *mut c_void,
-        _data: *const u8,
-        _addr: u64,
-        _len: usize,
-    ) -> bool {
-        false
+    extern "C" fn cb_is_never_branch_patch_available<A>(
+        ctxt: *mut c_void,
+        data: *const u8,
+        addr: u64,
+        len: usize,
+    ) -> bool
+    where
+        A: 'static + Architecture<Handle = CustomArchitectureHandle<A>> + Send + Sync,
+    {
+        let custom_arch = unsafe { &*(ctxt as *mut A) };
+        let data = unsafe { slice::from_raw_parts(data, len) };
+        custom_arch.is_never_branch_patch_available(data, addr)
     }
 
-    extern "C" fn cb_do_patch_unavailable(
-        _ctxt: *mut c_void,
-        _data: *mut u8,
-        _addr: u64,
-        _len: usize,
-    ) -> bool {
-        false
+    extern "C" fn cb_is_always_branch_patch_available<A>(
+        ctxt: *mut c_void,
+        data: *const u8,
+        addr: u64,
+        len: usize,
+    ) -> bool
+    where
+        A: 'static + Architecture<Handle = CustomArchitectureHandle<A>> + Send + Sync,
+    {
+        let custom_arch = unsafe { &*(ctxt as *mut A) };
+        let data = unsafe { slice::from_raw_parts(data, len) };
+        custom_arch.is_always_branch_patch_available(data, addr)
     }
 
-    extern "C" fn cb_skip_patch_unavailable(
-        _ctxt: *mut c_void,
-        _data: *mut u8,
-        _addr: u64,
-        _len: usize,
-        _val: u64,
-    ) -> bool {
-        false
+    extern "C" fn cb_is_invert_branch_patch_available<A>(
+        ctxt: *mut c_void,
+        data: *const u8,
+        addr: u64,
+        len: usize,
+    ) -> bool
+    where
+        A: 'static + Architecture<Handle = CustomArchitectureHandle<A>> + Send + Sync,
+    {
+        let custom_arch = unsafe { &*(ctxt as *mut A) };
+        let data = unsafe { slice::from_raw_parts(data, len) };
+        custom_arch.is_invert_branch_patch_available(data, addr)
     }
 
-    let name = name.as_bytes_with_nul();
+    extern "C" fn cb_is_skip_and_return_zero_patch_available<A>(
+        ctxt: *mut c_void,
+        data: *const u8,
+        addr: u64,
+        len: usize,
+    ) -> bool
+    where
+        A: 'static + Architecture<Handle = CustomArchitectureHandle<A>> + Send + Sync,
+    {
+        let custom_arch = unsafe { &*(ctxt as *mut A) };
+        let data = unsafe { slice::from_raw_parts(data, len) };
+        custom_arch.is_skip_and_return_zero_patch_available(data, addr)
+    }
+
+    extern "C" fn cb_is_skip_and_return_value_patch_available<A>(
+        ctxt: *mut c_void,
+        data: *const u8,
+        addr: u64,
+        len: usize,
+    ) -> bool
+    where
+        A: 'static + Architecture<Handle = CustomArchitectureHandle<A>> + Send + Sync,
+    {
+        let custom_arch = unsafe { &*(ctxt as *mut A) };
+        let data = unsafe { slice::from_raw_parts(data, len) };
+        custom_arch.is_skip_and_return_value_patch_available(data, addr)
+    }
+
+    extern "C" fn cb_convert_to_nop<A>(
+        ctxt: *mut c_void,
+        data: *mut u8,
+        addr: u64,
+        len: usize,
+    ) -> bool
+    where
+        A: 'static + Architecture<Handle = CustomArchitectureHandle<A>> + Send + Sync,
+    {
+        let custom_arch = unsafe { &*(ctxt as *mut A) };
+        let data = unsafe { slice::from_raw_parts_mut(data, len) };
+        custom_arch.convert_to_nop(data, addr)
+    }
+
+    extern "C" fn cb_always_branch<A>(
+        ctxt: *mut c_void,
+        data: *mut u8,
+        addr: u64,
+        len: usize,
+    ) -> bool
+    where
+        A: 'static + Architecture<Handle = CustomArchitectureHandle<A>> + Send + Sync,
+    {
+        let custom_arch = unsafe { &*(ctxt as *mut A) };
+        let data = unsafe { slice::from_raw_parts_mut(data, len) };
+        custom_arch.always_branch(data, addr)
+    }
+
+    extern "C" fn cb_invert_branch<A>(
+        ctxt: *mut c_void,
+        data: *mut u8,
+        addr: u64,
+        len: usize,
+    ) -> bool
+    where
+        A: 'static + Architecture<Handle = CustomArchitectureHandle<A>> + Send + Sync,
+    {
+        let custom_arch = unsafe { &*(ctxt as *mut A) };
+        let data = unsafe { slice::from_raw_parts_mut(data, len) };
+        custom_arch.invert_branch(data, addr)
+    }
+
+    extern "C" fn cb_skip_and_return_value<A>(
+        ctxt: *mut c_void,
+        data: *mut u8,
+        addr: u64,
+        len: usize,
+        val: u64,
+    ) -> bool
+    where
+        A: 'static + Architecture<Handle = CustomArchitectureHandle<A>> + Send + Sync,
+    {
+        let custom_arch = unsafe { &*(ctxt as *mut A) };
+        let data = unsafe { slice::from_raw_parts_mut(data, len) };
+        custom_arch.skip_and_return_value(data, addr, val)
+    }
 
     let uninit_arch = ArchitectureBuilder {
         arch: unsafe { zeroed() },
@@ -2117,29 +2747,26 @@ where
         getIntrinsicOutputs: Some(cb_intrinsic_outputs::<A>),
         freeTypeList: Some(cb_free_type_list::<A>),
 
-        canAssemble: Some(cb_can_assemble),
-        assemble: Some(cb_assemble),
+        canAssemble: Some(cb_can_assemble::<A>),
+        assemble: Some(cb_assemble::<A>),
 
-        isNeverBranchPatchAvailable: Some(cb_patch_unavailable),
-        isAlwaysBranchPatchAvailable: Some(cb_patch_unavailable),
-        isInvertBranchPatchAvailable: Some(cb_patch_unavailable),
-        isSkipAndReturnZeroPatchAvailable: Some(cb_patch_unavailable),
-        isSkipAndReturnValuePatchAvailable: Some(cb_patch_unavailable),
+        isNeverBranchPatchAvailable: Some(cb_is_never_branch_patch_available::<A>),
+        isAlwaysBranchPatchAvailable: Some(cb_is_always_branch_patch_available::<A>),
+        isInvertBranchPatchAvailable: Some(cb_is_invert_branch_patch_available::<A>),
+        isSkipAndReturnZeroPatchAvailable: Some(cb_is_skip_and_return_zero_patch_available::<A>),
+        isSkipAndReturnValuePatchAvailable: Some(cb_is_skip_and_return_value_patch_available::<A>),
 
-        convertToNop: Some(cb_do_patch_unavailable),
-        alwaysBranch: Some(cb_do_patch_unavailable),
-        invertBranch: Some(cb_do_patch_unavailable),
-        skipAndReturnValue: Some(cb_skip_patch_unavailable),
+        convertToNop: Some(cb_convert_to_nop::<A>),
+        alwaysBranch: Some(cb_always_branch::<A>),
+        invertBranch: Some(cb_invert_branch::<A>),
+        skipAndReturnValue: Some(cb_skip_and_return_value::<A>),
     };
 
-    unsafe {
-        let res =
-            BNRegisterArchitecture(name.as_ref().as_ptr() as *mut _, &mut custom_arch as *mut _);
+    let res = register(&mut custom_arch as *mut _);
 
-        assert!(!res.is_null());
+    assert!(!res.is_null());
 
-        &(*raw).arch
-    }
+    unsafe { &(*raw).arch }
 }
 
 pub struct CustomArchitectureHandle<A>
@@ -2183,3 +2810,394 @@ where
         unsafe { &*self.handle }
     }
 }
+
+/// Replacement behavior for selected methods of an existing architecture
+///
+/// Each method receives the architecture being extended or hooked as `base`, and defaults to
+/// delegating to it, so implementors only need to override the methods they care about.
+pub trait ArchitectureHook: 'static + Send + Sync + Sized {
+    fn instruction_info(
+        &self,
+        base: &CoreArchitecture,
+        data: &[u8],
+        addr: u64,
+    ) -> Option<InstructionInfo> {
+        base.instruction_info(data, addr)
+    }
+
+    fn instruction_text(
+        &self,
+        base: &CoreArchitecture,
+        data: &[u8],
+        addr: u64,
+    ) -> Option<(usize, Vec<InstructionTextToken>)> {
+        base.instruction_text(data, addr)
+            .map(|(len, tokens)| (len, tokens.into()))
+    }
+
+    fn instruction_llil(
+        &self,
+        base: &CoreArchitecture,
+        data: &[u8],
+        addr: u64,
+        il: &mut Lifter<CoreArchitecture>,
+    ) -> Option<(usize, bool)> {
+        base.instruction_llil(data, addr, il)
+    }
+}
+
+/// An `ArchitectureHook` registered over an existing architecture
+///
+/// Everything the hook does not override is answered by the base architecture.
+pub struct HookedArchitecture<H: ArchitectureHook> {
+    hook: H,
+    base: CoreArchitecture,
+    core: CoreArchitecture,
+    handle: CustomArchitectureHandle<HookedArchitecture<H>>,
+}
+
+impl<H: ArchitectureHook> HookedArchitecture<H> {
+    pub fn hook(&self) -> &H {
+        &self.hook
+    }
+
+    /// The architecture that non-overridden methods are delegated to
+    pub fn base(&self) -> &CoreArchitecture {
+        &self.base
+    }
+}
+
+impl<H: ArchitectureHook> AsRef<CoreArchitecture> for HookedArchitecture<H> {
+    fn as_ref(&self) -> &CoreArchitecture {
+        &self.core
+    }
+}
+
+impl<H: ArchitectureHook> Architecture for HookedArchitecture<H> {
+    type Handle = CustomArchitectureHandle<Self>;
+
+    type RegisterInfo = CoreRegisterInfo;
+    type Register = CoreRegister;
+    type RegisterStackInfo = CoreRegisterStackInfo;
+    type RegisterStack = CoreRegisterStack;
+    type Flag = CoreFlag;
+    type FlagWrite = CoreFlagWrite;
+    type FlagClass = CoreFlagClass;
+    type FlagGroup = CoreFlagGroup;
+    type Intrinsic = CoreIntrinsic;
+
+    type InstructionTextContainer = Vec<InstructionTextToken>;
+
+    fn endianness(&self) -> Endianness {
+        self.base.endianness()
+    }
+
+    fn address_size(&self) -> usize {
+        self.base.address_size()
+    }
+
+    fn default_integer_size(&self) -> usize {
+        self.base.default_integer_size()
+    }
+
+    fn instruction_alignment(&self) -> usize {
+        self.base.instruction_alignment()
+    }
+
+    fn max_instr_len(&self) -> usize {
+        self.base.max_instr_len()
+    }
+
+    fn opcode_display_len(&self) -> usize {
+        self.base.opcode_display_len()
+    }
+
+    fn associated_arch_by_addr(&self, addr: &mut u64) -> CoreArchitecture {
+        let arch = self.base.associated_arch_by_addr(addr);
+
+        // keep analysis on this architecture rather than falling back to the base
+        if arch == self.base {
+            self.core
+        } else {
+            arch
+        }
+    }
+
+    fn instruction_info(&self, data: &[u8], addr: u64) -> Option<InstructionInfo> {
+        self.hook.instruction_info(&self.base, data, addr)
+    }
+
+    fn instruction_text(
+        &self,
+        data: &[u8],
+        addr: u64,
+    ) -> Option<(usize, Vec<InstructionTextToken>)> {
+        self.hook.instruction_text(&self.base, data, addr)
+    }
+
+    fn instruction_llil(
+        &self,
+        data: &[u8],
+        addr: u64,
+        il: &mut Lifter<Self>,
+    ) -> Option<(usize, bool)> {
+        let mut base_il = unsafe { Lifter::from_raw(self.base, il.handle) };
+        self.hook
+            .instruction_llil(&self.base, data, addr, &mut base_il)
+    }
+
+    fn flag_write_llil<'a>(
+        &self,
+        flag: CoreFlag,
+        flag_write: CoreFlagWrite,
+        op: FlagWriteOp<CoreRegister>,
+        il: &'a mut Lifter<Self>,
+    ) -> Option<LiftedExpr<'a, Self>> {
+        let mut base_il = unsafe { Lifter::from_raw(self.base, il.handle) };
+        let expr_idx = self
+            .base
+            .flag_write_llil(flag, flag_write, op, &mut base_il)?
+            .expr_idx;
+
+        Some(LiftedExpr {
+            function: il,
+            expr_idx,
+            _ty: PhantomData,
+        })
+    }
+
+    fn flags_required_for_flag_condition(
+        &self,
+        condition: FlagCondition,
+        class: Option<CoreFlagClass>,
+    ) -> Vec<CoreFlag> {
+        self.base
+            .flags_required_for_flag_condition(condition, class)
+    }
+
+    fn flag_cond_llil<'a>(
+        &self,
+        cond: FlagCondition,
+        class: Option<CoreFlagClass>,
+        il: &'a mut Lifter<Self>,
+    ) -> Option<LiftedExpr<'a, Self>> {
+        let mut base_il = unsafe { Lifter::from_raw(self.base, il.handle) };
+        let expr_idx = self
+            .base
+            .flag_cond_llil(cond, class, &mut base_il)?
+            .expr_idx;
+
+        Some(LiftedExpr {
+            function: il,
+            expr_idx,
+            _ty: PhantomData,
+        })
+    }
+
+    fn flag_group_llil<'a>(
+        &self,
+        group: CoreFlagGroup,
+        il: &'a mut Lifter<Self>,
+    ) -> Option<LiftedExpr<'a, Self>> {
+        let mut base_il = unsafe { Lifter::from_raw(self.base, il.handle) };
+        let expr_idx = self.base.flag_group_llil(group, &mut base_il)?.expr_idx;
+
+        Some(LiftedExpr {
+            function: il,
+            expr_idx,
+            _ty: PhantomData,
+        })
+    }
+
+    fn registers_all(&self) -> Vec<CoreRegister> {
+        self.base.registers_all()
+    }
+
+    fn registers_full_width(&self) -> Vec<CoreRegister> {
+        self.base.registers_full_width()
+    }
+
+    fn registers_global(&self) -> Vec<CoreRegister> {
+        self.base.registers_global()
+    }
+
+    fn registers_system(&self) -> Vec<CoreRegister> {
+        self.base.registers_system()
+    }
+
+    fn register_stacks(&self) -> Vec<CoreRegisterStack> {
+        self.base.register_stacks()
+    }
+
+    fn flags(&self) -> Vec<CoreFlag> {
+        self.base.flags()
+    }
+
+    fn flag_write_types(&self) -> Vec<CoreFlagWrite> {
+        self.base.flag_write_types()
+    }
+
+    fn flag_classes(&self) -> Vec<CoreFlagClass> {
+        self.base.flag_classes()
+    }
+
+    fn flag_groups(&self) -> Vec<CoreFlagGroup> {
+        self.base.flag_groups()
+    }
+
+    fn intrinsics(&self) -> Vec<CoreIntrinsic> {
+        self.base.intrinsics()
+    }
+
+    fn stack_pointer_reg(&self) -> Option<CoreRegister> {
+        self.base.stack_pointer_reg()
+    }
+
+    fn link_reg(&self) -> Option<CoreRegister> {
+        self.base.link_reg()
+    }
+
+    fn register_from_id(&self, id: u32) -> Option<CoreRegister> {
+        self.base.register_from_id(id)
+    }
+
+    fn register_stack_from_id(&self, id: u32) -> Option<CoreRegisterStack> {
+        self.base.register_stack_from_id(id)
+    }
+
+    fn flag_from_id(&self, id: u32) -> Option<CoreFlag> {
+        self.base.flag_from_id(id)
+    }
+
+    fn flag_write_from_id(&self, id: u32) -> Option<CoreFlagWrite> {
+        self.base.flag_write_from_id(id)
+    }
+
+    fn flag_class_from_id(&self, id: u32) -> Option<CoreFlagClass> {
+        self.base.flag_class_from_id(id)
+    }
+
+    fn flag_group_from_id(&self, id: u32) -> Option<CoreFlagGroup> {
+        self.base.flag_group_from_id(id)
+    }
+
+    fn intrinsic_from_id(&self, id: u32) -> Option<CoreIntrinsic> {
+        self.base.intrinsic_from_id(id)
+    }
+
+    fn can_assemble(&self) -> bool {
+        self.base.can_assemble()
+    }
+
+    fn assemble(&self, code: &str, addr: u64) -> Result<Vec<u8>, String> {
+        self.base.assemble(code, addr)
+    }
+
+    fn is_never_branch_patch_available(&self, data: &[u8], addr: u64) -> bool {
+        self.base.is_never_branch_patch_available(data, addr)
+    }
+
+    fn is_always_branch_patch_available(&self, data: &[u8], addr: u64) -> bool {
+        self.base.is_always_branch_patch_available(data, addr)
+    }
+
+    fn is_invert_branch_patch_available(&self, data: &[u8], addr: u64) -> bool {
+        self.base.is_invert_branch_patch_available(data, addr)
+    }
+
+    fn is_skip_and_return_zero_patch_available(&self, data: &[u8], addr: u64) -> bool {
+        self.base
+            .is_skip_and_return_zero_patch_available(data, addr)
+    }
+
+    fn is_skip_and_return_value_patch_available(&self, data: &[u8], addr: u64) -> bool {
+        self.base
+            .is_skip_and_return_value_patch_available(data, addr)
+    }
+
+    fn convert_to_nop(&self, data: &mut [u8], addr: u64) -> bool {
+        self.base.convert_to_nop(data, addr)
+    }
+
+    fn always_branch(&self, data: &mut [u8], addr: u64) -> bool {
+        self.base.always_branch(data, addr)
+    }
+
+    fn invert_branch(&self, data: &mut [u8], addr: u64) -> bool {
+        self.base.invert_branch(data, addr)
+    }
+
+    fn skip_and_return_value(&self, data: &mut [u8], addr: u64, value: u64) -> bool {
+        self.base.skip_and_return_value(data, addr, value)
+    }
+
+    fn handle(&self) -> CustomArchitectureHandle<Self> {
+        self.handle
+    }
+}
+
+/// Registers a new architecture named `name` that behaves like `base` except where `hook`
+/// overrides it
+pub fn register_architecture_extension<S, H>(
+    name: S,
+    base: &CoreArchitecture,
+    hook: H,
+) -> &'static HookedArchitecture<H>
+where
+    S: BnStrCompatible,
+    H: ArchitectureHook,
+{
+    let name = name.as_bytes_with_nul();
+    let base = *base;
+
+    register_custom_architecture(
+        move |handle, core| HookedArchitecture {
+            hook,
+            base,
+            core,
+            handle,
+        },
+        |custom_arch| unsafe {
+            BNRegisterArchitectureExtension(name.as_ref().as_ptr() as *mut _, base.0, custom_arch)
+        },
+    )
+}
+
+/// Hooks `base` in place so that every user of the architecture sees the overrides in `hook`
+///
+/// Non-overridden methods are answered by the original implementation of `base`.
+pub fn register_architecture_hook<H>(
+    base: &CoreArchitecture,
+    hook: H,
+) -> &'static HookedArchitecture<H>
+where
+    H: ArchitectureHook,
+{
+    let base = *base;
+    let mut original = ptr::null_mut();
+
+    let handle = register_custom_architecture(
+        // `base` is not known until registration returns, and is patched in below
+        move |handle, core| HookedArchitecture {
+            hook,
+            base: core,
+            core,
+            handle,
+        },
+        |custom_arch| {
+            original = unsafe { BNRegisterArchitectureHook(base.0, custom_arch) };
+            original
+        },
+    )
+    .handle;
+
+    // the core returns the original implementation of `base`, and nothing calls into the hook
+    // until it is finalized
+    unsafe {
+        (*handle.handle).base = CoreArchitecture(original);
+
+        BNFinalizeArchitectureHook(base.0);
+
+        &*handle.handle
+    }
+}
@@ -0,0 +1,169 @@
+// Copyright 2021 Vector 35 Inc.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use binaryninjacore_sys::*;
+
+use std::io::{self, Read, Seek, SeekFrom};
+
+use crate::binaryview::{BinaryView, BinaryViewBase, BinaryViewExt};
+use crate::Endianness;
+
+use crate::rc::*;
+
+/// Cursor over the contents of a `BinaryView`
+///
+/// Multi-byte reads use the reader's endianness, which starts out as the
+/// view's default endianness.
+pub struct BinaryReader {
+    view: Ref<BinaryView>,
+    handle: *mut BNBinaryReader,
+}
+
+macro_rules! read_fn {
+    ($name:ident, $t:ty, $core:ident) => {
+        pub fn $name(&mut self) -> io::Result<$t> {
+            let mut res: $t = 0;
+
+            match unsafe { $core(self.handle, &mut res) } {
+                true => Ok(res),
+                false => Err(io::Error::new(
+                    io::ErrorKind::UnexpectedEof,
+                    "read past the end of the view",
+                )),
+            }
+        }
+    };
+}
+
+impl BinaryReader {
+    pub fn new(view: &BinaryView) -> Self {
+        Self::new_with_endianness(view, view.default_endianness())
+    }
+
+    pub fn new_with_endianness(view: &BinaryView, endianness: Endianness) -> Self {
+        let handle = unsafe { BNCreateBinaryReader(view.handle) };
+
+        unsafe {
+            BNSetBinaryReaderEndianness(handle, endianness);
+        }
+
+        Self {
+            view: view.to_owned(),
+            handle,
+        }
+    }
+
+    pub fn endianness(&self) -> Endianness {
+        unsafe { BNGetBinaryReaderEndianness(self.handle) }
+    }
+
+    pub fn set_endianness(&mut self, endianness: Endianness) {
+        unsafe { BNSetBinaryReaderEndianness(self.handle, endianness) }
+    }
+
+    pub fn offset(&self) -> u64 {
+        unsafe { BNGetReaderPosition(self.handle) }
+    }
+
+    pub fn seek_to_offset(&mut self, offset: u64) {
+        unsafe { BNSeekBinaryReader(self.handle, offset) }
+    }
+
+    pub fn seek_to_relative_offset(&mut self, offset: i64) {
+        unsafe { BNSeekBinaryReaderRelative(self.handle, offset) }
+    }
+
+    pub fn is_eof(&self) -> bool {
+        unsafe { BNIsEndOfFile(self.handle) }
+    }
+
+    read_fn!(read_u8, u8, BNRead8);
+
+    read_fn!(read_u16, u16, BNRead16);
+    read_fn!(read_u32, u32, BNRead32);
+    read_fn!(read_u64, u64, BNRead64);
+
+    read_fn!(read_u16_le, u16, BNReadLE16);
+    read_fn!(read_u32_le, u32, BNReadLE32);
+    read_fn!(read_u64_le, u64, BNReadLE64);
+
+    read_fn!(read_u16_be, u16, BNReadBE16);
+    read_fn!(read_u32_be, u32, BNReadBE32);
+    read_fn!(read_u64_be, u64, BNReadBE64);
+
+    pub fn read_i8(&mut self) -> io::Result<i8> {
+        self.read_u8().map(|v| v as i8)
+    }
+
+    pub fn read_i16(&mut self) -> io::Result<i16> {
+        self.read_u16().map(|v| v as i16)
+    }
+
+    pub fn read_i32(&mut self) -> io::Result<i32> {
+        self.read_u32().map(|v| v as i32)
+    }
+
+    pub fn read_i64(&mut self) -> io::Result<i64> {
+        self.read_u64().map(|v| v as i64)
+    }
+}
+
+impl Read for BinaryReader {
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        // go through the view rather than BNReadData so that a read
+        // running off the end of the view is short instead of failing
+        let len = self.view.read(buf, self.offset());
+        self.seek_to_relative_offset(len as i64);
+
+        Ok(len)
+    }
+}
+
+impl Seek for BinaryReader {
+    fn seek(&mut self, pos: SeekFrom) -> io::Result<u64> {
+        let (base, offset) = match pos {
+            SeekFrom::Start(offset) => {
+                self.seek_to_offset(offset);
+                return Ok(self.offset());
+            }
+            SeekFrom::Current(offset) => (self.offset(), offset),
+            SeekFrom::End(offset) => (self.view.end(), offset),
+        };
+
+        let target = if offset < 0 {
+            base.checked_sub(offset.unsigned_abs())
+        } else {
+            base.checked_add(offset as u64)
+        };
+
+        match target {
+            Some(target) => {
+                self.seek_to_offset(target);
+                Ok(self.offset())
+            }
+            None => Err(io::Error::new(
+                io::ErrorKind::InvalidInput,
+                "invalid seek to a negative or overflowing position",
+            )),
+        }
+    }
+}
+
+impl Drop for BinaryReader {
+    fn drop(&mut self) {
+        unsafe { BNFreeBinaryReader(self.handle) }
+    }
+}
+
+unsafe impl Send for BinaryReader {}
@@ -14,11 +14,16 @@
 
 use binaryninjacore_sys::*;
 
+pub use binaryninjacore_sys::BNAnalysisState as AnalysisState;
 pub use binaryninjacore_sys::BNModificationStatus as ModificationStatus;
+pub use binaryninjacore_sys::BNStringType as StringType;
 
 use std::ops;
+use std::os::raw::c_void;
 use std::ptr;
 use std::result;
+use std::slice;
+use std::sync::{Mutex, PoisonError};
 
 use crate::architecture::Architecture;
 use crate::architecture::CoreArchitecture;
@@ -29,12 +34,17 @@ use crate::fileaccessor::FileAccessor;
 use crate::filemetadata::FileMetadata;
 use crate::flowgraph::FlowGraph;
 use crate::function::{Function, NativeBlock};
+use crate::metadata::Metadata;
 use crate::platform::Platform;
+use crate::references::{data_reference_list, CodeReference};
+use crate::relocation::{relocation_range_list, RelocationInfo};
 use crate::section::{Section, SectionBuilder};
 use crate::segment::{Segment, SegmentBuilder};
 use crate::settings::Settings;
 use crate::symbol::{Symbol, SymbolType};
-use crate::types::{QualifiedName, Type};
+use crate::tags::{Tag, TagReference, TagType};
+use crate::typelibrary::TypeLibrary;
+use crate::types::{Conf, DataVariable, QualifiedName, Type};
 use crate::Endianness;
 
 use crate::rc::*;
@@ -222,6 +232,51 @@ pub trait BinaryViewExt: BinaryViewBase {
         }
     }
 
+    /// Starts analysis in the background and returns immediately
+    fn update_analysis(&self) {
+        unsafe {
+            BNUpdateAnalysis(self.as_ref().handle);
+        }
+    }
+
+    fn abort_analysis(&self) {
+        unsafe {
+            BNAbortAnalysis(self.as_ref().handle);
+        }
+    }
+
+    fn analysis_progress(&self) -> AnalysisProgress {
+        let progress = unsafe { BNGetAnalysisProgress(self.as_ref().handle) };
+
+        AnalysisProgress {
+            state: progress.state,
+            count: progress.count,
+            total: progress.total,
+        }
+    }
+
+    fn analysis_info(&self) -> AnalysisInfo {
+        unsafe {
+            let info = BNGetAnalysisInfo(self.as_ref().handle);
+            let res = AnalysisInfo::from_raw(&*info);
+
+            BNFreeAnalysisInfo(info);
+
+            res
+        }
+    }
+
+    /// Calls `callback` the next time analysis of the view completes
+    ///
+    /// The callback only fires once; add a new event to be notified of later
+    /// updates. Dropping the returned event cancels it if it has not fired yet.
+    fn add_analysis_completion_event<F>(&self, callback: F) -> AnalysisCompletionEvent<F>
+    where
+        F: 'static + FnMut() + Send,
+    {
+        AnalysisCompletionEvent::new(self.as_ref(), callback)
+    }
+
     fn default_arch(&self) -> Option<CoreArchitecture> {
         unsafe {
             let raw = BNGetDefaultArchitecture(self.as_ref().handle);
@@ -427,6 +482,116 @@ pub trait BinaryViewExt: BinaryViewBase {
         }
     }
 
+    fn has_data_variables(&self) -> bool {
+        unsafe { BNHasDataVariables(self.as_ref().handle) }
+    }
+
+    fn data_variables(&self) -> Array<DataVariable> {
+        unsafe {
+            let mut count = 0;
+            let vars = BNGetDataVariables(self.as_ref().handle, &mut count);
+
+            Array::new(vars, count, ())
+        }
+    }
+
+    fn data_variable_at(&self, addr: u64) -> Result<DataVariable> {
+        let mut var = BNDataVariable {
+            address: 0,
+            type_: ptr::null_mut(),
+            autoDiscovered: false,
+            typeConfidence: 0,
+        };
+
+        unsafe {
+            if !BNGetDataVariableAtAddress(self.as_ref().handle, addr, &mut var) {
+                return Err(());
+            }
+
+            let res = DataVariable::from_raw(&var);
+            BNFreeType(var.type_);
+
+            Ok(res)
+        }
+    }
+
+    fn define_auto_data_var<'a, T: Into<Conf<&'a Type>>>(&self, addr: u64, ty: T) {
+        let mut ty: BNTypeWithConfidence = ty.into().into();
+
+        unsafe {
+            BNDefineDataVariable(self.as_ref().handle, addr, &mut ty);
+        }
+    }
+
+    fn define_user_data_var<'a, T: Into<Conf<&'a Type>>>(&self, addr: u64, ty: T) {
+        let mut ty: BNTypeWithConfidence = ty.into().into();
+
+        unsafe {
+            BNDefineUserDataVariable(self.as_ref().handle, addr, &mut ty);
+        }
+    }
+
+    fn undefine_auto_data_var(&self, addr: u64) {
+        unsafe {
+            BNUndefineDataVariable(self.as_ref().handle, addr);
+        }
+    }
+
+    fn undefine_user_data_var(&self, addr: u64) {
+        unsafe {
+            BNUndefineUserDataVariable(self.as_ref().handle, addr);
+        }
+    }
+
+    /// Start of the first data variable after `addr`, or the end of the view if there is none
+    fn next_data_var_start_after(&self, addr: u64) -> u64 {
+        unsafe { BNGetNextDataVariableStartAfterAddress(self.as_ref().handle, addr) }
+    }
+
+    /// Start of the last data variable before `addr`, or the start of the view if there is none
+    fn previous_data_var_start_before(&self, addr: u64) -> u64 {
+        unsafe { BNGetPreviousDataVariableStartBeforeAddress(self.as_ref().handle, addr) }
+    }
+
+    fn strings(&self) -> Array<StringReference> {
+        unsafe {
+            let mut count = 0;
+            let strings = BNGetStrings(self.as_ref().handle, &mut count);
+
+            Array::new(strings, count, ())
+        }
+    }
+
+    fn strings_in_range(&self, range: ops::Range<u64>) -> Array<StringReference> {
+        unsafe {
+            let mut count = 0;
+            let strings = BNGetStringsInRange(
+                self.as_ref().handle,
+                range.start,
+                range.end - range.start,
+                &mut count,
+            );
+
+            Array::new(strings, count, ())
+        }
+    }
+
+    fn string_at(&self, addr: u64) -> Result<StringReference> {
+        let mut raw = BNStringReference {
+            type_: StringType::AsciiString,
+            start: 0,
+            length: 0,
+        };
+
+        unsafe {
+            if !BNGetStringAtAddress(self.as_ref().handle, addr, &mut raw) {
+                return Err(());
+            }
+        }
+
+        Ok(StringReference::from_raw(&raw))
+    }
+
     fn segments(&self) -> Array<Segment> {
         unsafe {
             let mut count = 0;
@@ -589,6 +754,391 @@ pub trait BinaryViewExt: BinaryViewBase {
         }
     }
 
+    fn code_refs_to(&self, addr: u64) -> Array<CodeReference> {
+        unsafe {
+            let mut count = 0;
+            let refs = BNGetCodeReferences(self.as_ref().handle, addr, &mut count);
+
+            Array::new(refs, count, ())
+        }
+    }
+
+    fn code_refs_to_range(&self, range: ops::Range<u64>) -> Array<CodeReference> {
+        unsafe {
+            let mut count = 0;
+            let refs = BNGetCodeReferencesInRange(
+                self.as_ref().handle,
+                range.start,
+                range.end - range.start,
+                &mut count,
+            );
+
+            Array::new(refs, count, ())
+        }
+    }
+
+    fn data_refs_to(&self, addr: u64) -> Vec<u64> {
+        unsafe {
+            let mut count = 0;
+            let refs = BNGetDataReferences(self.as_ref().handle, addr, &mut count);
+
+            data_reference_list(refs, count)
+        }
+    }
+
+    fn data_refs_from(&self, addr: u64) -> Vec<u64> {
+        unsafe {
+            let mut count = 0;
+            let refs = BNGetDataReferencesFrom(self.as_ref().handle, addr, &mut count);
+
+            data_reference_list(refs, count)
+        }
+    }
+
+    fn add_user_data_ref(&self, from_addr: u64, to_addr: u64) {
+        unsafe {
+            BNAddUserDataReference(self.as_ref().handle, from_addr, to_addr);
+        }
+    }
+
+    fn remove_user_data_ref(&self, from_addr: u64, to_addr: u64) {
+        unsafe {
+            BNRemoveUserDataReference(self.as_ref().handle, from_addr, to_addr);
+        }
+    }
+
+    /// Creates a tag type and registers it with this view
+    fn create_tag_type<N: BnStrCompatible, I: BnStrCompatible>(
+        &self,
+        name: N,
+        icon: I,
+    ) -> Ref<TagType> {
+        let tag_type = TagType::create(self.as_ref(), name, icon);
+
+        unsafe {
+            BNAddTagType(self.as_ref().handle, tag_type.handle);
+        }
+
+        tag_type
+    }
+
+    fn remove_tag_type(&self, tag_type: &TagType) {
+        unsafe { BNRemoveTagType(self.as_ref().handle, tag_type.handle) }
+    }
+
+    fn tag_type_by_name<S: BnStrCompatible>(&self, name: S) -> Option<Ref<TagType>> {
+        let name = name.as_bytes_with_nul();
+
+        unsafe {
+            let handle = BNGetTagType(self.as_ref().handle, name.as_ref().as_ptr() as *mut _);
+
+            if handle.is_null() {
+                return None;
+            }
+
+            Some(Ref::new(TagType::from_raw(handle)))
+        }
+    }
+
+    fn tag_types(&self) -> Array<TagType> {
+        unsafe {
+            let mut count = 0;
+            let tag_types = BNGetTagTypes(self.as_ref().handle, &mut count);
+
+            Array::new(tag_types, count, ())
+        }
+    }
+
+    /// Creates a tag and registers it with this view
+    ///
+    /// The tag still has to be placed with one of the `add_*_tag` methods.
+    fn create_tag<S: BnStrCompatible>(&self, tag_type: &TagType, data: S, user: bool) -> Ref<Tag> {
+        let tag = Tag::new(tag_type, data);
+
+        unsafe {
+            BNAddTag(self.as_ref().handle, tag.handle, user);
+        }
+
+        tag
+    }
+
+    fn remove_tag(&self, tag: &Tag, user: bool) {
+        unsafe { BNRemoveTag(self.as_ref().handle, tag.handle, user) }
+    }
+
+    fn tag_by_id<S: BnStrCompatible>(&self, id: S) -> Option<Ref<Tag>> {
+        let id = id.as_bytes_with_nul();
+
+        unsafe {
+            let handle = BNGetTag(self.as_ref().handle, id.as_ref().as_ptr() as *mut _);
+
+            if handle.is_null() {
+                return None;
+            }
+
+            Some(Ref::new(Tag::from_raw(handle)))
+        }
+    }
+
+    fn add_user_data_tag(&self, addr: u64, tag: &Tag) {
+        unsafe { BNAddUserDataTag(self.as_ref().handle, addr, tag.handle) }
+    }
+
+    fn add_auto_data_tag(&self, addr: u64, tag: &Tag) {
+        unsafe { BNAddAutoDataTag(self.as_ref().handle, addr, tag.handle) }
+    }
+
+    fn remove_user_data_tag(&self, addr: u64, tag: &Tag) {
+        unsafe { BNRemoveUserDataTag(self.as_ref().handle, addr, tag.handle) }
+    }
+
+    fn remove_auto_data_tag(&self, addr: u64, tag: &Tag) {
+        unsafe { BNRemoveAutoDataTag(self.as_ref().handle, addr, tag.handle) }
+    }
+
+    fn data_tags_at(&self, addr: u64) -> Array<Tag> {
+        unsafe {
+            let mut count = 0;
+            let tags = BNGetDataTags(self.as_ref().handle, addr, &mut count);
+
+            Array::new(tags, count, ())
+        }
+    }
+
+    fn data_tags_in_range(&self, range: ops::Range<u64>) -> Array<TagReference> {
+        unsafe {
+            let mut count = 0;
+            let refs =
+                BNGetDataTagsInRange(self.as_ref().handle, range.start, range.end, &mut count);
+
+            Array::new(refs, count, ())
+        }
+    }
+
+    /// Every tag placed anywhere in this view
+    fn tag_references(&self) -> Array<TagReference> {
+        unsafe {
+            let mut count = 0;
+            let refs = BNGetAllTagReferences(self.as_ref().handle, &mut count);
+
+            Array::new(refs, count, ())
+        }
+    }
+
+    fn tag_references_of_type(&self, tag_type: &TagType) -> Array<TagReference> {
+        unsafe {
+            let mut count = 0;
+            let refs =
+                BNGetAllTagReferencesOfType(self.as_ref().handle, tag_type.handle, &mut count);
+
+            Array::new(refs, count, ())
+        }
+    }
+
+    /// Stores `value` under `key`; unless `is_auto` is set it is saved with the database
+    fn store_metadata<S: BnStrCompatible, V: Into<Ref<Metadata>>>(
+        &self,
+        key: S,
+        value: V,
+        is_auto: bool,
+    ) {
+        let key = key.as_bytes_with_nul();
+        let value: Ref<Metadata> = value.into();
+
+        unsafe {
+            BNBinaryViewStoreMetadata(
+                self.as_ref().handle,
+                key.as_ref().as_ptr() as *const _,
+                value.handle,
+                is_auto,
+            );
+        }
+    }
+
+    fn query_metadata<S: BnStrCompatible>(&self, key: S) -> Option<Ref<Metadata>> {
+        let key = key.as_bytes_with_nul();
+
+        unsafe {
+            let value =
+                BNBinaryViewQueryMetadata(self.as_ref().handle, key.as_ref().as_ptr() as *const _);
+
+            if value.is_null() {
+                return None;
+            }
+
+            Some(Ref::new(Metadata::from_raw(value)))
+        }
+    }
+
+    fn remove_metadata<S: BnStrCompatible>(&self, key: S) {
+        let key = key.as_bytes_with_nul();
+
+        unsafe {
+            BNBinaryViewRemoveMetadata(self.as_ref().handle, key.as_ref().as_ptr() as *const _);
+        }
+    }
+
+    fn define_relocation<A: Architecture>(
+        &self,
+        arch: &A,
+        info: &RelocationInfo,
+        target: u64,
+        reloc: u64,
+    ) {
+        let mut raw = info.as_raw();
+
+        unsafe {
+            BNDefineRelocation(
+                self.as_ref().handle,
+                arch.as_ref().0,
+                &mut raw,
+                target,
+                reloc,
+            )
+        }
+    }
+
+    /// Defines a relocation whose target is the address of `target`
+    fn define_symbol_relocation<A: Architecture>(
+        &self,
+        arch: &A,
+        info: &RelocationInfo,
+        target: &Symbol,
+        reloc: u64,
+    ) {
+        let mut raw = info.as_raw();
+
+        unsafe {
+            BNDefineSymbolRelocation(
+                self.as_ref().handle,
+                arch.as_ref().0,
+                &mut raw,
+                target.handle,
+                reloc,
+            )
+        }
+    }
+
+    /// Address ranges that have been modified by relocations
+    fn relocation_ranges(&self) -> Vec<ops::Range<u64>> {
+        unsafe {
+            let mut count = 0;
+            let ranges = BNGetRelocationRanges(self.as_ref().handle, &mut count);
+
+            relocation_range_list(ranges, count)
+        }
+    }
+
+    fn relocation_ranges_at(&self, addr: u64) -> Vec<ops::Range<u64>> {
+        unsafe {
+            let mut count = 0;
+            let ranges = BNGetRelocationRangesAtAddress(self.as_ref().handle, addr, &mut count);
+
+            relocation_range_list(ranges, count)
+        }
+    }
+
+    fn add_type_library(&self, lib: &TypeLibrary) {
+        unsafe { BNAddBinaryViewTypeLibrary(self.as_ref().handle, lib.handle) }
+    }
+
+    fn type_library_by_name<S: BnStrCompatible>(&self, name: S) -> Option<Ref<TypeLibrary>> {
+        let name = name.as_bytes_with_nul();
+
+        unsafe {
+            TypeLibrary::ref_from_raw(BNGetBinaryViewTypeLibrary(
+                self.as_ref().handle,
+                name.as_ref().as_ptr() as *const _,
+            ))
+        }
+    }
+
+    fn type_libraries(&self) -> Array<TypeLibrary> {
+        unsafe {
+            let mut count = 0;
+            let libs = BNGetBinaryViewTypeLibraries(self.as_ref().handle, &mut count);
+
+            Array::new(libs, count, ())
+        }
+    }
+
+    /// Copies a named type from `lib` into the view, returning a reference to it
+    fn import_type_library_type<N: Into<QualifiedName>>(
+        &self,
+        lib: &TypeLibrary,
+        name: N,
+    ) -> Option<Ref<Type>> {
+        let mut name = name.into();
+
+        unsafe {
+            let t =
+                BNBinaryViewImportTypeLibraryType(self.as_ref().handle, lib.handle, &mut name.0);
+
+            if t.is_null() {
+                return None;
+            }
+
+            Some(Type::ref_from_raw(t))
+        }
+    }
+
+    /// Looks up a function or data declaration in `lib`, importing any types it depends on
+    fn import_type_library_object<N: Into<QualifiedName>>(
+        &self,
+        lib: &TypeLibrary,
+        name: N,
+    ) -> Option<Ref<Type>> {
+        let mut name = name.into();
+
+        unsafe {
+            let t =
+                BNBinaryViewImportTypeLibraryObject(self.as_ref().handle, lib.handle, &mut name.0);
+
+            if t.is_null() {
+                return None;
+            }
+
+            Some(Type::ref_from_raw(t))
+        }
+    }
+
+    /// Adds a named type to `lib`, along with any types from the view it refers to
+    fn export_type_to_type_library<N: Into<QualifiedName>>(
+        &self,
+        lib: &TypeLibrary,
+        name: N,
+        t: &Type,
+    ) {
+        let mut name = name.into();
+
+        unsafe {
+            BNBinaryViewExportTypeToTypeLibrary(
+                self.as_ref().handle,
+                lib.handle,
+                &mut name.0,
+                t.handle,
+            )
+        }
+    }
+
+    fn export_object_to_type_library<N: Into<QualifiedName>>(
+        &self,
+        lib: &TypeLibrary,
+        name: N,
+        t: &Type,
+    ) {
+        let mut name = name.into();
+
+        unsafe {
+            BNBinaryViewExportObjectToTypeLibrary(
+                self.as_ref().handle,
+                lib.handle,
+                &mut name.0,
+                t.handle,
+            )
+        }
+    }
+
     fn is_new_auto_function_analysis_suppressed(&self) -> bool {
         unsafe { BNGetNewAutoFunctionAnalysisSuppressed(self.as_ref().handle) }
     }
@@ -817,3 +1367,199 @@ impl ToOwned for BinaryView {
 
 unsafe impl Send for BinaryView {}
 unsafe impl Sync for BinaryView {}
+
+/// A string found by the core's string analysis
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+pub struct StringReference {
+    pub t: StringType,
+    pub start: u64,
+    /// Length in bytes, not characters
+    pub length: usize,
+}
+
+impl StringReference {
+    pub(crate) fn from_raw(raw: &BNStringReference) -> Self {
+        Self {
+            t: raw.type_,
+            start: raw.start,
+            length: raw.length,
+        }
+    }
+
+    /// Reads the string out of `view` and decodes it according to its type
+    ///
+    /// Wide strings are decoded using the view's default endianness, and
+    /// anything that doesn't decode cleanly is replaced with U+FFFD.
+    pub fn value<V: BinaryViewExt>(&self, view: &V) -> String {
+        let data = view.read_vec(self.start, self.length);
+        let big_endian = view.default_endianness() == Endianness::BigEndian;
+
+        match self.t {
+            StringType::AsciiString | StringType::Utf8String => {
+                String::from_utf8_lossy(&data).into_owned()
+            }
+            StringType::Utf16String => {
+                let units: Vec<u16> = data
+                    .chunks_exact(2)
+                    .map(|c| {
+                        let bytes = [c[0], c[1]];
+
+                        match big_endian {
+                            true => u16::from_be_bytes(bytes),
+                            false => u16::from_le_bytes(bytes),
+                        }
+                    })
+                    .collect();
+
+                String::from_utf16_lossy(&units)
+            }
+            StringType::Utf32String => data
+                .chunks_exact(4)
+                .map(|c| {
+                    let bytes = [c[0], c[1], c[2], c[3]];
+                    let unit = match big_endian {
+                        true => u32::from_be_bytes(bytes),
+                        false => u32::from_le_bytes(bytes),
+                    };
+
+                    std::char::from_u32(unit).unwrap_or(std::char::REPLACEMENT_CHARACTER)
+                })
+                .collect(),
+        }
+    }
+}
+
+unsafe impl CoreOwnedArrayProvider for StringReference {
+    type Raw = BNStringReference;
+    type Context = ();
+
+    unsafe fn free(raw: *mut Self::Raw, _count: usize, _context: &Self::Context) {
+        BNFreeStringReferenceList(raw);
+    }
+}
+
+unsafe impl<'a> CoreOwnedArrayWrapper<'a> for StringReference {
+    type Wrapped = StringReference;
+
+    unsafe fn wrap_raw(raw: &'a Self::Raw, _context: &'a Self::Context) -> Self::Wrapped {
+        StringReference::from_raw(raw)
+    }
+}
+
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+pub struct AnalysisProgress {
+    pub state: AnalysisState,
+    pub count: usize,
+    pub total: usize,
+}
+
+/// A function that is currently being analyzed
+pub struct ActiveAnalysisInfo {
+    pub function: Ref<Function>,
+    /// Time spent analyzing the function so far, in milliseconds
+    pub analysis_time: u64,
+    pub update_count: usize,
+    pub submit_count: usize,
+}
+
+pub struct AnalysisInfo {
+    pub state: AnalysisState,
+    /// Time spent in the current analysis pass, in milliseconds
+    pub analysis_time: u64,
+    pub active_info: Vec<ActiveAnalysisInfo>,
+}
+
+impl AnalysisInfo {
+    pub(crate) unsafe fn from_raw(raw: &BNAnalysisInfo) -> Self {
+        let active_info = if raw.activeInfo.is_null() {
+            Vec::new()
+        } else {
+            slice::from_raw_parts(raw.activeInfo, raw.count)
+                .iter()
+                .map(|info| ActiveAnalysisInfo {
+                    function: Function::from_raw(BNNewFunctionReference(info.func)),
+                    analysis_time: info.analysisTime,
+                    update_count: info.updateCount,
+                    submit_count: info.submitCount,
+                })
+                .collect()
+        };
+
+        Self {
+            state: raw.state,
+            analysis_time: raw.analysisTime,
+            active_info,
+        }
+    }
+}
+
+/// A callback waiting for analysis of a view to complete
+///
+/// Dropping the event cancels the callback if it has not fired yet.
+#[must_use]
+pub struct AnalysisCompletionEvent<F>
+where
+    F: 'static + FnMut() + Send,
+{
+    handle: *mut BNAnalysisCompletionEvent,
+    ctxt: *mut Mutex<Option<F>>,
+}
+
+unsafe impl<F> Send for AnalysisCompletionEvent<F> where F: 'static + FnMut() + Send {}
+unsafe impl<F> Sync for AnalysisCompletionEvent<F> where F: 'static + FnMut() + Send {}
+
+impl<F> AnalysisCompletionEvent<F>
+where
+    F: 'static + FnMut() + Send,
+{
+    fn new(view: &BinaryView, callback: F) -> Self {
+        extern "C" fn cb_complete<F>(ctxt: *mut c_void)
+        where
+            F: 'static + FnMut() + Send,
+        {
+            ffi_wrap!("AnalysisCompletionEvent::complete", unsafe {
+                let callback = &*(ctxt as *mut Mutex<Option<F>>);
+                let mut callback = callback.lock().unwrap_or_else(PoisonError::into_inner);
+
+                if let Some(ref mut callback) = *callback {
+                    callback();
+                }
+            })
+        }
+
+        let ctxt = Box::into_raw(Box::new(Mutex::new(Some(callback))));
+        let handle = unsafe {
+            BNAddAnalysisCompletionEvent(view.handle, ctxt as *mut _, Some(cb_complete::<F>))
+        };
+
+        Self { handle, ctxt }
+    }
+
+    pub fn cancel(&self) {
+        unsafe {
+            BNCancelAnalysisCompletionEvent(self.handle);
+        }
+
+        // waits out a callback that is already running on an analysis thread
+        let callback = unsafe { &*self.ctxt };
+        let _callback = callback
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner)
+            .take();
+    }
+}
+
+impl<F> Drop for AnalysisCompletionEvent<F>
+where
+    F: 'static + FnMut() + Send,
+{
+    fn drop(&mut self) {
+        self.cancel();
+
+        unsafe {
+            BNFreeAnalysisCompletionEvent(self.handle);
+
+            let _callback = Box::from_raw(self.ctxt);
+        }
+    }
+}
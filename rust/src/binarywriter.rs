@@ -0,0 +1,165 @@
+// Copyright 2021 Vector 35 Inc.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use binaryninjacore_sys::*;
+
+use std::io::{self, Seek, SeekFrom, Write};
+
+use crate::binaryview::{BinaryView, BinaryViewBase, BinaryViewExt};
+use crate::Endianness;
+
+use crate::rc::*;
+
+/// Cursor for modifying the contents of a `BinaryView`
+///
+/// Multi-byte writes use the writer's endianness, which starts out as the
+/// view's default endianness.
+pub struct BinaryWriter {
+    view: Ref<BinaryView>,
+    handle: *mut BNBinaryWriter,
+}
+
+macro_rules! write_fn {
+    ($name:ident, $t:ty, $core:ident) => {
+        pub fn $name(&mut self, value: $t) -> io::Result<()> {
+            match unsafe { $core(self.handle, value) } {
+                true => Ok(()),
+                false => Err(io::Error::new(
+                    io::ErrorKind::WriteZero,
+                    "write past the end of the view",
+                )),
+            }
+        }
+    };
+}
+
+impl BinaryWriter {
+    pub fn new(view: &BinaryView) -> Self {
+        Self::new_with_endianness(view, view.default_endianness())
+    }
+
+    pub fn new_with_endianness(view: &BinaryView, endianness: Endianness) -> Self {
+        let handle = unsafe { BNCreateBinaryWriter(view.handle) };
+
+        unsafe {
+            BNSetBinaryWriterEndianness(handle, endianness);
+        }
+
+        Self {
+            view: view.to_owned(),
+            handle,
+        }
+    }
+
+    pub fn endianness(&self) -> Endianness {
+        unsafe { BNGetBinaryWriterEndianness(self.handle) }
+    }
+
+    pub fn set_endianness(&mut self, endianness: Endianness) {
+        unsafe { BNSetBinaryWriterEndianness(self.handle, endianness) }
+    }
+
+    pub fn offset(&self) -> u64 {
+        unsafe { BNGetWriterPosition(self.handle) }
+    }
+
+    pub fn seek_to_offset(&mut self, offset: u64) {
+        unsafe { BNSeekBinaryWriter(self.handle, offset) }
+    }
+
+    pub fn seek_to_relative_offset(&mut self, offset: i64) {
+        unsafe { BNSeekBinaryWriterRelative(self.handle, offset) }
+    }
+
+    write_fn!(write_u8, u8, BNWrite8);
+
+    write_fn!(write_u16, u16, BNWrite16);
+    write_fn!(write_u32, u32, BNWrite32);
+    write_fn!(write_u64, u64, BNWrite64);
+
+    write_fn!(write_u16_le, u16, BNWriteLE16);
+    write_fn!(write_u32_le, u32, BNWriteLE32);
+    write_fn!(write_u64_le, u64, BNWriteLE64);
+
+    write_fn!(write_u16_be, u16, BNWriteBE16);
+    write_fn!(write_u32_be, u32, BNWriteBE32);
+    write_fn!(write_u64_be, u64, BNWriteBE64);
+
+    pub fn write_i8(&mut self, value: i8) -> io::Result<()> {
+        self.write_u8(value as u8)
+    }
+
+    pub fn write_i16(&mut self, value: i16) -> io::Result<()> {
+        self.write_u16(value as u16)
+    }
+
+    pub fn write_i32(&mut self, value: i32) -> io::Result<()> {
+        self.write_u32(value as u32)
+    }
+
+    pub fn write_i64(&mut self, value: i64) -> io::Result<()> {
+        self.write_u64(value as u64)
+    }
+}
+
+impl Write for BinaryWriter {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        let len = self.view.write(self.offset(), buf);
+        self.seek_to_relative_offset(len as i64);
+
+        Ok(len)
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        Ok(())
+    }
+}
+
+impl Seek for BinaryWriter {
+    fn seek(&mut self, pos: SeekFrom) -> io::Result<u64> {
+        let (base, offset) = match pos {
+            SeekFrom::Start(offset) => {
+                self.seek_to_offset(offset);
+                return Ok(self.offset());
+            }
+            SeekFrom::Current(offset) => (self.offset(), offset),
+            SeekFrom::End(offset) => (self.view.end(), offset),
+        };
+
+        let target = if offset < 0 {
+            base.checked_sub(offset.unsigned_abs())
+        } else {
+            base.checked_add(offset as u64)
+        };
+
+        match target {
+            Some(target) => {
+                self.seek_to_offset(target);
+                Ok(self.offset())
+            }
+            None => Err(io::Error::new(
+                io::ErrorKind::InvalidInput,
+                "invalid seek to a negative or overflowing position",
+            )),
+        }
+    }
+}
+
+impl Drop for BinaryWriter {
+    fn drop(&mut self) {
+        unsafe { BNFreeBinaryWriter(self.handle) }
+    }
+}
+
+unsafe impl Send for BinaryWriter {}
@@ -0,0 +1,611 @@
+// Copyright 2021 Vector 35 Inc.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! The contents of a `.bndb`: its snapshot history and key-value stores.
+
+use binaryninjacore_sys::*;
+
+use std::fmt;
+use std::slice;
+
+use crate::databuffer::DataBuffer;
+use crate::filemetadata::FileMetadata;
+
+use crate::rc::*;
+use crate::string::*;
+
+pub type ActionType = BNActionType;
+
+/// A database backing a `FileMetadata`
+///
+/// Snapshots form a DAG; each save adds a snapshot whose parents are the
+/// snapshots it was based on.
+#[derive(PartialEq, Eq, Hash)]
+pub struct Database {
+    pub(crate) handle: *mut BNDatabase,
+}
+
+impl Database {
+    pub(crate) unsafe fn from_raw(handle: *mut BNDatabase) -> Self {
+        debug_assert!(!handle.is_null());
+
+        Self { handle }
+    }
+
+    pub fn snapshots(&self) -> Array<Snapshot> {
+        unsafe {
+            let mut count = 0;
+            let snapshots = BNGetDatabaseSnapshots(self.handle, &mut count);
+
+            Array::new(snapshots, count, ())
+        }
+    }
+
+    pub fn snapshot(&self, id: i64) -> Option<Ref<Snapshot>> {
+        unsafe {
+            let snapshot = BNGetDatabaseSnapshot(self.handle, id);
+
+            if snapshot.is_null() {
+                return None;
+            }
+
+            Some(Ref::new(Snapshot::from_raw(snapshot)))
+        }
+    }
+
+    pub fn current_snapshot(&self) -> Option<Ref<Snapshot>> {
+        unsafe {
+            let snapshot = BNGetDatabaseCurrentSnapshot(self.handle);
+
+            if snapshot.is_null() {
+                return None;
+            }
+
+            Some(Ref::new(Snapshot::from_raw(snapshot)))
+        }
+    }
+
+    pub fn set_current_snapshot(&self, id: i64) {
+        unsafe { BNSetDatabaseCurrentSnapshot(self.handle, id) }
+    }
+
+    pub fn remove_snapshot(&self, id: i64) -> Result<(), ()> {
+        match unsafe { BNRemoveDatabaseSnapshot(self.handle, id) } {
+            true => Ok(()),
+            false => Err(()),
+        }
+    }
+
+    pub fn global_keys(&self) -> Array<BnString> {
+        unsafe {
+            let mut count = 0;
+            let keys = BNGetDatabaseGlobalKeys(self.handle, &mut count);
+
+            Array::new(keys, count, ())
+        }
+    }
+
+    pub fn has_global<S: BnStrCompatible>(&self, key: S) -> bool {
+        let key = key.as_bytes_with_nul();
+
+        unsafe { BNDatabaseHasGlobal(self.handle, key.as_ref().as_ptr() as *const _) != 0 }
+    }
+
+    pub fn read_global<S: BnStrCompatible>(&self, key: S) -> Option<BnString> {
+        let key = key.as_bytes_with_nul();
+
+        unsafe {
+            let value = BNReadDatabaseGlobal(self.handle, key.as_ref().as_ptr() as *const _);
+
+            if value.is_null() {
+                return None;
+            }
+
+            Some(BnString::from_raw(value))
+        }
+    }
+
+    pub fn write_global<K: BnStrCompatible, V: BnStrCompatible>(
+        &self,
+        key: K,
+        value: V,
+    ) -> Result<(), ()> {
+        let key = key.as_bytes_with_nul();
+        let value = value.as_bytes_with_nul();
+
+        let res = unsafe {
+            BNWriteDatabaseGlobal(
+                self.handle,
+                key.as_ref().as_ptr() as *const _,
+                value.as_ref().as_ptr() as *const _,
+            )
+        };
+
+        match res {
+            true => Ok(()),
+            false => Err(()),
+        }
+    }
+
+    pub fn read_global_data<S: BnStrCompatible>(&self, key: S) -> Option<DataBuffer> {
+        let key = key.as_bytes_with_nul();
+
+        unsafe {
+            let value = BNReadDatabaseGlobalData(self.handle, key.as_ref().as_ptr() as *const _);
+
+            if value.is_null() {
+                return None;
+            }
+
+            Some(DataBuffer::from_raw(value))
+        }
+    }
+
+    pub fn write_global_data<S: BnStrCompatible>(
+        &self,
+        key: S,
+        value: &DataBuffer,
+    ) -> Result<(), ()> {
+        let key = key.as_bytes_with_nul();
+
+        let res = unsafe {
+            BNWriteDatabaseGlobalData(
+                self.handle,
+                key.as_ref().as_ptr() as *const _,
+                value.as_raw(),
+            )
+        };
+
+        match res {
+            true => Ok(()),
+            false => Err(()),
+        }
+    }
+
+    pub fn file(&self) -> Ref<FileMetadata> {
+        unsafe { Ref::new(FileMetadata::from_raw(BNGetDatabaseFile(self.handle))) }
+    }
+
+    pub fn analysis_cache(&self) -> Result<Ref<KeyValueStore>, ()> {
+        unsafe {
+            let cache = BNReadDatabaseAnalysisCache(self.handle);
+
+            if cache.is_null() {
+                return Err(());
+            }
+
+            Ok(Ref::new(KeyValueStore::from_raw(cache)))
+        }
+    }
+
+    pub fn set_analysis_cache(&self, cache: &KeyValueStore) -> Result<(), ()> {
+        match unsafe { BNWriteDatabaseAnalysisCache(self.handle, cache.handle) } {
+            true => Ok(()),
+            false => Err(()),
+        }
+    }
+}
+
+unsafe impl Send for Database {}
+unsafe impl Sync for Database {}
+
+impl ToOwned for Database {
+    type Owned = Ref<Self>;
+
+    fn to_owned(&self) -> Self::Owned {
+        unsafe { RefCountable::inc_ref(self) }
+    }
+}
+
+unsafe impl RefCountable for Database {
+    unsafe fn inc_ref(handle: &Self) -> Ref<Self> {
+        Ref::new(Self {
+            handle: BNNewDatabaseReference(handle.handle),
+        })
+    }
+
+    unsafe fn dec_ref(handle: &Self) {
+        BNFreeDatabase(handle.handle);
+    }
+}
+
+#[derive(PartialEq, Eq, Hash)]
+pub struct Snapshot {
+    pub(crate) handle: *mut BNSnapshot,
+}
+
+impl Snapshot {
+    pub(crate) unsafe fn from_raw(handle: *mut BNSnapshot) -> Self {
+        debug_assert!(!handle.is_null());
+
+        Self { handle }
+    }
+
+    pub fn database(&self) -> Ref<Database> {
+        unsafe { Ref::new(Database::from_raw(BNGetSnapshotDatabase(self.handle))) }
+    }
+
+    pub fn id(&self) -> i64 {
+        unsafe { BNGetSnapshotId(self.handle) }
+    }
+
+    pub fn name(&self) -> BnString {
+        unsafe { BnString::from_raw(BNGetSnapshotName(self.handle)) }
+    }
+
+    pub fn is_auto_save(&self) -> bool {
+        unsafe { BNIsSnapshotAutoSave(self.handle) }
+    }
+
+    pub fn first_parent(&self) -> Option<Ref<Snapshot>> {
+        unsafe {
+            let parent = BNGetSnapshotFirstParent(self.handle);
+
+            if parent.is_null() {
+                return None;
+            }
+
+            Some(Ref::new(Snapshot::from_raw(parent)))
+        }
+    }
+
+    pub fn parents(&self) -> Array<Snapshot> {
+        unsafe {
+            let mut count = 0;
+            let parents = BNGetSnapshotParents(self.handle, &mut count);
+
+            Array::new(parents, count, ())
+        }
+    }
+
+    pub fn children(&self) -> Array<Snapshot> {
+        unsafe {
+            let mut count = 0;
+            let children = BNGetSnapshotChildren(self.handle, &mut count);
+
+            Array::new(children, count, ())
+        }
+    }
+
+    /// Contents of the file at the time of the snapshot
+    pub fn file_contents(&self) -> Result<DataBuffer, ()> {
+        unsafe {
+            let contents = BNGetSnapshotFileContents(self.handle);
+
+            if contents.is_null() {
+                return Err(());
+            }
+
+            Ok(DataBuffer::from_raw(contents))
+        }
+    }
+
+    pub fn file_contents_hash(&self) -> Result<DataBuffer, ()> {
+        unsafe {
+            let hash = BNGetSnapshotFileContentsHash(self.handle);
+
+            if hash.is_null() {
+                return Err(());
+            }
+
+            Ok(DataBuffer::from_raw(hash))
+        }
+    }
+
+    /// The analysis state saved in this snapshot
+    pub fn data(&self) -> Result<Ref<KeyValueStore>, ()> {
+        unsafe {
+            let data = BNReadSnapshotData(self.handle);
+
+            if data.is_null() {
+                return Err(());
+            }
+
+            Ok(Ref::new(KeyValueStore::from_raw(data)))
+        }
+    }
+
+    pub fn undo_entries(&self) -> Array<UndoEntry> {
+        unsafe {
+            let mut count = 0;
+            let entries = BNGetSnapshotUndoEntries(self.handle, &mut count);
+
+            Array::new(entries, count, ())
+        }
+    }
+
+    pub fn has_ancestor(&self, other: &Snapshot) -> bool {
+        unsafe { BNSnapshotHasAncestor(self.handle, other.handle) }
+    }
+}
+
+unsafe impl Send for Snapshot {}
+unsafe impl Sync for Snapshot {}
+
+impl fmt::Debug for Snapshot {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "<snapshot {} '{}'>", self.id(), self.name())
+    }
+}
+
+impl ToOwned for Snapshot {
+    type Owned = Ref<Self>;
+
+    fn to_owned(&self) -> Self::Owned {
+        unsafe { RefCountable::inc_ref(self) }
+    }
+}
+
+unsafe impl RefCountable for Snapshot {
+    unsafe fn inc_ref(handle: &Self) -> Ref<Self> {
+        Ref::new(Self {
+            handle: BNNewSnapshotReference(handle.handle),
+        })
+    }
+
+    unsafe fn dec_ref(handle: &Self) {
+        BNFreeSnapshot(handle.handle);
+    }
+}
+
+unsafe impl CoreOwnedArrayProvider for Snapshot {
+    type Raw = *mut BNSnapshot;
+    type Context = ();
+
+    unsafe fn free(raw: *mut Self::Raw, count: usize, _context: &Self::Context) {
+        BNFreeSnapshotList(raw, count);
+    }
+}
+
+unsafe impl<'a> CoreOwnedArrayWrapper<'a> for Snapshot {
+    type Wrapped = Guard<'a, Snapshot>;
+
+    unsafe fn wrap_raw(raw: &'a Self::Raw, context: &'a Self::Context) -> Self::Wrapped {
+        Guard::new(Snapshot::from_raw(*raw), context)
+    }
+}
+
+/// A single undoable change recorded in a snapshot
+#[derive(Clone, Debug)]
+pub struct UndoAction {
+    pub action_type: ActionType,
+    pub summary: String,
+}
+
+/// A group of undo actions committed together
+#[derive(Clone, Debug)]
+pub struct UndoEntry {
+    /// Name of the user that made the change, if known
+    pub user: Option<String>,
+    pub hash: String,
+    pub timestamp: u64,
+    pub actions: Vec<UndoAction>,
+}
+
+impl UndoEntry {
+    pub(crate) unsafe fn from_raw(raw: &BNUndoEntry) -> Self {
+        let actions = if raw.actions.is_null() {
+            Vec::new()
+        } else {
+            slice::from_raw_parts(raw.actions, raw.actionCount as usize)
+                .iter()
+                .map(|action| UndoAction {
+                    action_type: action.actionType,
+                    summary: raw_to_string(action.summaryText).unwrap_or_default(),
+                })
+                .collect()
+        };
+
+        let user = if raw.user.is_null() {
+            None
+        } else {
+            Some(BnString::from_raw(BNGetUserName(raw.user)).to_string())
+        };
+
+        Self {
+            user,
+            hash: raw_to_string(raw.hash).unwrap_or_default(),
+            timestamp: raw.timestamp,
+            actions,
+        }
+    }
+}
+
+unsafe impl CoreOwnedArrayProvider for UndoEntry {
+    type Raw = BNUndoEntry;
+    type Context = ();
+
+    unsafe fn free(raw: *mut Self::Raw, count: usize, _context: &Self::Context) {
+        BNFreeUndoEntries(raw, count);
+    }
+}
+
+unsafe impl<'a> CoreOwnedArrayWrapper<'a> for UndoEntry {
+    type Wrapped = UndoEntry;
+
+    unsafe fn wrap_raw(raw: &'a Self::Raw, _context: &'a Self::Context) -> Self::Wrapped {
+        UndoEntry::from_raw(raw)
+    }
+}
+
+/// A namespaced string-to-buffer store, as used for snapshot data and database globals
+#[derive(PartialEq, Eq, Hash)]
+pub struct KeyValueStore {
+    pub(crate) handle: *mut BNKeyValueStore,
+}
+
+impl KeyValueStore {
+    pub(crate) unsafe fn from_raw(handle: *mut BNKeyValueStore) -> Self {
+        debug_assert!(!handle.is_null());
+
+        Self { handle }
+    }
+
+    pub fn new() -> Ref<Self> {
+        unsafe { Ref::new(Self::from_raw(BNCreateKeyValueStore())) }
+    }
+
+    /// Deserializes a store previously produced by `serialized_data`
+    pub fn from_data_buffer(buffer: &DataBuffer) -> Result<Ref<Self>, ()> {
+        unsafe {
+            let handle = BNCreateKeyValueStoreFromDataBuffer(buffer.as_raw());
+
+            if handle.is_null() {
+                return Err(());
+            }
+
+            Ok(Ref::new(Self::from_raw(handle)))
+        }
+    }
+
+    pub fn keys(&self) -> Array<BnString> {
+        unsafe {
+            let mut count = 0;
+            let keys = BNGetKeyValueStoreKeys(self.handle, &mut count);
+
+            Array::new(keys, count, ())
+        }
+    }
+
+    pub fn has_value<S: BnStrCompatible>(&self, name: S) -> bool {
+        let name = name.as_bytes_with_nul();
+
+        unsafe { BNKeyValueStoreHasValue(self.handle, name.as_ref().as_ptr() as *const _) }
+    }
+
+    pub fn value<S: BnStrCompatible>(&self, name: S) -> Option<BnString> {
+        let name = name.as_bytes_with_nul();
+
+        unsafe {
+            let value = BNGetKeyValueStoreValue(self.handle, name.as_ref().as_ptr() as *const _);
+
+            if value.is_null() {
+                return None;
+            }
+
+            Some(BnString::from_raw(value))
+        }
+    }
+
+    pub fn buffer<S: BnStrCompatible>(&self, name: S) -> Option<DataBuffer> {
+        let name = name.as_bytes_with_nul();
+
+        unsafe {
+            let value = BNGetKeyValueStoreBuffer(self.handle, name.as_ref().as_ptr() as *const _);
+
+            if value.is_null() {
+                return None;
+            }
+
+            Some(DataBuffer::from_raw(value))
+        }
+    }
+
+    pub fn set_value<K: BnStrCompatible, V: BnStrCompatible>(
+        &self,
+        name: K,
+        value: V,
+    ) -> Result<(), ()> {
+        let name = name.as_bytes_with_nul();
+        let value = value.as_bytes_with_nul();
+
+        let res = unsafe {
+            BNSetKeyValueStoreValue(
+                self.handle,
+                name.as_ref().as_ptr() as *const _,
+                value.as_ref().as_ptr() as *const _,
+            )
+        };
+
+        match res {
+            true => Ok(()),
+            false => Err(()),
+        }
+    }
+
+    pub fn set_buffer<S: BnStrCompatible>(&self, name: S, value: &DataBuffer) -> Result<(), ()> {
+        let name = name.as_bytes_with_nul();
+
+        let res = unsafe {
+            BNSetKeyValueStoreBuffer(
+                self.handle,
+                name.as_ref().as_ptr() as *const _,
+                value.as_raw(),
+            )
+        };
+
+        match res {
+            true => Ok(()),
+            false => Err(()),
+        }
+    }
+
+    pub fn serialized_data(&self) -> DataBuffer {
+        unsafe { DataBuffer::from_raw(BNGetKeyValueStoreSerializedData(self.handle)) }
+    }
+
+    /// Scopes subsequent reads and writes under `name` until the matching `end_namespace`
+    pub fn begin_namespace<S: BnStrCompatible>(&self, name: S) {
+        let name = name.as_bytes_with_nul();
+
+        unsafe { BNBeginKeyValueStoreNamespace(self.handle, name.as_ref().as_ptr() as *const _) }
+    }
+
+    pub fn end_namespace(&self) {
+        unsafe { BNEndKeyValueStoreNamespace(self.handle) }
+    }
+
+    pub fn is_empty(&self) -> bool {
+        unsafe { BNIsKeyValueStoreEmpty(self.handle) }
+    }
+
+    pub fn value_size(&self) -> usize {
+        unsafe { BNGetKeyValueStoreValueSize(self.handle) }
+    }
+
+    pub fn data_size(&self) -> usize {
+        unsafe { BNGetKeyValueStoreDataSize(self.handle) }
+    }
+
+    pub fn value_storage_size(&self) -> usize {
+        unsafe { BNGetKeyValueStoreValueStorageSize(self.handle) }
+    }
+
+    pub fn namespace_size(&self) -> usize {
+        unsafe { BNGetKeyValueStoreNamespaceSize(self.handle) }
+    }
+}
+
+unsafe impl Send for KeyValueStore {}
+unsafe impl Sync for KeyValueStore {}
+
+impl ToOwned for KeyValueStore {
+    type Owned = Ref<Self>;
+
+    fn to_owned(&self) -> Self::Owned {
+        unsafe { RefCountable::inc_ref(self) }
+    }
+}
+
+unsafe impl RefCountable for KeyValueStore {
+    unsafe fn inc_ref(handle: &Self) -> Ref<Self> {
+        Ref::new(Self {
+            handle: BNNewKeyValueStoreReference(handle.handle),
+        })
+    }
+
+    unsafe fn dec_ref(handle: &Self) {
+        BNFreeKeyValueStore(handle.handle);
+    }
+}
@@ -43,14 +43,24 @@ impl DataBuffer {
         unsafe { BNGetDataBufferLength(self.0) }
     }
 
-    // pub fn new(data: ?, len: usize) -> Result<Self> {
-    //   let read_buffer = unsafe { BNCreateDataBuffer(data, len) };
-    //   if read_buffer.is_null() {
-    //     Err(())
-    //   } else {
-    //     Ok(DataBuffer::from_raw(read_buffer))
-    //   }
-    // }
+    pub fn new(data: &[u8]) -> Result<Self, ()> {
+        let buffer = unsafe { BNCreateDataBuffer(data.as_ptr() as *const _, data.len()) };
+        if buffer.is_null() {
+            Err(())
+        } else {
+            Ok(DataBuffer::from_raw(buffer))
+        }
+    }
+
+    pub fn set_data(&mut self, data: &[u8]) {
+        unsafe {
+            BNSetDataBufferContents(self.0, data.as_ptr() as *mut _, data.len());
+        }
+    }
+
+    pub(crate) fn as_raw(&self) -> *mut BNDataBuffer {
+        self.0
+    }
 }
 
 // TODO : delete this
@@ -0,0 +1,342 @@
+// Copyright 2021 Vector 35 Inc.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Observers for changes made to a `BinaryView`, by analysis or by the user.
+
+use binaryninjacore_sys::*;
+
+use std::os::raw::c_void;
+
+use crate::binaryview::{BinaryView, StringType};
+use crate::function::Function;
+use crate::symbol::Symbol;
+use crate::tags::{TagReference, TagType};
+use crate::types::{DataVariable, QualifiedName, Type};
+
+use crate::rc::*;
+
+/// Receives change notifications for a single view
+///
+/// Every method defaults to doing nothing, so implementations only need to
+/// override the events they care about. Notifications are delivered from
+/// analysis threads, so any state kept by an implementation needs to be
+/// synchronized.
+#[allow(unused_variables)]
+pub trait BinaryDataNotification: 'static + Sync {
+    fn data_written(&self, view: &BinaryView, offset: u64, len: usize) {}
+    fn data_inserted(&self, view: &BinaryView, offset: u64, len: usize) {}
+    fn data_removed(&self, view: &BinaryView, offset: u64, len: u64) {}
+
+    fn function_added(&self, view: &BinaryView, func: &Function) {}
+    fn function_removed(&self, view: &BinaryView, func: &Function) {}
+    fn function_updated(&self, view: &BinaryView, func: &Function) {}
+    fn function_update_requested(&self, view: &BinaryView, func: &Function) {}
+
+    fn data_variable_added(&self, view: &BinaryView, var: &DataVariable) {}
+    fn data_variable_removed(&self, view: &BinaryView, var: &DataVariable) {}
+    fn data_variable_updated(&self, view: &BinaryView, var: &DataVariable) {}
+
+    fn data_metadata_updated(&self, view: &BinaryView, offset: u64) {}
+
+    fn tag_type_updated(&self, view: &BinaryView, tag_type: &TagType) {}
+    fn tag_added(&self, view: &BinaryView, tag_ref: &TagReference) {}
+    fn tag_updated(&self, view: &BinaryView, tag_ref: &TagReference) {}
+    fn tag_removed(&self, view: &BinaryView, tag_ref: &TagReference) {}
+
+    fn symbol_added(&self, view: &BinaryView, sym: &Symbol) {}
+    fn symbol_updated(&self, view: &BinaryView, sym: &Symbol) {}
+    fn symbol_removed(&self, view: &BinaryView, sym: &Symbol) {}
+
+    fn string_found(&self, view: &BinaryView, string_type: StringType, offset: u64, len: usize) {}
+    fn string_removed(&self, view: &BinaryView, string_type: StringType, offset: u64, len: usize) {}
+
+    fn type_defined(&self, view: &BinaryView, name: &QualifiedName, t: &Type) {}
+    fn type_undefined(&self, view: &BinaryView, name: &QualifiedName, t: &Type) {}
+    fn type_reference_changed(&self, view: &BinaryView, name: &QualifiedName, t: &Type) {}
+}
+
+/// Keeps a notification registered with its view; dropping it unregisters the notification
+#[must_use]
+pub struct DataNotificationGuard<N: BinaryDataNotification> {
+    view: Ref<BinaryView>,
+    raw: *mut BNBinaryDataNotification,
+    ctxt: *mut N,
+}
+
+unsafe impl<N: BinaryDataNotification + Send> Send for DataNotificationGuard<N> {}
+unsafe impl<N: BinaryDataNotification + Send> Sync for DataNotificationGuard<N> {}
+
+impl<N: BinaryDataNotification> Drop for DataNotificationGuard<N> {
+    fn drop(&mut self) {
+        unsafe {
+            BNUnregisterDataNotification(self.view.handle, self.raw);
+
+            let _raw = Box::from_raw(self.raw);
+            let _notification = Box::from_raw(self.ctxt);
+        }
+    }
+}
+
+macro_rules! data_cb {
+    ($cb:ident, $method:ident, $len:ty) => {
+        extern "C" fn $cb<N>(ctxt: *mut c_void, view: *mut BNBinaryView, offset: u64, len: $len)
+        where
+            N: BinaryDataNotification,
+        {
+            ffi_wrap!(
+                concat!("BinaryDataNotification::", stringify!($method)),
+                unsafe {
+                    let notification = &*(ctxt as *const N);
+                    let view = BinaryView { handle: view };
+
+                    notification.$method(&view, offset, len);
+                }
+            )
+        }
+    };
+}
+
+macro_rules! function_cb {
+    ($cb:ident, $method:ident) => {
+        extern "C" fn $cb<N>(ctxt: *mut c_void, view: *mut BNBinaryView, func: *mut BNFunction)
+        where
+            N: BinaryDataNotification,
+        {
+            ffi_wrap!(
+                concat!("BinaryDataNotification::", stringify!($method)),
+                unsafe {
+                    let notification = &*(ctxt as *const N);
+                    let view = BinaryView { handle: view };
+                    let func = Function { handle: func };
+
+                    notification.$method(&view, &func);
+                }
+            )
+        }
+    };
+}
+
+macro_rules! data_var_cb {
+    ($cb:ident, $method:ident) => {
+        extern "C" fn $cb<N>(ctxt: *mut c_void, view: *mut BNBinaryView, var: *mut BNDataVariable)
+        where
+            N: BinaryDataNotification,
+        {
+            ffi_wrap!(
+                concat!("BinaryDataNotification::", stringify!($method)),
+                unsafe {
+                    let notification = &*(ctxt as *const N);
+                    let view = BinaryView { handle: view };
+                    let var = DataVariable::from_raw(&*var);
+
+                    notification.$method(&view, &var);
+                }
+            )
+        }
+    };
+}
+
+macro_rules! tag_cb {
+    ($cb:ident, $method:ident) => {
+        extern "C" fn $cb<N>(
+            ctxt: *mut c_void,
+            view: *mut BNBinaryView,
+            tag_ref: *mut BNTagReference,
+        ) where
+            N: BinaryDataNotification,
+        {
+            ffi_wrap!(
+                concat!("BinaryDataNotification::", stringify!($method)),
+                unsafe {
+                    let notification = &*(ctxt as *const N);
+                    let view = BinaryView { handle: view };
+                    let tag_ref = TagReference::from_raw(&*tag_ref);
+
+                    notification.$method(&view, &tag_ref);
+                }
+            )
+        }
+    };
+}
+
+macro_rules! symbol_cb {
+    ($cb:ident, $method:ident) => {
+        extern "C" fn $cb<N>(ctxt: *mut c_void, view: *mut BNBinaryView, sym: *mut BNSymbol)
+        where
+            N: BinaryDataNotification,
+        {
+            ffi_wrap!(
+                concat!("BinaryDataNotification::", stringify!($method)),
+                unsafe {
+                    let notification = &*(ctxt as *const N);
+                    let view = BinaryView { handle: view };
+                    let sym = Symbol::from_raw(sym);
+
+                    notification.$method(&view, &sym);
+                }
+            )
+        }
+    };
+}
+
+macro_rules! string_cb {
+    ($cb:ident, $method:ident) => {
+        extern "C" fn $cb<N>(
+            ctxt: *mut c_void,
+            view: *mut BNBinaryView,
+            string_type: BNStringType,
+            offset: u64,
+            len: usize,
+        ) where
+            N: BinaryDataNotification,
+        {
+            ffi_wrap!(
+                concat!("BinaryDataNotification::", stringify!($method)),
+                unsafe {
+                    let notification = &*(ctxt as *const N);
+                    let view = BinaryView { handle: view };
+
+                    notification.$method(&view, string_type, offset, len);
+                }
+            )
+        }
+    };
+}
+
+macro_rules! type_cb {
+    ($cb:ident, $method:ident) => {
+        extern "C" fn $cb<N>(
+            ctxt: *mut c_void,
+            view: *mut BNBinaryView,
+            name: *mut BNQualifiedName,
+            t: *mut BNType,
+        ) where
+            N: BinaryDataNotification,
+        {
+            ffi_wrap!(
+                concat!("BinaryDataNotification::", stringify!($method)),
+                unsafe {
+                    let notification = &*(ctxt as *const N);
+                    let view = BinaryView { handle: view };
+                    let name = &*(name as *const QualifiedName);
+                    let t = Type { handle: t };
+
+                    notification.$method(&view, name, &t);
+                }
+            )
+        }
+    };
+}
+
+data_cb!(cb_data_written, data_written, usize);
+data_cb!(cb_data_inserted, data_inserted, usize);
+data_cb!(cb_data_removed, data_removed, u64);
+
+function_cb!(cb_function_added, function_added);
+function_cb!(cb_function_removed, function_removed);
+function_cb!(cb_function_updated, function_updated);
+function_cb!(cb_function_update_requested, function_update_requested);
+
+data_var_cb!(cb_data_variable_added, data_variable_added);
+data_var_cb!(cb_data_variable_removed, data_variable_removed);
+data_var_cb!(cb_data_variable_updated, data_variable_updated);
+
+extern "C" fn cb_data_metadata_updated<N>(ctxt: *mut c_void, view: *mut BNBinaryView, offset: u64)
+where
+    N: BinaryDataNotification,
+{
+    ffi_wrap!("BinaryDataNotification::data_metadata_updated", unsafe {
+        let notification = &*(ctxt as *const N);
+        let view = BinaryView { handle: view };
+
+        notification.data_metadata_updated(&view, offset);
+    })
+}
+
+extern "C" fn cb_tag_type_updated<N>(
+    ctxt: *mut c_void,
+    view: *mut BNBinaryView,
+    tag_type: *mut BNTagType,
+) where
+    N: BinaryDataNotification,
+{
+    ffi_wrap!("BinaryDataNotification::tag_type_updated", unsafe {
+        let notification = &*(ctxt as *const N);
+        let view = BinaryView { handle: view };
+        let tag_type = TagType::from_raw(tag_type);
+
+        notification.tag_type_updated(&view, &tag_type);
+    })
+}
+
+tag_cb!(cb_tag_added, tag_added);
+tag_cb!(cb_tag_updated, tag_updated);
+tag_cb!(cb_tag_removed, tag_removed);
+
+symbol_cb!(cb_symbol_added, symbol_added);
+symbol_cb!(cb_symbol_updated, symbol_updated);
+symbol_cb!(cb_symbol_removed, symbol_removed);
+
+string_cb!(cb_string_found, string_found);
+string_cb!(cb_string_removed, string_removed);
+
+type_cb!(cb_type_defined, type_defined);
+type_cb!(cb_type_undefined, type_undefined);
+type_cb!(cb_type_reference_changed, type_reference_changed);
+
+/// Starts delivering changes made to `view` to `notification`, until the returned guard is dropped
+pub fn register_data_notification<N>(view: &BinaryView, notification: N) -> DataNotificationGuard<N>
+where
+    N: BinaryDataNotification,
+{
+    let ctxt = Box::into_raw(Box::new(notification));
+
+    // the same pointer has to be handed back when unregistering
+    let raw = Box::into_raw(Box::new(BNBinaryDataNotification {
+        context: ctxt as *mut _,
+        dataWritten: Some(cb_data_written::<N>),
+        dataInserted: Some(cb_data_inserted::<N>),
+        dataRemoved: Some(cb_data_removed::<N>),
+        functionAdded: Some(cb_function_added::<N>),
+        functionRemoved: Some(cb_function_removed::<N>),
+        functionUpdated: Some(cb_function_updated::<N>),
+        functionUpdateRequested: Some(cb_function_update_requested::<N>),
+        dataVariableAdded: Some(cb_data_variable_added::<N>),
+        dataVariableRemoved: Some(cb_data_variable_removed::<N>),
+        dataVariableUpdated: Some(cb_data_variable_updated::<N>),
+        dataMetadataUpdated: Some(cb_data_metadata_updated::<N>),
+        tagTypeUpdated: Some(cb_tag_type_updated::<N>),
+        tagAdded: Some(cb_tag_added::<N>),
+        tagUpdated: Some(cb_tag_updated::<N>),
+        tagRemoved: Some(cb_tag_removed::<N>),
+        symbolAdded: Some(cb_symbol_added::<N>),
+        symbolUpdated: Some(cb_symbol_updated::<N>),
+        symbolRemoved: Some(cb_symbol_removed::<N>),
+        stringFound: Some(cb_string_found::<N>),
+        stringRemoved: Some(cb_string_removed::<N>),
+        typeDefined: Some(cb_type_defined::<N>),
+        typeUndefined: Some(cb_type_undefined::<N>),
+        typeReferenceChanged: Some(cb_type_reference_changed::<N>),
+    }));
+
+    unsafe {
+        BNRegisterDataNotification(view.handle, raw);
+    }
+
+    DataNotificationGuard {
+        view: view.to_owned(),
+        raw,
+        ctxt,
+    }
+}
@@ -0,0 +1,108 @@
+// Copyright 2021 Vector 35 Inc.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Demanglers for MSVC and GNU3 (Itanium) symbol names.
+
+use binaryninjacore_sys::*;
+
+use std::os::raw::c_char;
+use std::ptr;
+
+use crate::architecture::CoreArchitecture;
+use crate::types::{QualifiedName, Type};
+
+use crate::rc::*;
+use crate::string::*;
+
+pub type Result<R> = std::result::Result<R, ()>;
+
+type Demangler = unsafe extern "C" fn(
+    *mut BNArchitecture,
+    *const c_char,
+    *mut *mut BNType,
+    *mut *mut *mut c_char,
+    *mut usize,
+    bool,
+) -> bool;
+
+fn demangle<S: BnStrCompatible>(
+    demangler: Demangler,
+    arch: &CoreArchitecture,
+    mangled_name: S,
+    simplify: bool,
+) -> Result<(Option<Ref<Type>>, QualifiedName)> {
+    let mangled_name = mangled_name.as_bytes_with_nul();
+
+    let mut out_type: *mut BNType = ptr::null_mut();
+    let mut out_name: *mut *mut c_char = ptr::null_mut();
+    let mut out_size: usize = 0;
+
+    unsafe {
+        if !demangler(
+            arch.0,
+            mangled_name.as_ref().as_ptr() as *const _,
+            &mut out_type,
+            &mut out_name,
+            &mut out_size,
+            simplify,
+        ) {
+            return Err(());
+        }
+
+        let join = BnString::new("::");
+        let name = QualifiedName(BNQualifiedName {
+            name: BNAllocStringList(out_name as *mut *const c_char, out_size),
+            join: join.into_raw(),
+            nameCount: out_size,
+        });
+
+        BNFreeDemangledName(&mut out_name, out_size);
+
+        let t = if out_type.is_null() {
+            None
+        } else {
+            Some(Type::ref_from_raw(out_type))
+        };
+
+        Ok((t, name))
+    }
+}
+
+/// Demangles an MSVC name such as `?foo@@YAHH@Z`
+///
+/// The type is only present for names that encode one, such as functions.
+pub fn demangle_ms<S: BnStrCompatible>(
+    arch: &CoreArchitecture,
+    mangled_name: S,
+    simplify: bool,
+) -> Result<(Option<Ref<Type>>, QualifiedName)> {
+    demangle(BNDemangleMS, arch, mangled_name, simplify)
+}
+
+/// Demangles a GNU3 (Itanium C++ ABI) name such as `_Z3fooi`
+///
+/// The type is only present for names that encode one, such as functions.
+pub fn demangle_gnu3<S: BnStrCompatible>(
+    arch: &CoreArchitecture,
+    mangled_name: S,
+    simplify: bool,
+) -> Result<(Option<Ref<Type>>, QualifiedName)> {
+    demangle(BNDemangleGNU3, arch, mangled_name, simplify)
+}
+
+pub fn is_gnu3_mangled_string<S: BnStrCompatible>(mangled_name: S) -> bool {
+    let mangled_name = mangled_name.as_bytes_with_nul();
+
+    unsafe { BNIsGNU3MangledString(mangled_name.as_ref().as_ptr() as *const _) }
+}
@@ -18,11 +18,15 @@ use binaryninjacore_sys::{
     BNCloseProject,
     BNCommitUndoActions,
     BNCreateDatabase,
+    BNCreateDatabaseWithProgress,
     BNCreateFileMetadata,
+    BNCreateSaveSettings,
     BNFileMetadata,
     BNFreeFileMetadata,
+    BNFreeSaveSettings,
     BNGetCurrentOffset,
     BNGetCurrentView,
+    BNGetFileMetadataDatabase,
     BNGetFileViewOfType,
     BNGetFilename,
     BNIsAnalysisChanged,
@@ -30,26 +34,37 @@ use binaryninjacore_sys::{
     //BNSetFileMetadataNavigationHandler,
     BNIsFileModified,
     BNIsProjectOpen,
+    BNIsSaveSettingsOptionSet,
     BNMarkFileModified,
     BNMarkFileSaved,
     BNNavigate,
     BNNewFileReference,
+    BNNewSaveSettingsReference,
     BNOpenDatabaseForConfiguration,
     BNOpenExistingDatabase,
+    BNOpenExistingDatabaseWithProgress,
     BNOpenProject,
     BNRedo,
     BNSaveAutoSnapshot,
+    BNSaveAutoSnapshotWithProgress,
+    BNSaveOption,
+    BNSaveSettings,
     BNSetFilename,
+    BNSetSaveSettingsOption,
     BNUndo,
 };
 
 use crate::binaryview::BinaryView;
+use crate::database::Database;
 
 use crate::rc::*;
 use crate::string::*;
 
+use std::os::raw::c_void;
 use std::ptr;
 
+pub type SaveOption = BNSaveOption;
+
 #[derive(PartialEq, Eq, Hash)]
 pub struct FileMetadata {
     pub(crate) handle: *mut BNFileMetadata,
@@ -205,6 +220,51 @@ impl FileMetadata {
         }
     }
 
+    /// Like `create_database`, calling `progress(current, total)` as the database is written
+    pub fn create_database_with_progress<S, F>(
+        &self,
+        filename: S,
+        settings: Option<&SaveSettings>,
+        mut progress: F,
+    ) -> bool
+    where
+        S: BnStrCompatible,
+        F: FnMut(usize, usize),
+    {
+        let filename = filename.as_bytes_with_nul();
+        let raw = "Raw".as_bytes_with_nul();
+
+        unsafe {
+            BNCreateDatabaseWithProgress(
+                BNGetFileViewOfType(self.handle, raw.as_ptr() as *mut _),
+                filename.as_ref().as_ptr() as *mut _,
+                &mut progress as *mut F as *mut c_void,
+                Some(cb_progress::<F>),
+                settings.map_or(ptr::null_mut(), |s| s.handle),
+            )
+        }
+    }
+
+    pub fn save_auto_snapshot_with_progress<F>(
+        &self,
+        settings: Option<&SaveSettings>,
+        mut progress: F,
+    ) -> bool
+    where
+        F: FnMut(usize, usize),
+    {
+        let raw = "Raw".as_bytes_with_nul();
+
+        unsafe {
+            BNSaveAutoSnapshotWithProgress(
+                BNGetFileViewOfType(self.handle, raw.as_ptr() as *mut _),
+                &mut progress as *mut F as *mut c_void,
+                Some(cb_progress::<F>),
+                settings.map_or(ptr::null_mut(), |s| s.handle),
+            )
+        }
+    }
+
     pub fn open_database_for_configuration<S: BnStrCompatible>(
         &self,
         filename: S,
@@ -228,11 +288,34 @@ impl FileMetadata {
 
         let view = unsafe { BNOpenExistingDatabase(self.handle, filename_ptr) };
 
-        // TODO : add optional progress function
-        // let view = match progress_func {
-        //     None => BNOpenExistingDatabase(self.handle, filename_ptr),
-        //     _ => BNOpenExistingDatabaseWithProgress(self.handle, str(filename), None, ctypes.CFUNCTYPE(None, ctypes.c_void_p, ctypes.c_ulonglong, ctypes.c_ulonglong)(lambda ctxt, cur, total: progress_func(cur, total)))
-        // };
+        if view.is_null() {
+            Err(())
+        } else {
+            Ok(unsafe { BinaryView::from_raw(view) })
+        }
+    }
+
+    /// Like `open_database`, calling `progress(current, total)` as the database is loaded
+    pub fn open_database_with_progress<S, F>(
+        &self,
+        filename: S,
+        mut progress: F,
+    ) -> Result<Ref<BinaryView>, ()>
+    where
+        S: BnStrCompatible,
+        F: FnMut(usize, usize),
+    {
+        let filename = filename.as_bytes_with_nul();
+        let filename_ptr = filename.as_ref().as_ptr() as *mut _;
+
+        let view = unsafe {
+            BNOpenExistingDatabaseWithProgress(
+                self.handle,
+                filename_ptr,
+                &mut progress as *mut F as *mut c_void,
+                Some(cb_progress::<F>),
+            )
+        };
 
         if view.is_null() {
             Err(())
@@ -241,6 +324,18 @@ impl FileMetadata {
         }
     }
 
+    pub fn database(&self) -> Option<Ref<Database>> {
+        unsafe {
+            let db = BNGetFileMetadataDatabase(self.handle);
+
+            if db.is_null() {
+                return None;
+            }
+
+            Some(Ref::new(Database::from_raw(db)))
+        }
+    }
+
     pub fn open_project(&self) -> bool {
         unsafe { BNOpenProject(self.handle) }
     }
@@ -276,7 +371,59 @@ unsafe impl RefCountable for FileMetadata {
     }
 }
 
-/*
-BNCreateDatabase,
-BNCreateDatabaseWithProgress,
-*/
+extern "C" fn cb_progress<F>(ctxt: *mut c_void, progress: usize, total: usize)
+where
+    F: FnMut(usize, usize),
+{
+    ffi_wrap!("FileMetadata::progress", unsafe {
+        let f = &mut *(ctxt as *mut F);
+        f(progress, total)
+    })
+}
+
+/// Options controlling what gets written when saving a database
+#[derive(PartialEq, Eq, Hash)]
+pub struct SaveSettings {
+    pub(crate) handle: *mut BNSaveSettings,
+}
+
+impl SaveSettings {
+    pub fn new() -> Ref<Self> {
+        unsafe {
+            Ref::new(Self {
+                handle: BNCreateSaveSettings(),
+            })
+        }
+    }
+
+    pub fn is_option_set(&self, option: SaveOption) -> bool {
+        unsafe { BNIsSaveSettingsOptionSet(self.handle, option) }
+    }
+
+    pub fn set_option(&self, option: SaveOption, state: bool) {
+        unsafe { BNSetSaveSettingsOption(self.handle, option, state) }
+    }
+}
+
+unsafe impl Send for SaveSettings {}
+unsafe impl Sync for SaveSettings {}
+
+impl ToOwned for SaveSettings {
+    type Owned = Ref<Self>;
+
+    fn to_owned(&self) -> Self::Owned {
+        unsafe { RefCountable::inc_ref(self) }
+    }
+}
+
+unsafe impl RefCountable for SaveSettings {
+    unsafe fn inc_ref(handle: &Self) -> Ref<Self> {
+        Ref::new(Self {
+            handle: BNNewSaveSettingsReference(handle.handle),
+        })
+    }
+
+    unsafe fn dec_ref(handle: &Self) {
+        BNFreeSaveSettings(handle.handle);
+    }
+}
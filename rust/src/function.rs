@@ -13,6 +13,9 @@
 // limitations under the License.
 
 use std::fmt;
+use std::mem;
+use std::ops;
+use std::slice;
 
 use binaryninjacore_sys::*;
 
@@ -20,10 +23,14 @@ use crate::architecture::CoreArchitecture;
 use crate::basicblock::{BasicBlock, BlockContext};
 use crate::binaryview::{BinaryView, BinaryViewExt};
 use crate::platform::Platform;
+use crate::references::{address_list, CodeReference};
 use crate::symbol::Symbol;
-use crate::types::Type;
+use crate::tags::{Tag, TagReference, TagType};
+use crate::types::{Conf, NamedTypedVariable, Type, Variable};
 
+use crate::hlil;
 use crate::llil;
+use crate::mlil;
 
 use crate::rc::*;
 use crate::string::*;
@@ -230,6 +237,391 @@ impl Function {
         }
     }
 
+    pub fn medium_level_il(&self) -> Result<Ref<mlil::RegularFunction>, ()> {
+        unsafe {
+            let mlil = BNGetFunctionMediumLevelIL(self.handle);
+
+            if mlil.is_null() {
+                return Err(());
+            }
+
+            Ok(Ref::new(mlil::RegularFunction::from_raw(mlil)))
+        }
+    }
+
+    pub fn high_level_il(&self) -> Result<Ref<hlil::Function>, ()> {
+        unsafe {
+            let hlil = BNGetFunctionHighLevelIL(self.handle);
+
+            if hlil.is_null() {
+                return Err(());
+            }
+
+            Ok(Ref::new(hlil::Function::from_raw(hlil)))
+        }
+    }
+
+    /// Every location in code that calls this function
+    pub fn callers(&self) -> Array<CodeReference> {
+        unsafe {
+            let mut count = 0;
+            let refs = BNGetCallers(self.view().handle, self.start(), &mut count);
+
+            Array::new(refs, count, ())
+        }
+    }
+
+    /// Every call instruction in this function
+    pub fn call_sites(&self) -> Array<CodeReference> {
+        unsafe {
+            let mut count = 0;
+            let refs = BNGetFunctionCallSites(self.handle, &mut count);
+
+            Array::new(refs, count, ())
+        }
+    }
+
+    /// Addresses this function calls, in call site order
+    pub fn callee_addresses(&self) -> Vec<u64> {
+        let view = self.view();
+        let mut res = Vec::new();
+
+        for site in &self.call_sites() {
+            unsafe {
+                let mut raw = site.as_raw();
+                let mut count = 0;
+                let callees = BNGetCallees(view.handle, &mut raw, &mut count);
+
+                res.extend(address_list(callees, count));
+            }
+        }
+
+        res
+    }
+
+    pub fn callees(&self) -> Vec<Ref<Function>> {
+        let view = self.view();
+        let platform = self.platform();
+
+        self.callee_addresses()
+            .into_iter()
+            .filter_map(|addr| view.function_at(&platform, addr).ok())
+            .collect()
+    }
+
+    /// Addresses referenced by the instruction at `addr`
+    pub fn code_refs_from(&self, addr: u64) -> Vec<u64> {
+        unsafe {
+            let mut raw = BNReferenceSource {
+                func: self.handle,
+                arch: self.arch().0,
+                addr,
+            };
+            let mut count = 0;
+            let refs = BNGetCodeReferencesFrom(self.view().handle, &mut raw, &mut count);
+
+            address_list(refs, count)
+        }
+    }
+
+    pub fn add_user_code_ref(&self, from_addr: u64, to_addr: u64) {
+        unsafe {
+            BNAddUserCodeReference(self.handle, self.arch().0, from_addr, to_addr);
+        }
+    }
+
+    pub fn remove_user_code_ref(&self, from_addr: u64, to_addr: u64) {
+        unsafe {
+            BNRemoveUserCodeReference(self.handle, self.arch().0, from_addr, to_addr);
+        }
+    }
+
+    pub fn add_user_address_tag(&self, addr: u64, tag: &Tag) {
+        unsafe { BNAddUserAddressTag(self.handle, self.arch().0, addr, tag.handle) }
+    }
+
+    pub fn add_auto_address_tag(&self, addr: u64, tag: &Tag) {
+        unsafe { BNAddAutoAddressTag(self.handle, self.arch().0, addr, tag.handle) }
+    }
+
+    pub fn remove_user_address_tag(&self, addr: u64, tag: &Tag) {
+        unsafe { BNRemoveUserAddressTag(self.handle, self.arch().0, addr, tag.handle) }
+    }
+
+    pub fn remove_auto_address_tag(&self, addr: u64, tag: &Tag) {
+        unsafe { BNRemoveAutoAddressTag(self.handle, self.arch().0, addr, tag.handle) }
+    }
+
+    pub fn address_tags_at(&self, addr: u64) -> Array<Tag> {
+        unsafe {
+            let mut count = 0;
+            let tags = BNGetAddressTags(self.handle, self.arch().0, addr, &mut count);
+
+            Array::new(tags, count, ())
+        }
+    }
+
+    pub fn address_tags_in_range(&self, range: ops::Range<u64>) -> Array<TagReference> {
+        unsafe {
+            let mut count = 0;
+            let refs = BNGetAddressTagsInRange(
+                self.handle,
+                self.arch().0,
+                range.start,
+                range.end,
+                &mut count,
+            );
+
+            Array::new(refs, count, ())
+        }
+    }
+
+    pub fn add_user_function_tag(&self, tag: &Tag) {
+        unsafe { BNAddUserFunctionTag(self.handle, tag.handle) }
+    }
+
+    pub fn add_auto_function_tag(&self, tag: &Tag) {
+        unsafe { BNAddAutoFunctionTag(self.handle, tag.handle) }
+    }
+
+    pub fn remove_user_function_tag(&self, tag: &Tag) {
+        unsafe { BNRemoveUserFunctionTag(self.handle, tag.handle) }
+    }
+
+    pub fn remove_auto_function_tag(&self, tag: &Tag) {
+        unsafe { BNRemoveAutoFunctionTag(self.handle, tag.handle) }
+    }
+
+    /// Tags placed on the function itself, rather than on addresses within it
+    pub fn function_tags(&self) -> Array<Tag> {
+        unsafe {
+            let mut count = 0;
+            let tags = BNGetFunctionTags(self.handle, &mut count);
+
+            Array::new(tags, count, ())
+        }
+    }
+
+    /// Every tag placed on this function or on addresses within it
+    pub fn tag_references(&self) -> Array<TagReference> {
+        unsafe {
+            let mut count = 0;
+            let refs = BNGetFunctionAllTagReferences(self.handle, &mut count);
+
+            Array::new(refs, count, ())
+        }
+    }
+
+    pub fn tag_references_of_type(&self, tag_type: &TagType) -> Array<TagReference> {
+        unsafe {
+            let mut count = 0;
+            let refs = BNGetFunctionTagReferencesOfType(self.handle, tag_type.handle, &mut count);
+
+            Array::new(refs, count, ())
+        }
+    }
+
+    /// Every variable in this function, with its name and type
+    pub fn variables(&self) -> Array<NamedTypedVariable> {
+        unsafe {
+            let mut count = 0;
+            let vars = BNGetFunctionVariables(self.handle, &mut count);
+
+            Array::new(vars, count, ())
+        }
+    }
+
+    pub fn parameter_variables(&self) -> Conf<Vec<Variable>> {
+        unsafe {
+            let mut raw = BNGetFunctionParameterVariables(self.handle);
+
+            let vars = if raw.vars.is_null() {
+                Vec::new()
+            } else {
+                slice::from_raw_parts(raw.vars, raw.count)
+                    .iter()
+                    .map(|&var| Variable::from_raw(var))
+                    .collect()
+            };
+            let confidence = raw.confidence;
+
+            BNFreeParameterVariables(&mut raw);
+
+            Conf::new(vars, confidence)
+        }
+    }
+
+    pub fn variable_name(&self, var: &Variable) -> BnString {
+        let raw = var.into_raw();
+
+        unsafe { BnString::from_raw(BNGetVariableName(self.handle, &raw)) }
+    }
+
+    pub fn variable_type(&self, var: &Variable) -> Option<Conf<Ref<Type>>> {
+        let raw = var.into_raw();
+        let ty = unsafe { BNGetVariableType(self.handle, &raw) };
+
+        if ty.type_.is_null() {
+            return None;
+        }
+
+        Some(ty.into())
+    }
+
+    pub fn is_variable_user_defined(&self, var: &Variable) -> bool {
+        let raw = var.into_raw();
+
+        unsafe { BNIsVariableUserDefined(self.handle, &raw) }
+    }
+
+    pub fn create_user_var<'a, S: BnStrCompatible, T: Into<Conf<&'a Type>>>(
+        &self,
+        var: &Variable,
+        ty: T,
+        name: S,
+        ignore_disjoint_uses: bool,
+    ) {
+        let raw = var.into_raw();
+        let mut ty: BNTypeWithConfidence = ty.into().into();
+        let name = name.as_bytes_with_nul();
+
+        unsafe {
+            BNCreateUserVariable(
+                self.handle,
+                &raw,
+                &mut ty,
+                name.as_ref().as_ptr() as *const _,
+                ignore_disjoint_uses,
+            );
+        }
+    }
+
+    pub fn create_auto_var<'a, S: BnStrCompatible, T: Into<Conf<&'a Type>>>(
+        &self,
+        var: &Variable,
+        ty: T,
+        name: S,
+        ignore_disjoint_uses: bool,
+    ) {
+        let raw = var.into_raw();
+        let mut ty: BNTypeWithConfidence = ty.into().into();
+        let name = name.as_bytes_with_nul();
+
+        unsafe {
+            BNCreateAutoVariable(
+                self.handle,
+                &raw,
+                &mut ty,
+                name.as_ref().as_ptr() as *const _,
+                ignore_disjoint_uses,
+            );
+        }
+    }
+
+    pub fn delete_user_var(&self, var: &Variable) {
+        let raw = var.into_raw();
+
+        unsafe { BNDeleteUserVariable(self.handle, &raw) }
+    }
+
+    pub fn delete_auto_var(&self, var: &Variable) {
+        let raw = var.into_raw();
+
+        unsafe { BNDeleteAutoVariable(self.handle, &raw) }
+    }
+
+    /// Gives `var` a user defined name, keeping its current type
+    pub fn rename_var<S: BnStrCompatible>(&self, var: &Variable, name: S) -> Result<(), ()> {
+        let ty = self.variable_type(var).ok_or(())?;
+
+        self.create_user_var(var, Conf::new(&*ty.contents, ty.confidence), name, false);
+        Ok(())
+    }
+
+    /// Gives `var` a user defined type, keeping its current name
+    pub fn retype_var<'a, T: Into<Conf<&'a Type>>>(&self, var: &Variable, ty: T) {
+        let name = self.variable_name(var);
+
+        self.create_user_var(var, ty, name, false);
+    }
+
+    /// Variables on the stack frame, ordered by offset
+    pub fn stack_layout(&self) -> Array<NamedTypedVariable> {
+        unsafe {
+            let mut count = 0;
+            let vars = BNGetStackLayout(self.handle, &mut count);
+
+            Array::new(vars, count, ())
+        }
+    }
+
+    /// The stack variable covering `offset` as seen by the instruction at `addr`
+    pub fn stack_variable_at_frame_offset(
+        &self,
+        addr: u64,
+        offset: i64,
+    ) -> Option<NamedTypedVariable> {
+        unsafe {
+            let mut raw: BNVariableNameAndType = mem::zeroed();
+
+            if !BNGetStackVariableAtFrameOffset(self.handle, self.arch().0, addr, offset, &mut raw)
+            {
+                return None;
+            }
+
+            let res = NamedTypedVariable::from_raw(&raw);
+            BNFreeVariableNameAndType(&mut raw);
+
+            Some(res)
+        }
+    }
+
+    pub fn create_user_stack_var<'a, S: BnStrCompatible, T: Into<Conf<&'a Type>>>(
+        &self,
+        offset: i64,
+        ty: T,
+        name: S,
+    ) {
+        let mut ty: BNTypeWithConfidence = ty.into().into();
+        let name = name.as_bytes_with_nul();
+
+        unsafe {
+            BNCreateUserStackVariable(
+                self.handle,
+                offset,
+                &mut ty,
+                name.as_ref().as_ptr() as *const _,
+            );
+        }
+    }
+
+    pub fn create_auto_stack_var<'a, S: BnStrCompatible, T: Into<Conf<&'a Type>>>(
+        &self,
+        offset: i64,
+        ty: T,
+        name: S,
+    ) {
+        let mut ty: BNTypeWithConfidence = ty.into().into();
+        let name = name.as_bytes_with_nul();
+
+        unsafe {
+            BNCreateAutoStackVariable(
+                self.handle,
+                offset,
+                &mut ty,
+                name.as_ref().as_ptr() as *const _,
+            );
+        }
+    }
+
+    pub fn delete_user_stack_var(&self, offset: i64) {
+        unsafe { BNDeleteUserStackVariable(self.handle, offset) }
+    }
+
+    pub fn delete_auto_stack_var(&self, offset: i64) {
+        unsafe { BNDeleteAutoStackVariable(self.handle, offset) }
+    }
+
     pub fn set_user_type(&self, t: Type) {
         unsafe {
             BNSetFunctionUserType(self.handle, t.handle);
@@ -0,0 +1,124 @@
+// Copyright 2021 Vector 35 Inc.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Hooks that run on every function during analysis, e.g. to name known stubs and thunks.
+
+use binaryninjacore_sys::*;
+
+use std::os::raw::c_void;
+
+use crate::architecture::{Architecture, CoreArchitecture};
+use crate::binaryview::BinaryView;
+use crate::function::Function;
+use crate::llil;
+use crate::mlil;
+
+pub trait FunctionRecognizer: 'static + Sync {
+    /// Called once the LLIL of `func` is available
+    ///
+    /// Returns whether the function was recognized; recognizers typically act on
+    /// a match by defining symbols or types in `view`.
+    fn recognize_low_level_il(
+        &self,
+        _view: &BinaryView,
+        _func: &Function,
+        _llil: &llil::RegularFunction<CoreArchitecture>,
+    ) -> bool {
+        false
+    }
+
+    fn recognize_medium_level_il(
+        &self,
+        _view: &BinaryView,
+        _func: &Function,
+        _mlil: &mlil::RegularFunction,
+    ) -> bool {
+        false
+    }
+}
+
+fn create_function_recognizer<R>(recognizer: R) -> BNFunctionRecognizer
+where
+    R: FunctionRecognizer,
+{
+    extern "C" fn cb_recognize_llil<R>(
+        ctxt: *mut c_void,
+        view: *mut BNBinaryView,
+        func: *mut BNFunction,
+        il: *mut BNLowLevelILFunction,
+    ) -> bool
+    where
+        R: FunctionRecognizer,
+    {
+        ffi_wrap!("FunctionRecognizer::recognize_low_level_il", unsafe {
+            let recognizer = &*(ctxt as *const R);
+            let view = BinaryView { handle: view };
+            let func = Function { handle: func };
+            let llil = llil::RegularFunction::from_raw(func.arch(), il);
+
+            recognizer.recognize_low_level_il(&view, &func, &llil)
+        })
+    }
+
+    extern "C" fn cb_recognize_mlil<R>(
+        ctxt: *mut c_void,
+        view: *mut BNBinaryView,
+        func: *mut BNFunction,
+        il: *mut BNMediumLevelILFunction,
+    ) -> bool
+    where
+        R: FunctionRecognizer,
+    {
+        ffi_wrap!("FunctionRecognizer::recognize_medium_level_il", unsafe {
+            let recognizer = &*(ctxt as *const R);
+            let view = BinaryView { handle: view };
+            let func = Function { handle: func };
+            let mlil = mlil::RegularFunction::from_raw(il);
+
+            recognizer.recognize_medium_level_il(&view, &func, &mlil)
+        })
+    }
+
+    // the core never releases recognizers, so neither do we
+    BNFunctionRecognizer {
+        context: Box::into_raw(Box::new(recognizer)) as *mut _,
+        recognizeLowLevelIL: Some(cb_recognize_llil::<R>),
+        recognizeMediumLevelIL: Some(cb_recognize_mlil::<R>),
+    }
+}
+
+/// Registers `recognizer` to run on functions of every architecture
+pub fn register_global_function_recognizer<R>(recognizer: R)
+where
+    R: FunctionRecognizer,
+{
+    let mut raw = create_function_recognizer(recognizer);
+
+    unsafe {
+        BNRegisterGlobalFunctionRecognizer(&mut raw);
+    }
+}
+
+/// Registers `recognizer` to run only on functions of `arch`
+pub fn register_arch_function_recognizer<A, R>(arch: &A, recognizer: R)
+where
+    A: Architecture,
+    R: FunctionRecognizer,
+{
+    let mut raw = create_function_recognizer(recognizer);
+
+    unsafe {
+        BNRegisterArchitectureFunctionRecognizer(arch.as_ref().0, &mut raw);
+    }
+}
@@ -0,0 +1,566 @@
+// Copyright 2021 Vector 35 Inc.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use binaryninjacore_sys::BNGetHighLevelILByIndex;
+use binaryninjacore_sys::BNHighLevelILInstruction;
+
+use super::operation;
+use super::operation::Operation;
+use super::*;
+
+macro_rules! visit {
+    ($f:expr, $($e:expr),*) => {
+        if let VisitorAction::Halt = $f($($e,)*) {
+            return VisitorAction::Halt;
+        }
+    }
+}
+
+pub struct Expression<'func> {
+    pub(crate) function: &'func Function,
+    pub(crate) expr_idx: usize,
+}
+
+impl<'func> Expression<'func> {
+    pub fn index(&self) -> usize {
+        self.expr_idx
+    }
+
+    /// The expression this one is an operand of, if any
+    pub fn parent(&self) -> Option<Expression<'func>> {
+        let op = unsafe { BNGetHighLevelILByIndex(self.function.handle, self.expr_idx, true) };
+
+        if op.parent == self.expr_idx || op.parent >= self.function.expression_count() {
+            return None;
+        }
+
+        Some(Expression {
+            function: self.function,
+            expr_idx: op.parent,
+        })
+    }
+
+    pub fn info(&self) -> ExprInfo<'func> {
+        use binaryninjacore_sys::BNHighLevelILOperation::*;
+
+        let function = self.function;
+        let expr_idx = self.expr_idx;
+        let op = unsafe { BNGetHighLevelILByIndex(function.handle, expr_idx, true) };
+
+        match op.operation {
+            HLIL_NOP => ExprInfo::Nop(Operation::new(function, op, expr_idx)),
+            HLIL_BLOCK => ExprInfo::Block(Operation::new(function, op, expr_idx)),
+            HLIL_IF => ExprInfo::If(Operation::new(function, op, expr_idx)),
+            HLIL_WHILE => ExprInfo::While(Operation::new(function, op, expr_idx)),
+            HLIL_DO_WHILE => ExprInfo::DoWhile(Operation::new(function, op, expr_idx)),
+            HLIL_FOR => ExprInfo::For(Operation::new(function, op, expr_idx)),
+            HLIL_SWITCH => ExprInfo::Switch(Operation::new(function, op, expr_idx)),
+            HLIL_CASE => ExprInfo::Case(Operation::new(function, op, expr_idx)),
+            HLIL_BREAK => ExprInfo::Break(Operation::new(function, op, expr_idx)),
+            HLIL_CONTINUE => ExprInfo::Continue(Operation::new(function, op, expr_idx)),
+            HLIL_JUMP => ExprInfo::Jump(Operation::new(function, op, expr_idx)),
+            HLIL_RET => ExprInfo::Ret(Operation::new(function, op, expr_idx)),
+            HLIL_NORET => ExprInfo::NoRet(Operation::new(function, op, expr_idx)),
+            HLIL_GOTO => ExprInfo::Goto(Operation::new(function, op, expr_idx)),
+            HLIL_LABEL => ExprInfo::Label(Operation::new(function, op, expr_idx)),
+
+            HLIL_VAR_DECLARE => ExprInfo::VarDeclare(Operation::new(function, op, expr_idx)),
+            HLIL_VAR_INIT => ExprInfo::VarInit(Operation::new(function, op, expr_idx)),
+            HLIL_ASSIGN => ExprInfo::Assign(Operation::new(function, op, expr_idx)),
+            HLIL_ASSIGN_UNPACK => ExprInfo::AssignUnpack(Operation::new(function, op, expr_idx)),
+
+            HLIL_VAR => ExprInfo::Var(Operation::new(function, op, expr_idx)),
+            HLIL_STRUCT_FIELD => ExprInfo::StructField(Operation::new(function, op, expr_idx)),
+            HLIL_ARRAY_INDEX => ExprInfo::ArrayIndex(Operation::new(function, op, expr_idx)),
+            HLIL_SPLIT => ExprInfo::Split(Operation::new(function, op, expr_idx)),
+            HLIL_DEREF => ExprInfo::Deref(Operation::new(function, op, expr_idx)),
+            HLIL_DEREF_FIELD => ExprInfo::DerefField(Operation::new(function, op, expr_idx)),
+            HLIL_ADDRESS_OF => ExprInfo::AddressOf(Operation::new(function, op, expr_idx)),
+
+            HLIL_CONST => ExprInfo::Const(Operation::new(function, op, expr_idx)),
+            HLIL_CONST_PTR => ExprInfo::ConstPtr(Operation::new(function, op, expr_idx)),
+            HLIL_EXTERN_PTR => ExprInfo::ExternPtr(Operation::new(function, op, expr_idx)),
+            HLIL_FLOAT_CONST => ExprInfo::FloatConst(Operation::new(function, op, expr_idx)),
+            HLIL_IMPORT => ExprInfo::Import(Operation::new(function, op, expr_idx)),
+
+            HLIL_ADD => ExprInfo::Add(Operation::new(function, op, expr_idx)),
+            HLIL_ADC => ExprInfo::Adc(Operation::new(function, op, expr_idx)),
+            HLIL_SUB => ExprInfo::Sub(Operation::new(function, op, expr_idx)),
+            HLIL_SBB => ExprInfo::Sbb(Operation::new(function, op, expr_idx)),
+            HLIL_AND => ExprInfo::And(Operation::new(function, op, expr_idx)),
+            HLIL_OR => ExprInfo::Or(Operation::new(function, op, expr_idx)),
+            HLIL_XOR => ExprInfo::Xor(Operation::new(function, op, expr_idx)),
+            HLIL_LSL => ExprInfo::Lsl(Operation::new(function, op, expr_idx)),
+            HLIL_LSR => ExprInfo::Lsr(Operation::new(function, op, expr_idx)),
+            HLIL_ASR => ExprInfo::Asr(Operation::new(function, op, expr_idx)),
+            HLIL_ROL => ExprInfo::Rol(Operation::new(function, op, expr_idx)),
+            HLIL_RLC => ExprInfo::Rlc(Operation::new(function, op, expr_idx)),
+            HLIL_ROR => ExprInfo::Ror(Operation::new(function, op, expr_idx)),
+            HLIL_RRC => ExprInfo::Rrc(Operation::new(function, op, expr_idx)),
+            HLIL_MUL => ExprInfo::Mul(Operation::new(function, op, expr_idx)),
+            HLIL_MULU_DP => ExprInfo::MuluDp(Operation::new(function, op, expr_idx)),
+            HLIL_MULS_DP => ExprInfo::MulsDp(Operation::new(function, op, expr_idx)),
+            HLIL_DIVU => ExprInfo::Divu(Operation::new(function, op, expr_idx)),
+            HLIL_DIVU_DP => ExprInfo::DivuDp(Operation::new(function, op, expr_idx)),
+            HLIL_DIVS => ExprInfo::Divs(Operation::new(function, op, expr_idx)),
+            HLIL_DIVS_DP => ExprInfo::DivsDp(Operation::new(function, op, expr_idx)),
+            HLIL_MODU => ExprInfo::Modu(Operation::new(function, op, expr_idx)),
+            HLIL_MODU_DP => ExprInfo::ModuDp(Operation::new(function, op, expr_idx)),
+            HLIL_MODS => ExprInfo::Mods(Operation::new(function, op, expr_idx)),
+            HLIL_MODS_DP => ExprInfo::ModsDp(Operation::new(function, op, expr_idx)),
+
+            HLIL_NEG => ExprInfo::Neg(Operation::new(function, op, expr_idx)),
+            HLIL_NOT => ExprInfo::Not(Operation::new(function, op, expr_idx)),
+            HLIL_SX => ExprInfo::Sx(Operation::new(function, op, expr_idx)),
+            HLIL_ZX => ExprInfo::Zx(Operation::new(function, op, expr_idx)),
+            HLIL_LOW_PART => ExprInfo::LowPart(Operation::new(function, op, expr_idx)),
+
+            HLIL_CALL => ExprInfo::Call(Operation::new(function, op, expr_idx)),
+            HLIL_TAILCALL => ExprInfo::Tailcall(Operation::new(function, op, expr_idx)),
+            HLIL_SYSCALL => ExprInfo::Syscall(Operation::new(function, op, expr_idx)),
+            HLIL_INTRINSIC => ExprInfo::Intrinsic(Operation::new(function, op, expr_idx)),
+
+            HLIL_CMP_E => ExprInfo::CmpE(Operation::new(function, op, expr_idx)),
+            HLIL_CMP_NE => ExprInfo::CmpNe(Operation::new(function, op, expr_idx)),
+            HLIL_CMP_SLT => ExprInfo::CmpSlt(Operation::new(function, op, expr_idx)),
+            HLIL_CMP_ULT => ExprInfo::CmpUlt(Operation::new(function, op, expr_idx)),
+            HLIL_CMP_SLE => ExprInfo::CmpSle(Operation::new(function, op, expr_idx)),
+            HLIL_CMP_ULE => ExprInfo::CmpUle(Operation::new(function, op, expr_idx)),
+            HLIL_CMP_SGE => ExprInfo::CmpSge(Operation::new(function, op, expr_idx)),
+            HLIL_CMP_UGE => ExprInfo::CmpUge(Operation::new(function, op, expr_idx)),
+            HLIL_CMP_SGT => ExprInfo::CmpSgt(Operation::new(function, op, expr_idx)),
+            HLIL_CMP_UGT => ExprInfo::CmpUgt(Operation::new(function, op, expr_idx)),
+            HLIL_TEST_BIT => ExprInfo::TestBit(Operation::new(function, op, expr_idx)),
+            HLIL_BOOL_TO_INT => ExprInfo::BoolToInt(Operation::new(function, op, expr_idx)),
+            HLIL_ADD_OVERFLOW => ExprInfo::AddOverflow(Operation::new(function, op, expr_idx)),
+
+            HLIL_FADD => ExprInfo::Fadd(Operation::new(function, op, expr_idx)),
+            HLIL_FSUB => ExprInfo::Fsub(Operation::new(function, op, expr_idx)),
+            HLIL_FMUL => ExprInfo::Fmul(Operation::new(function, op, expr_idx)),
+            HLIL_FDIV => ExprInfo::Fdiv(Operation::new(function, op, expr_idx)),
+            HLIL_FSQRT => ExprInfo::Fsqrt(Operation::new(function, op, expr_idx)),
+            HLIL_FNEG => ExprInfo::Fneg(Operation::new(function, op, expr_idx)),
+            HLIL_FABS => ExprInfo::Fabs(Operation::new(function, op, expr_idx)),
+            HLIL_FLOAT_TO_INT => ExprInfo::FloatToInt(Operation::new(function, op, expr_idx)),
+            HLIL_INT_TO_FLOAT => ExprInfo::IntToFloat(Operation::new(function, op, expr_idx)),
+            HLIL_FLOAT_CONV => ExprInfo::FloatConv(Operation::new(function, op, expr_idx)),
+            HLIL_ROUND_TO_INT => ExprInfo::RoundToInt(Operation::new(function, op, expr_idx)),
+            HLIL_FLOOR => ExprInfo::Floor(Operation::new(function, op, expr_idx)),
+            HLIL_CEIL => ExprInfo::Ceil(Operation::new(function, op, expr_idx)),
+            HLIL_FTRUNC => ExprInfo::Ftrunc(Operation::new(function, op, expr_idx)),
+
+            HLIL_FCMP_E => ExprInfo::FcmpE(Operation::new(function, op, expr_idx)),
+            HLIL_FCMP_NE => ExprInfo::FcmpNe(Operation::new(function, op, expr_idx)),
+            HLIL_FCMP_LT => ExprInfo::FcmpLt(Operation::new(function, op, expr_idx)),
+            HLIL_FCMP_LE => ExprInfo::FcmpLe(Operation::new(function, op, expr_idx)),
+            HLIL_FCMP_GE => ExprInfo::FcmpGe(Operation::new(function, op, expr_idx)),
+            HLIL_FCMP_GT => ExprInfo::FcmpGt(Operation::new(function, op, expr_idx)),
+            HLIL_FCMP_O => ExprInfo::FcmpO(Operation::new(function, op, expr_idx)),
+            HLIL_FCMP_UO => ExprInfo::FcmpUo(Operation::new(function, op, expr_idx)),
+
+            HLIL_BP => ExprInfo::Bp(Operation::new(function, op, expr_idx)),
+            HLIL_TRAP => ExprInfo::Trap(Operation::new(function, op, expr_idx)),
+            HLIL_UNIMPL => ExprInfo::Unimpl(Operation::new(function, op, expr_idx)),
+            HLIL_UNIMPL_MEM => ExprInfo::UnimplMem(Operation::new(function, op, expr_idx)),
+            HLIL_UNDEF => ExprInfo::Undef(Operation::new(function, op, expr_idx)),
+
+            // SSA operations only show up in the SSA form, which isn't exposed
+            _ => {
+                #[cfg(debug_assertions)]
+                {
+                    error!(
+                        "Got unexpected operation {:?} in hlil expr at 0x{:x}",
+                        op.operation, op.address
+                    );
+                }
+
+                ExprInfo::Undef(Operation::new(function, op, expr_idx))
+            }
+        }
+    }
+
+    /// Visits this expression and then its operands, depth first and in source order
+    pub fn visit_tree<F>(&self, f: &mut F) -> VisitorAction
+    where
+        F: FnMut(&Expression<'func>, &ExprInfo<'func>) -> VisitorAction,
+    {
+        use self::ExprInfo::*;
+
+        let info = self.info();
+
+        match f(self, &info) {
+            VisitorAction::Descend => {}
+            action => return action,
+        };
+
+        let fb = &mut |e: &Expression<'func>| e.visit_tree(f);
+
+        match info {
+            Block(ref op) => {
+                for e in op.body() {
+                    visit!(fb, &e);
+                }
+            }
+            If(ref op) => {
+                visit!(fb, &op.condition());
+                visit!(fb, &op.true_body());
+                visit!(fb, &op.false_body());
+            }
+            While(ref op) => {
+                visit!(fb, &op.condition());
+                visit!(fb, &op.body());
+            }
+            DoWhile(ref op) => {
+                visit!(fb, &op.body());
+                visit!(fb, &op.condition());
+            }
+            For(ref op) => {
+                visit!(fb, &op.init());
+                visit!(fb, &op.condition());
+                visit!(fb, &op.update());
+                visit!(fb, &op.body());
+            }
+            Switch(ref op) => {
+                visit!(fb, &op.condition());
+                for e in op.cases() {
+                    visit!(fb, &e);
+                }
+                visit!(fb, &op.default());
+            }
+            Case(ref op) => {
+                for e in op.values() {
+                    visit!(fb, &e);
+                }
+                visit!(fb, &op.body());
+            }
+            Jump(ref op) => visit!(fb, &op.dest()),
+            Ret(ref op) => {
+                for e in op.sources() {
+                    visit!(fb, &e);
+                }
+            }
+            VarInit(ref op) => visit!(fb, &op.source_expr()),
+            Assign(ref op) => {
+                visit!(fb, &op.dest());
+                visit!(fb, &op.source_expr());
+            }
+            AssignUnpack(ref op) => {
+                for e in op.dest() {
+                    visit!(fb, &e);
+                }
+                visit!(fb, &op.source_expr());
+            }
+            StructField(ref op) | DerefField(ref op) => visit!(fb, &op.source_expr()),
+            ArrayIndex(ref op) => {
+                visit!(fb, &op.source_expr());
+                visit!(fb, &op.index());
+            }
+            Split(ref op) => {
+                visit!(fb, &op.high());
+                visit!(fb, &op.low());
+            }
+            Call(ref op) | Tailcall(ref op) => {
+                visit!(fb, &op.target());
+                for e in op.params() {
+                    visit!(fb, &e);
+                }
+            }
+            Syscall(ref op) => {
+                for e in op.params() {
+                    visit!(fb, &e);
+                }
+            }
+            Intrinsic(ref op) => {
+                for e in op.params() {
+                    visit!(fb, &e);
+                }
+            }
+            _ => {
+                if let Some(op) = info.as_cmp_op() {
+                    visit!(fb, &op.left());
+                    visit!(fb, &op.right());
+                } else if let Some(op) = info.as_binary_op() {
+                    visit!(fb, &op.left());
+                    visit!(fb, &op.right());
+                } else if let Some(op) = info.as_binary_op_carry() {
+                    visit!(fb, &op.left());
+                    visit!(fb, &op.right());
+                    visit!(fb, &op.carry());
+                } else if let Some(op) = info.as_unary_op() {
+                    visit!(fb, &op.operand());
+                }
+            }
+        }
+
+        VisitorAction::Sibling
+    }
+
+    /// Decompiled source for this expression and everything below it
+    pub fn text_lines(&self) -> Vec<String> {
+        self.function.expr_text(self.expr_idx)
+    }
+}
+
+impl<'func> fmt::Debug for Expression<'func> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let info = self.info();
+        write!(
+            f,
+            "<expr {}: {:?}>",
+            self.expr_idx,
+            info.raw_struct().operation
+        )
+    }
+}
+
+pub enum ExprInfo<'func> {
+    Nop(Operation<'func, operation::NoArgs>),
+    Block(Operation<'func, operation::Block>),
+    If(Operation<'func, operation::If>),
+    While(Operation<'func, operation::While>),
+    DoWhile(Operation<'func, operation::DoWhile>),
+    For(Operation<'func, operation::For>),
+    Switch(Operation<'func, operation::Switch>),
+    Case(Operation<'func, operation::Case>),
+    Break(Operation<'func, operation::NoArgs>),
+    Continue(Operation<'func, operation::NoArgs>),
+    Jump(Operation<'func, operation::Jump>),
+    Ret(Operation<'func, operation::Ret>),
+    NoRet(Operation<'func, operation::NoArgs>),
+    Goto(Operation<'func, operation::Label>),
+    Label(Operation<'func, operation::Label>),
+
+    VarDeclare(Operation<'func, operation::VarDeclare>),
+    VarInit(Operation<'func, operation::VarInit>),
+    Assign(Operation<'func, operation::Assign>),
+    AssignUnpack(Operation<'func, operation::AssignUnpack>),
+
+    Var(Operation<'func, operation::Var>),
+    StructField(Operation<'func, operation::Field>),
+    ArrayIndex(Operation<'func, operation::ArrayIndex>),
+    Split(Operation<'func, operation::Split>),
+    Deref(Operation<'func, operation::UnaryOp>),
+    DerefField(Operation<'func, operation::Field>),
+    AddressOf(Operation<'func, operation::UnaryOp>),
+
+    Const(Operation<'func, operation::Const>),
+    ConstPtr(Operation<'func, operation::Const>),
+    ExternPtr(Operation<'func, operation::ExternPtr>),
+    FloatConst(Operation<'func, operation::FloatConst>),
+    Import(Operation<'func, operation::Const>),
+
+    Add(Operation<'func, operation::BinaryOp>),
+    Adc(Operation<'func, operation::BinaryOpCarry>),
+    Sub(Operation<'func, operation::BinaryOp>),
+    Sbb(Operation<'func, operation::BinaryOpCarry>),
+    And(Operation<'func, operation::BinaryOp>),
+    Or(Operation<'func, operation::BinaryOp>),
+    Xor(Operation<'func, operation::BinaryOp>),
+    Lsl(Operation<'func, operation::BinaryOp>),
+    Lsr(Operation<'func, operation::BinaryOp>),
+    Asr(Operation<'func, operation::BinaryOp>),
+    Rol(Operation<'func, operation::BinaryOp>),
+    Rlc(Operation<'func, operation::BinaryOpCarry>),
+    Ror(Operation<'func, operation::BinaryOp>),
+    Rrc(Operation<'func, operation::BinaryOpCarry>),
+    Mul(Operation<'func, operation::BinaryOp>),
+
+    MuluDp(Operation<'func, operation::BinaryOp>),
+    MulsDp(Operation<'func, operation::BinaryOp>),
+
+    Divu(Operation<'func, operation::BinaryOp>),
+    DivuDp(Operation<'func, operation::BinaryOp>),
+    Divs(Operation<'func, operation::BinaryOp>),
+    DivsDp(Operation<'func, operation::BinaryOp>),
+
+    Modu(Operation<'func, operation::BinaryOp>),
+    ModuDp(Operation<'func, operation::BinaryOp>),
+    Mods(Operation<'func, operation::BinaryOp>),
+    ModsDp(Operation<'func, operation::BinaryOp>),
+
+    Neg(Operation<'func, operation::UnaryOp>),
+    Not(Operation<'func, operation::UnaryOp>),
+    Sx(Operation<'func, operation::UnaryOp>),
+    Zx(Operation<'func, operation::UnaryOp>),
+    LowPart(Operation<'func, operation::UnaryOp>),
+
+    Call(Operation<'func, operation::Call>),
+    Tailcall(Operation<'func, operation::Call>),
+    Syscall(Operation<'func, operation::Syscall>),
+    Intrinsic(Operation<'func, operation::Intrinsic>),
+
+    CmpE(Operation<'func, operation::Condition>),
+    CmpNe(Operation<'func, operation::Condition>),
+    CmpSlt(Operation<'func, operation::Condition>),
+    CmpUlt(Operation<'func, operation::Condition>),
+    CmpSle(Operation<'func, operation::Condition>),
+    CmpUle(Operation<'func, operation::Condition>),
+    CmpSge(Operation<'func, operation::Condition>),
+    CmpUge(Operation<'func, operation::Condition>),
+    CmpSgt(Operation<'func, operation::Condition>),
+    CmpUgt(Operation<'func, operation::Condition>),
+
+    TestBit(Operation<'func, operation::BinaryOp>),
+    BoolToInt(Operation<'func, operation::UnaryOp>),
+    AddOverflow(Operation<'func, operation::BinaryOp>),
+
+    Fadd(Operation<'func, operation::BinaryOp>),
+    Fsub(Operation<'func, operation::BinaryOp>),
+    Fmul(Operation<'func, operation::BinaryOp>),
+    Fdiv(Operation<'func, operation::BinaryOp>),
+    Fsqrt(Operation<'func, operation::UnaryOp>),
+    Fneg(Operation<'func, operation::UnaryOp>),
+    Fabs(Operation<'func, operation::UnaryOp>),
+    FloatToInt(Operation<'func, operation::UnaryOp>),
+    IntToFloat(Operation<'func, operation::UnaryOp>),
+    FloatConv(Operation<'func, operation::UnaryOp>),
+    RoundToInt(Operation<'func, operation::UnaryOp>),
+    Floor(Operation<'func, operation::UnaryOp>),
+    Ceil(Operation<'func, operation::UnaryOp>),
+    Ftrunc(Operation<'func, operation::UnaryOp>),
+
+    FcmpE(Operation<'func, operation::Condition>),
+    FcmpNe(Operation<'func, operation::Condition>),
+    FcmpLt(Operation<'func, operation::Condition>),
+    FcmpLe(Operation<'func, operation::Condition>),
+    FcmpGe(Operation<'func, operation::Condition>),
+    FcmpGt(Operation<'func, operation::Condition>),
+    FcmpO(Operation<'func, operation::Condition>),
+    FcmpUo(Operation<'func, operation::Condition>),
+
+    Bp(Operation<'func, operation::NoArgs>),
+    Trap(Operation<'func, operation::Trap>),
+    Unimpl(Operation<'func, operation::NoArgs>),
+    UnimplMem(Operation<'func, operation::UnaryOp>),
+    Undef(Operation<'func, operation::NoArgs>),
+}
+
+impl<'func> ExprInfo<'func> {
+    pub fn address(&self) -> u64 {
+        self.raw_struct().address
+    }
+
+    /// Determines if the expressions represent the same operation
+    ///
+    /// It does not examine the operands for equality.
+    pub fn is_same_op_as(&self, other: &Self) -> bool {
+        self.raw_struct().operation == other.raw_struct().operation
+    }
+
+    pub fn as_cmp_op(&self) -> Option<&Operation<'func, operation::Condition>> {
+        use self::ExprInfo::*;
+
+        match *self {
+            CmpE(ref op) | CmpNe(ref op) | CmpSlt(ref op) | CmpUlt(ref op) | CmpSle(ref op)
+            | CmpUle(ref op) | CmpSge(ref op) | CmpUge(ref op) | CmpSgt(ref op)
+            | CmpUgt(ref op) | FcmpE(ref op) | FcmpNe(ref op) | FcmpLt(ref op) | FcmpLe(ref op)
+            | FcmpGe(ref op) | FcmpGt(ref op) | FcmpO(ref op) | FcmpUo(ref op) => Some(op),
+            _ => None,
+        }
+    }
+
+    pub fn as_binary_op(&self) -> Option<&Operation<'func, operation::BinaryOp>> {
+        use self::ExprInfo::*;
+
+        match *self {
+            Add(ref op) | Sub(ref op) | And(ref op) | Or(ref op) | Xor(ref op) | Lsl(ref op)
+            | Lsr(ref op) | Asr(ref op) | Rol(ref op) | Ror(ref op) | Mul(ref op)
+            | MuluDp(ref op) | MulsDp(ref op) | Divu(ref op) | DivuDp(ref op) | Divs(ref op)
+            | DivsDp(ref op) | Modu(ref op) | ModuDp(ref op) | Mods(ref op) | ModsDp(ref op)
+            | TestBit(ref op) | AddOverflow(ref op) | Fadd(ref op) | Fsub(ref op)
+            | Fmul(ref op) | Fdiv(ref op) => Some(op),
+            _ => None,
+        }
+    }
+
+    pub fn as_binary_op_carry(&self) -> Option<&Operation<'func, operation::BinaryOpCarry>> {
+        use self::ExprInfo::*;
+
+        match *self {
+            Adc(ref op) | Sbb(ref op) | Rlc(ref op) | Rrc(ref op) => Some(op),
+            _ => None,
+        }
+    }
+
+    pub fn as_unary_op(&self) -> Option<&Operation<'func, operation::UnaryOp>> {
+        use self::ExprInfo::*;
+
+        match *self {
+            Deref(ref op) | AddressOf(ref op) | Neg(ref op) | Not(ref op) | Sx(ref op)
+            | Zx(ref op) | LowPart(ref op) | BoolToInt(ref op) | Fsqrt(ref op) | Fneg(ref op)
+            | Fabs(ref op) | FloatToInt(ref op) | IntToFloat(ref op) | FloatConv(ref op)
+            | RoundToInt(ref op) | Floor(ref op) | Ceil(ref op) | Ftrunc(ref op)
+            | UnimplMem(ref op) => Some(op),
+            _ => None,
+        }
+    }
+
+    pub(crate) fn raw_struct(&self) -> &BNHighLevelILInstruction {
+        use self::ExprInfo::*;
+
+        if let Some(op) = self.as_cmp_op() {
+            return &op.op;
+        }
+
+        if let Some(op) = self.as_binary_op() {
+            return &op.op;
+        }
+
+        if let Some(op) = self.as_binary_op_carry() {
+            return &op.op;
+        }
+
+        if let Some(op) = self.as_unary_op() {
+            return &op.op;
+        }
+
+        match *self {
+            Nop(ref op) | Break(ref op) | Continue(ref op) | NoRet(ref op) | Bp(ref op)
+            | Unimpl(ref op) | Undef(ref op) => &op.op,
+
+            Block(ref op) => &op.op,
+            If(ref op) => &op.op,
+            While(ref op) => &op.op,
+            DoWhile(ref op) => &op.op,
+            For(ref op) => &op.op,
+            Switch(ref op) => &op.op,
+            Case(ref op) => &op.op,
+            Jump(ref op) => &op.op,
+            Ret(ref op) => &op.op,
+            Goto(ref op) | Label(ref op) => &op.op,
+
+            VarDeclare(ref op) => &op.op,
+            VarInit(ref op) => &op.op,
+            Assign(ref op) => &op.op,
+            AssignUnpack(ref op) => &op.op,
+
+            Var(ref op) => &op.op,
+            StructField(ref op) | DerefField(ref op) => &op.op,
+            ArrayIndex(ref op) => &op.op,
+            Split(ref op) => &op.op,
+
+            Const(ref op) | ConstPtr(ref op) | Import(ref op) => &op.op,
+            ExternPtr(ref op) => &op.op,
+            FloatConst(ref op) => &op.op,
+
+            Call(ref op) | Tailcall(ref op) => &op.op,
+            Syscall(ref op) => &op.op,
+            Intrinsic(ref op) => &op.op,
+            Trap(ref op) => &op.op,
+
+            // handled by the as_*_op helpers above
+            _ => unreachable!(),
+        }
+    }
+}
@@ -0,0 +1,190 @@
+// Copyright 2021 Vector 35 Inc.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use binaryninjacore_sys::BNFreeHighLevelILFunction;
+use binaryninjacore_sys::BNHighLevelILFunction;
+use binaryninjacore_sys::BNNewHighLevelILFunctionReference;
+
+use std::ffi::CStr;
+use std::ptr;
+use std::slice;
+
+use crate::function::Function as NativeFunction;
+use crate::mlil;
+use crate::rc::*;
+
+use super::*;
+
+pub struct Function {
+    pub(crate) handle: *mut BNHighLevelILFunction,
+}
+
+unsafe impl Send for Function {}
+unsafe impl Sync for Function {}
+
+impl Eq for Function {}
+impl PartialEq for Function {
+    fn eq(&self, rhs: &Self) -> bool {
+        self.handle == rhs.handle
+    }
+}
+
+use std::hash::{Hash, Hasher};
+impl Hash for Function {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.handle.hash(state);
+    }
+}
+
+impl Function {
+    pub(crate) unsafe fn from_raw(handle: *mut BNHighLevelILFunction) -> Self {
+        debug_assert!(!handle.is_null());
+
+        Self { handle }
+    }
+
+    pub fn owner_function(&self) -> Ref<NativeFunction> {
+        use binaryninjacore_sys::BNGetHighLevelILOwnerFunction;
+
+        unsafe { NativeFunction::from_raw(BNGetHighLevelILOwnerFunction(self.handle)) }
+    }
+
+    pub fn medium_level_il(&self) -> Result<Ref<mlil::RegularFunction>, ()> {
+        use binaryninjacore_sys::BNGetMediumLevelILForHighLevelILFunction;
+
+        unsafe {
+            let mlil = BNGetMediumLevelILForHighLevelILFunction(self.handle);
+
+            if mlil.is_null() {
+                return Err(());
+            }
+
+            Ok(Ref::new(mlil::RegularFunction::from_raw(mlil)))
+        }
+    }
+
+    /// The top of the AST, normally a `Block` holding the function body
+    pub fn root(&self) -> Expression {
+        use binaryninjacore_sys::BNGetHighLevelILRootExpr;
+
+        Expression {
+            function: self,
+            expr_idx: unsafe { BNGetHighLevelILRootExpr(self.handle) },
+        }
+    }
+
+    pub fn instruction_from_idx(&self, instr_idx: usize) -> Instruction {
+        if instr_idx >= self.instruction_count() {
+            panic!("instruction index {} out of bounds", instr_idx);
+        }
+
+        Instruction {
+            function: self,
+            instr_idx,
+        }
+    }
+
+    pub fn instruction_count(&self) -> usize {
+        unsafe {
+            use binaryninjacore_sys::BNGetHighLevelILInstructionCount;
+            BNGetHighLevelILInstructionCount(self.handle)
+        }
+    }
+
+    pub fn expression_count(&self) -> usize {
+        unsafe {
+            use binaryninjacore_sys::BNGetHighLevelILExprCount;
+            BNGetHighLevelILExprCount(self.handle)
+        }
+    }
+
+    /// Decompiled source of the whole function, one entry per line
+    pub fn text_lines(&self) -> Vec<String> {
+        self.root().text_lines()
+    }
+
+    pub(crate) fn operand_list(&self, expr_idx: usize, operand: usize) -> Vec<u64> {
+        use binaryninjacore_sys::BNHighLevelILFreeOperandList;
+        use binaryninjacore_sys::BNHighLevelILGetOperandList;
+
+        unsafe {
+            let mut count = 0;
+            let raw = BNHighLevelILGetOperandList(self.handle, expr_idx, operand, &mut count);
+
+            if raw.is_null() {
+                return Vec::new();
+            }
+
+            let list = slice::from_raw_parts(raw, count).to_vec();
+            BNHighLevelILFreeOperandList(raw);
+
+            list
+        }
+    }
+
+    pub(crate) fn expr_text(&self, expr_idx: usize) -> Vec<String> {
+        use binaryninjacore_sys::BNFreeDisassemblyTextLines;
+        use binaryninjacore_sys::BNGetHighLevelILExprText;
+
+        unsafe {
+            let mut count = 0;
+            let lines =
+                BNGetHighLevelILExprText(self.handle, expr_idx, true, &mut count, ptr::null_mut());
+
+            if lines.is_null() {
+                return Vec::new();
+            }
+
+            let res = slice::from_raw_parts(lines, count)
+                .iter()
+                .map(|line| {
+                    slice::from_raw_parts(line.tokens, line.count)
+                        .iter()
+                        .map(|token| CStr::from_ptr(token.text).to_string_lossy())
+                        .collect()
+                })
+                .collect();
+
+            BNFreeDisassemblyTextLines(lines, count);
+
+            res
+        }
+    }
+}
+
+impl ToOwned for Function {
+    type Owned = Ref<Self>;
+
+    fn to_owned(&self) -> Self::Owned {
+        unsafe { RefCountable::inc_ref(self) }
+    }
+}
+
+unsafe impl RefCountable for Function {
+    unsafe fn inc_ref(handle: &Self) -> Ref<Self> {
+        Ref::new(Self {
+            handle: BNNewHighLevelILFunctionReference(handle.handle),
+        })
+    }
+
+    unsafe fn dec_ref(handle: &Self) {
+        BNFreeHighLevelILFunction(handle.handle);
+    }
+}
+
+impl fmt::Debug for Function {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "<hlil func handle {:p}>", self.handle)
+    }
+}
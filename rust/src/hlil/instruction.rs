@@ -0,0 +1,63 @@
+// Copyright 2021 Vector 35 Inc.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use binaryninjacore_sys::BNGetHighLevelILIndexForInstruction;
+
+use super::*;
+
+/// A top level statement of the function
+///
+/// HLIL statements are themselves expressions, so everything
+/// interesting lives on the `Expression` returned by `expression`.
+pub struct Instruction<'func> {
+    pub(crate) function: &'func Function,
+    pub(crate) instr_idx: usize,
+}
+
+impl<'func> Instruction<'func> {
+    pub fn index(&self) -> usize {
+        self.instr_idx
+    }
+
+    pub fn expression(&self) -> Expression<'func> {
+        let expr_idx =
+            unsafe { BNGetHighLevelILIndexForInstruction(self.function.handle, self.instr_idx) };
+
+        Expression {
+            function: self.function,
+            expr_idx,
+        }
+    }
+
+    pub fn address(&self) -> u64 {
+        self.expression().info().address()
+    }
+
+    pub fn info(&self) -> ExprInfo<'func> {
+        self.expression().info()
+    }
+
+    pub fn visit_tree<F>(&self, f: &mut F) -> VisitorAction
+    where
+        F: FnMut(&Expression<'func>, &ExprInfo<'func>) -> VisitorAction,
+    {
+        self.expression().visit_tree(f)
+    }
+}
+
+impl<'func> fmt::Debug for Instruction<'func> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "<instr {}: {:?}>", self.instr_idx, self.expression())
+    }
+}
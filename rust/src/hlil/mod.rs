@@ -0,0 +1,28 @@
+// Copyright 2021 Vector 35 Inc.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::fmt;
+
+use crate::types::Variable;
+
+mod expression;
+mod function;
+mod instruction;
+pub mod operation;
+
+pub use self::expression::*;
+pub use self::function::*;
+pub use self::instruction::*;
+
+pub use crate::llil::VisitorAction;
@@ -0,0 +1,552 @@
+// Copyright 2021 Vector 35 Inc.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use binaryninjacore_sys::BNHighLevelILInstruction;
+
+use std::marker::PhantomData;
+
+use crate::string::BnString;
+
+use super::*;
+
+pub struct Operation<'func, O>
+where
+    O: OperationArguments,
+{
+    pub(crate) function: &'func Function,
+    pub(crate) op: BNHighLevelILInstruction,
+    pub(crate) expr_idx: usize,
+    _args: PhantomData<O>,
+}
+
+impl<'func, O> Operation<'func, O>
+where
+    O: OperationArguments,
+{
+    pub(crate) fn new(
+        function: &'func Function,
+        op: BNHighLevelILInstruction,
+        expr_idx: usize,
+    ) -> Self {
+        Self {
+            function,
+            op,
+            expr_idx,
+            _args: PhantomData,
+        }
+    }
+
+    pub fn address(&self) -> u64 {
+        self.op.address
+    }
+
+    fn expr(&self, operand: usize) -> Expression<'func> {
+        Expression {
+            function: self.function,
+            expr_idx: self.op.operands[operand] as usize,
+        }
+    }
+
+    fn expr_list(&self, operand: usize) -> Vec<Expression<'func>> {
+        self.function
+            .operand_list(self.expr_idx, operand)
+            .into_iter()
+            .map(|expr_idx| Expression {
+                function: self.function,
+                expr_idx: expr_idx as usize,
+            })
+            .collect()
+    }
+}
+
+// HLIL_NOP, HLIL_BREAK, HLIL_CONTINUE, HLIL_NORET, HLIL_BP, HLIL_UNDEF, HLIL_UNIMPL
+pub struct NoArgs;
+
+// HLIL_BLOCK
+pub struct Block;
+
+impl<'func> Operation<'func, Block> {
+    pub fn body(&self) -> Vec<Expression<'func>> {
+        self.expr_list(0)
+    }
+}
+
+// HLIL_IF
+pub struct If;
+
+impl<'func> Operation<'func, If> {
+    pub fn condition(&self) -> Expression<'func> {
+        self.expr(0)
+    }
+
+    pub fn true_body(&self) -> Expression<'func> {
+        self.expr(1)
+    }
+
+    pub fn false_body(&self) -> Expression<'func> {
+        self.expr(2)
+    }
+}
+
+// HLIL_WHILE
+pub struct While;
+
+impl<'func> Operation<'func, While> {
+    pub fn condition(&self) -> Expression<'func> {
+        self.expr(0)
+    }
+
+    pub fn body(&self) -> Expression<'func> {
+        self.expr(1)
+    }
+}
+
+// HLIL_DO_WHILE
+pub struct DoWhile;
+
+impl<'func> Operation<'func, DoWhile> {
+    pub fn body(&self) -> Expression<'func> {
+        self.expr(0)
+    }
+
+    pub fn condition(&self) -> Expression<'func> {
+        self.expr(1)
+    }
+}
+
+// HLIL_FOR
+pub struct For;
+
+impl<'func> Operation<'func, For> {
+    pub fn init(&self) -> Expression<'func> {
+        self.expr(0)
+    }
+
+    pub fn condition(&self) -> Expression<'func> {
+        self.expr(1)
+    }
+
+    pub fn update(&self) -> Expression<'func> {
+        self.expr(2)
+    }
+
+    pub fn body(&self) -> Expression<'func> {
+        self.expr(3)
+    }
+}
+
+// HLIL_SWITCH
+pub struct Switch;
+
+impl<'func> Operation<'func, Switch> {
+    pub fn condition(&self) -> Expression<'func> {
+        self.expr(0)
+    }
+
+    pub fn default(&self) -> Expression<'func> {
+        self.expr(1)
+    }
+
+    /// `Case` expressions, in source order
+    pub fn cases(&self) -> Vec<Expression<'func>> {
+        self.expr_list(2)
+    }
+}
+
+// HLIL_CASE
+pub struct Case;
+
+impl<'func> Operation<'func, Case> {
+    pub fn values(&self) -> Vec<Expression<'func>> {
+        self.expr_list(0)
+    }
+
+    pub fn body(&self) -> Expression<'func> {
+        self.expr(2)
+    }
+}
+
+// HLIL_JUMP
+pub struct Jump;
+
+impl<'func> Operation<'func, Jump> {
+    pub fn dest(&self) -> Expression<'func> {
+        self.expr(0)
+    }
+}
+
+// HLIL_RET
+pub struct Ret;
+
+impl<'func> Operation<'func, Ret> {
+    pub fn sources(&self) -> Vec<Expression<'func>> {
+        self.expr_list(0)
+    }
+}
+
+// HLIL_GOTO, HLIL_LABEL
+pub struct Label;
+
+impl<'func> Operation<'func, Label> {
+    pub fn label_id(&self) -> u64 {
+        self.op.operands[0]
+    }
+
+    pub fn label_name(&self) -> BnString {
+        use binaryninjacore_sys::BNGetGotoLabelName;
+
+        let owner = self.function.owner_function();
+        unsafe { BnString::from_raw(BNGetGotoLabelName(owner.handle, self.op.operands[0])) }
+    }
+}
+
+// HLIL_VAR_DECLARE
+pub struct VarDeclare;
+
+impl<'func> Operation<'func, VarDeclare> {
+    pub fn var(&self) -> Variable {
+        Variable::from_identifier(self.op.operands[0])
+    }
+}
+
+// HLIL_VAR_INIT
+pub struct VarInit;
+
+impl<'func> Operation<'func, VarInit> {
+    pub fn size(&self) -> usize {
+        self.op.size
+    }
+
+    pub fn dest(&self) -> Variable {
+        Variable::from_identifier(self.op.operands[0])
+    }
+
+    pub fn source_expr(&self) -> Expression<'func> {
+        self.expr(1)
+    }
+}
+
+// HLIL_ASSIGN
+pub struct Assign;
+
+impl<'func> Operation<'func, Assign> {
+    pub fn size(&self) -> usize {
+        self.op.size
+    }
+
+    pub fn dest(&self) -> Expression<'func> {
+        self.expr(0)
+    }
+
+    pub fn source_expr(&self) -> Expression<'func> {
+        self.expr(1)
+    }
+}
+
+// HLIL_ASSIGN_UNPACK
+pub struct AssignUnpack;
+
+impl<'func> Operation<'func, AssignUnpack> {
+    pub fn size(&self) -> usize {
+        self.op.size
+    }
+
+    pub fn dest(&self) -> Vec<Expression<'func>> {
+        self.expr_list(0)
+    }
+
+    pub fn source_expr(&self) -> Expression<'func> {
+        self.expr(2)
+    }
+}
+
+// HLIL_VAR
+pub struct Var;
+
+impl<'func> Operation<'func, Var> {
+    pub fn size(&self) -> usize {
+        self.op.size
+    }
+
+    pub fn var(&self) -> Variable {
+        Variable::from_identifier(self.op.operands[0])
+    }
+}
+
+// HLIL_STRUCT_FIELD, HLIL_DEREF_FIELD
+pub struct Field;
+
+impl<'func> Operation<'func, Field> {
+    pub fn size(&self) -> usize {
+        self.op.size
+    }
+
+    pub fn source_expr(&self) -> Expression<'func> {
+        self.expr(0)
+    }
+
+    pub fn offset(&self) -> u64 {
+        self.op.operands[1]
+    }
+
+    /// Index of the accessed member in its structure, if the core resolved one
+    pub fn member_index(&self) -> Option<usize> {
+        match self.op.operands[2] as usize {
+            crate::BN_INVALID_EXPR => None,
+            idx => Some(idx),
+        }
+    }
+}
+
+// HLIL_ARRAY_INDEX
+pub struct ArrayIndex;
+
+impl<'func> Operation<'func, ArrayIndex> {
+    pub fn size(&self) -> usize {
+        self.op.size
+    }
+
+    pub fn source_expr(&self) -> Expression<'func> {
+        self.expr(0)
+    }
+
+    pub fn index(&self) -> Expression<'func> {
+        self.expr(1)
+    }
+}
+
+// HLIL_SPLIT
+pub struct Split;
+
+impl<'func> Operation<'func, Split> {
+    pub fn size(&self) -> usize {
+        self.op.size
+    }
+
+    pub fn high(&self) -> Expression<'func> {
+        self.expr(0)
+    }
+
+    pub fn low(&self) -> Expression<'func> {
+        self.expr(1)
+    }
+}
+
+// HLIL_CONST, HLIL_CONST_PTR, HLIL_IMPORT
+pub struct Const;
+
+impl<'func> Operation<'func, Const> {
+    pub fn size(&self) -> usize {
+        self.op.size
+    }
+
+    pub fn value(&self) -> u64 {
+        self.op.operands[0]
+    }
+}
+
+// HLIL_EXTERN_PTR
+pub struct ExternPtr;
+
+impl<'func> Operation<'func, ExternPtr> {
+    pub fn size(&self) -> usize {
+        self.op.size
+    }
+
+    pub fn value(&self) -> u64 {
+        self.op.operands[0]
+    }
+
+    pub fn offset(&self) -> u64 {
+        self.op.operands[1]
+    }
+}
+
+// HLIL_FLOAT_CONST
+pub struct FloatConst;
+
+impl<'func> Operation<'func, FloatConst> {
+    pub fn size(&self) -> usize {
+        self.op.size
+    }
+
+    pub fn raw_value(&self) -> u64 {
+        self.op.operands[0]
+    }
+
+    /// Decodes single and double precision constants; other sizes only have `raw_value`
+    pub fn value(&self) -> Option<f64> {
+        match self.op.size {
+            4 => Some(f32::from_bits(self.op.operands[0] as u32) as f64),
+            8 => Some(f64::from_bits(self.op.operands[0])),
+            _ => None,
+        }
+    }
+}
+
+// HLIL_ADD, HLIL_SUB, HLIL_AND, HLIL_OR, HLIL_XOR, HLIL_LSL,
+// HLIL_LSR, HLIL_ASR, HLIL_ROL, HLIL_ROR, HLIL_MUL, HLIL_MULU_DP,
+// HLIL_MULS_DP, HLIL_DIVU, HLIL_DIVU_DP, HLIL_DIVS, HLIL_DIVS_DP,
+// HLIL_MODU, HLIL_MODU_DP, HLIL_MODS, HLIL_MODS_DP, HLIL_TEST_BIT,
+// HLIL_ADD_OVERFLOW, HLIL_FADD, HLIL_FSUB, HLIL_FMUL, HLIL_FDIV
+pub struct BinaryOp;
+
+impl<'func> Operation<'func, BinaryOp> {
+    pub fn size(&self) -> usize {
+        self.op.size
+    }
+
+    pub fn left(&self) -> Expression<'func> {
+        self.expr(0)
+    }
+
+    pub fn right(&self) -> Expression<'func> {
+        self.expr(1)
+    }
+}
+
+// HLIL_ADC, HLIL_SBB, HLIL_RLC, HLIL_RRC
+pub struct BinaryOpCarry;
+
+impl<'func> Operation<'func, BinaryOpCarry> {
+    pub fn size(&self) -> usize {
+        self.op.size
+    }
+
+    pub fn left(&self) -> Expression<'func> {
+        self.expr(0)
+    }
+
+    pub fn right(&self) -> Expression<'func> {
+        self.expr(1)
+    }
+
+    pub fn carry(&self) -> Expression<'func> {
+        self.expr(2)
+    }
+}
+
+// HLIL_DEREF, HLIL_ADDRESS_OF, HLIL_NEG, HLIL_NOT, HLIL_SX, HLIL_ZX,
+// HLIL_LOW_PART, HLIL_BOOL_TO_INT, HLIL_UNIMPL_MEM, HLIL_FSQRT, HLIL_FNEG,
+// HLIL_FABS, HLIL_FLOAT_TO_INT, HLIL_INT_TO_FLOAT, HLIL_FLOAT_CONV,
+// HLIL_ROUND_TO_INT, HLIL_FLOOR, HLIL_CEIL, HLIL_FTRUNC
+pub struct UnaryOp;
+
+impl<'func> Operation<'func, UnaryOp> {
+    pub fn size(&self) -> usize {
+        self.op.size
+    }
+
+    pub fn operand(&self) -> Expression<'func> {
+        self.expr(0)
+    }
+}
+
+// HLIL_CMP_X, HLIL_FCMP_X
+pub struct Condition;
+
+impl<'func> Operation<'func, Condition> {
+    pub fn size(&self) -> usize {
+        self.op.size
+    }
+
+    pub fn left(&self) -> Expression<'func> {
+        self.expr(0)
+    }
+
+    pub fn right(&self) -> Expression<'func> {
+        self.expr(1)
+    }
+}
+
+// HLIL_CALL, HLIL_TAILCALL
+pub struct Call;
+
+impl<'func> Operation<'func, Call> {
+    pub fn size(&self) -> usize {
+        self.op.size
+    }
+
+    pub fn target(&self) -> Expression<'func> {
+        self.expr(0)
+    }
+
+    pub fn params(&self) -> Vec<Expression<'func>> {
+        self.expr_list(1)
+    }
+}
+
+// HLIL_SYSCALL
+pub struct Syscall;
+
+impl<'func> Operation<'func, Syscall> {
+    pub fn params(&self) -> Vec<Expression<'func>> {
+        self.expr_list(0)
+    }
+}
+
+// HLIL_INTRINSIC
+pub struct Intrinsic;
+
+impl<'func> Operation<'func, Intrinsic> {
+    pub fn intrinsic_id(&self) -> u32 {
+        self.op.operands[0] as u32
+    }
+
+    pub fn params(&self) -> Vec<Expression<'func>> {
+        self.expr_list(1)
+    }
+}
+
+// HLIL_TRAP
+pub struct Trap;
+
+impl<'func> Operation<'func, Trap> {
+    pub fn vector(&self) -> u64 {
+        self.op.operands[0]
+    }
+}
+
+pub trait OperationArguments: 'static {}
+
+impl OperationArguments for NoArgs {}
+impl OperationArguments for Block {}
+impl OperationArguments for If {}
+impl OperationArguments for While {}
+impl OperationArguments for DoWhile {}
+impl OperationArguments for For {}
+impl OperationArguments for Switch {}
+impl OperationArguments for Case {}
+impl OperationArguments for Jump {}
+impl OperationArguments for Ret {}
+impl OperationArguments for Label {}
+impl OperationArguments for VarDeclare {}
+impl OperationArguments for VarInit {}
+impl OperationArguments for Assign {}
+impl OperationArguments for AssignUnpack {}
+impl OperationArguments for Var {}
+impl OperationArguments for Field {}
+impl OperationArguments for ArrayIndex {}
+impl OperationArguments for Split {}
+impl OperationArguments for Const {}
+impl OperationArguments for ExternPtr {}
+impl OperationArguments for FloatConst {}
+impl OperationArguments for BinaryOp {}
+impl OperationArguments for BinaryOpCarry {}
+impl OperationArguments for UnaryOp {}
+impl OperationArguments for Condition {}
+impl OperationArguments for Call {}
+impl OperationArguments for Syscall {}
+impl OperationArguments for Intrinsic {}
+impl OperationArguments for Trap {}
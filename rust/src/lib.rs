@@ -39,26 +39,40 @@ mod ffi;
 pub mod architecture;
 pub mod backgroundtask;
 pub mod basicblock;
+pub mod binaryreader;
 pub mod binaryview;
+pub mod binarywriter;
 pub mod callingconvention;
 pub mod command;
 pub mod custombinaryview;
+pub mod database;
 pub mod databuffer;
+pub mod datanotification;
 pub mod debuginfo;
+pub mod demangle;
 pub mod disassembly;
 pub mod fileaccessor;
 pub mod filemetadata;
 pub mod flowgraph;
 pub mod function;
+pub mod functionrecognizer;
 pub mod headless;
+pub mod hlil;
 pub mod llil;
+pub mod metadata;
+pub mod mlil;
 pub mod platform;
 pub mod rc;
+pub mod references;
+pub mod relocation;
 pub mod section;
 pub mod segment;
 pub mod settings;
 pub mod string;
 pub mod symbol;
+pub mod tags;
+pub mod transform;
+pub mod typelibrary;
 pub mod types;
 
 use std::collections::HashMap;
@@ -77,9 +91,9 @@ pub use binaryninjacore_sys::BNEndianness as Endianness;
 // const BN_MAX_STORED_DATA_LENGTH: u64 = 0x3fffffff;
 // const BN_NULL_ID: i64 = -1;
 // const BN_INVALID_REGISTER: usize = 0xffffffff;
-// const BN_AUTOCOERCE_EXTERN_PTR: u64 = 0xfffffffd;
-// const BN_NOCOERCE_EXTERN_PTR: u64 = 0xfffffffe;
-// const BN_INVALID_OPERAND: u64 = 0xffffffff;
+const BN_AUTOCOERCE_EXTERN_PTR: usize = 0xfffffffd;
+const BN_NOCOERCE_EXTERN_PTR: usize = 0xfffffffe;
+const BN_INVALID_OPERAND: usize = 0xffffffff;
 // const BN_MAX_STRING_LENGTH: u64 = 128;
 // const BN_MAX_VARIABLE_OFFSET: u64 = 0x7fffffffff;
 // const BN_MAX_VARIABLE_INDEX: u64 = 0xfffff;
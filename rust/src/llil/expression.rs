@@ -17,6 +17,7 @@ use binaryninjacore_sys::BNLowLevelILInstruction;
 
 use std::fmt;
 use std::marker::PhantomData;
+use std::ptr;
 
 use super::operation;
 use super::operation::Operation;
@@ -24,6 +25,7 @@ use super::*;
 
 use crate::architecture::Architecture;
 use crate::architecture::RegisterInfo;
+use crate::types::{PossibleValueSet, RegisterValue};
 
 // used as a marker for Expressions that can produce a value
 #[derive(Copy, Clone, Debug)]
@@ -78,6 +80,7 @@ where
 fn common_info<'func, A, M, F>(
     function: &'func Function<A, M, F>,
     op: BNLowLevelILInstruction,
+    expr_idx: usize,
 ) -> ExprInfo<'func, A, M, F>
 where
     A: 'func + Architecture,
@@ -87,62 +90,90 @@ where
     use binaryninjacore_sys::BNLowLevelILOperation::*;
 
     match op.operation {
-        LLIL_CONST => ExprInfo::Const(Operation::new(function, op)),
-        LLIL_CONST_PTR => ExprInfo::ConstPtr(Operation::new(function, op)),
-
-        LLIL_ADD => ExprInfo::Add(Operation::new(function, op)),
-        LLIL_ADC => ExprInfo::Adc(Operation::new(function, op)),
-        LLIL_SUB => ExprInfo::Sub(Operation::new(function, op)),
-        LLIL_SBB => ExprInfo::Sbb(Operation::new(function, op)),
-        LLIL_AND => ExprInfo::And(Operation::new(function, op)),
-        LLIL_OR => ExprInfo::Or(Operation::new(function, op)),
-        LLIL_XOR => ExprInfo::Xor(Operation::new(function, op)),
-        LLIL_LSL => ExprInfo::Lsl(Operation::new(function, op)),
-        LLIL_LSR => ExprInfo::Lsr(Operation::new(function, op)),
-        LLIL_ASR => ExprInfo::Asr(Operation::new(function, op)),
-        LLIL_ROL => ExprInfo::Rol(Operation::new(function, op)),
-        LLIL_RLC => ExprInfo::Rlc(Operation::new(function, op)),
-        LLIL_ROR => ExprInfo::Ror(Operation::new(function, op)),
-        LLIL_RRC => ExprInfo::Rrc(Operation::new(function, op)),
-        LLIL_MUL => ExprInfo::Mul(Operation::new(function, op)),
-
-        LLIL_MULU_DP => ExprInfo::MuluDp(Operation::new(function, op)),
-        LLIL_MULS_DP => ExprInfo::MulsDp(Operation::new(function, op)),
-
-        LLIL_DIVU => ExprInfo::Divu(Operation::new(function, op)),
-        LLIL_DIVS => ExprInfo::Divs(Operation::new(function, op)),
-
-        LLIL_DIVU_DP => ExprInfo::DivuDp(Operation::new(function, op)),
-        LLIL_DIVS_DP => ExprInfo::DivsDp(Operation::new(function, op)),
-
-        LLIL_MODU => ExprInfo::Modu(Operation::new(function, op)),
-        LLIL_MODS => ExprInfo::Mods(Operation::new(function, op)),
-
-        LLIL_MODU_DP => ExprInfo::ModuDp(Operation::new(function, op)),
-        LLIL_MODS_DP => ExprInfo::ModsDp(Operation::new(function, op)),
-
-        LLIL_NEG => ExprInfo::Neg(Operation::new(function, op)),
-        LLIL_NOT => ExprInfo::Not(Operation::new(function, op)),
-
-        LLIL_SX => ExprInfo::Sx(Operation::new(function, op)),
-        LLIL_ZX => ExprInfo::Zx(Operation::new(function, op)),
-        LLIL_LOW_PART => ExprInfo::LowPart(Operation::new(function, op)),
-
-        LLIL_CMP_E => ExprInfo::CmpE(Operation::new(function, op)),
-        LLIL_CMP_NE => ExprInfo::CmpNe(Operation::new(function, op)),
-        LLIL_CMP_SLT => ExprInfo::CmpSlt(Operation::new(function, op)),
-        LLIL_CMP_ULT => ExprInfo::CmpUlt(Operation::new(function, op)),
-        LLIL_CMP_SLE => ExprInfo::CmpSle(Operation::new(function, op)),
-        LLIL_CMP_ULE => ExprInfo::CmpUle(Operation::new(function, op)),
-        LLIL_CMP_SGE => ExprInfo::CmpSge(Operation::new(function, op)),
-        LLIL_CMP_UGE => ExprInfo::CmpUge(Operation::new(function, op)),
-        LLIL_CMP_SGT => ExprInfo::CmpSgt(Operation::new(function, op)),
-        LLIL_CMP_UGT => ExprInfo::CmpUgt(Operation::new(function, op)),
-
-        LLIL_BOOL_TO_INT => ExprInfo::BoolToInt(Operation::new(function, op)),
-
-        LLIL_UNIMPL => ExprInfo::Unimpl(Operation::new(function, op)),
-        LLIL_UNIMPL_MEM => ExprInfo::UnimplMem(Operation::new(function, op)),
+        LLIL_CONST => ExprInfo::Const(Operation::new(function, op, expr_idx)),
+        LLIL_CONST_PTR => ExprInfo::ConstPtr(Operation::new(function, op, expr_idx)),
+        LLIL_FLOAT_CONST => ExprInfo::FloatConst(Operation::new(function, op, expr_idx)),
+
+        LLIL_ADD => ExprInfo::Add(Operation::new(function, op, expr_idx)),
+        LLIL_ADC => ExprInfo::Adc(Operation::new(function, op, expr_idx)),
+        LLIL_SUB => ExprInfo::Sub(Operation::new(function, op, expr_idx)),
+        LLIL_SBB => ExprInfo::Sbb(Operation::new(function, op, expr_idx)),
+        LLIL_AND => ExprInfo::And(Operation::new(function, op, expr_idx)),
+        LLIL_OR => ExprInfo::Or(Operation::new(function, op, expr_idx)),
+        LLIL_XOR => ExprInfo::Xor(Operation::new(function, op, expr_idx)),
+        LLIL_LSL => ExprInfo::Lsl(Operation::new(function, op, expr_idx)),
+        LLIL_LSR => ExprInfo::Lsr(Operation::new(function, op, expr_idx)),
+        LLIL_ASR => ExprInfo::Asr(Operation::new(function, op, expr_idx)),
+        LLIL_ROL => ExprInfo::Rol(Operation::new(function, op, expr_idx)),
+        LLIL_RLC => ExprInfo::Rlc(Operation::new(function, op, expr_idx)),
+        LLIL_ROR => ExprInfo::Ror(Operation::new(function, op, expr_idx)),
+        LLIL_RRC => ExprInfo::Rrc(Operation::new(function, op, expr_idx)),
+        LLIL_MUL => ExprInfo::Mul(Operation::new(function, op, expr_idx)),
+
+        LLIL_MULU_DP => ExprInfo::MuluDp(Operation::new(function, op, expr_idx)),
+        LLIL_MULS_DP => ExprInfo::MulsDp(Operation::new(function, op, expr_idx)),
+
+        LLIL_DIVU => ExprInfo::Divu(Operation::new(function, op, expr_idx)),
+        LLIL_DIVS => ExprInfo::Divs(Operation::new(function, op, expr_idx)),
+
+        LLIL_DIVU_DP => ExprInfo::DivuDp(Operation::new(function, op, expr_idx)),
+        LLIL_DIVS_DP => ExprInfo::DivsDp(Operation::new(function, op, expr_idx)),
+
+        LLIL_MODU => ExprInfo::Modu(Operation::new(function, op, expr_idx)),
+        LLIL_MODS => ExprInfo::Mods(Operation::new(function, op, expr_idx)),
+
+        LLIL_MODU_DP => ExprInfo::ModuDp(Operation::new(function, op, expr_idx)),
+        LLIL_MODS_DP => ExprInfo::ModsDp(Operation::new(function, op, expr_idx)),
+
+        LLIL_NEG => ExprInfo::Neg(Operation::new(function, op, expr_idx)),
+        LLIL_NOT => ExprInfo::Not(Operation::new(function, op, expr_idx)),
+
+        LLIL_SX => ExprInfo::Sx(Operation::new(function, op, expr_idx)),
+        LLIL_ZX => ExprInfo::Zx(Operation::new(function, op, expr_idx)),
+        LLIL_LOW_PART => ExprInfo::LowPart(Operation::new(function, op, expr_idx)),
+
+        LLIL_CMP_E => ExprInfo::CmpE(Operation::new(function, op, expr_idx)),
+        LLIL_CMP_NE => ExprInfo::CmpNe(Operation::new(function, op, expr_idx)),
+        LLIL_CMP_SLT => ExprInfo::CmpSlt(Operation::new(function, op, expr_idx)),
+        LLIL_CMP_ULT => ExprInfo::CmpUlt(Operation::new(function, op, expr_idx)),
+        LLIL_CMP_SLE => ExprInfo::CmpSle(Operation::new(function, op, expr_idx)),
+        LLIL_CMP_ULE => ExprInfo::CmpUle(Operation::new(function, op, expr_idx)),
+        LLIL_CMP_SGE => ExprInfo::CmpSge(Operation::new(function, op, expr_idx)),
+        LLIL_CMP_UGE => ExprInfo::CmpUge(Operation::new(function, op, expr_idx)),
+        LLIL_CMP_SGT => ExprInfo::CmpSgt(Operation::new(function, op, expr_idx)),
+        LLIL_CMP_UGT => ExprInfo::CmpUgt(Operation::new(function, op, expr_idx)),
+
+        LLIL_BOOL_TO_INT => ExprInfo::BoolToInt(Operation::new(function, op, expr_idx)),
+
+        LLIL_FADD => ExprInfo::Fadd(Operation::new(function, op, expr_idx)),
+        LLIL_FSUB => ExprInfo::Fsub(Operation::new(function, op, expr_idx)),
+        LLIL_FMUL => ExprInfo::Fmul(Operation::new(function, op, expr_idx)),
+        LLIL_FDIV => ExprInfo::Fdiv(Operation::new(function, op, expr_idx)),
+
+        LLIL_FSQRT => ExprInfo::Fsqrt(Operation::new(function, op, expr_idx)),
+        LLIL_FNEG => ExprInfo::Fneg(Operation::new(function, op, expr_idx)),
+        LLIL_FABS => ExprInfo::Fabs(Operation::new(function, op, expr_idx)),
+
+        LLIL_FLOAT_TO_INT => ExprInfo::FloatToInt(Operation::new(function, op, expr_idx)),
+        LLIL_INT_TO_FLOAT => ExprInfo::IntToFloat(Operation::new(function, op, expr_idx)),
+        LLIL_FLOAT_CONV => ExprInfo::FloatConv(Operation::new(function, op, expr_idx)),
+
+        LLIL_ROUND_TO_INT => ExprInfo::RoundToInt(Operation::new(function, op, expr_idx)),
+        LLIL_FLOOR => ExprInfo::Floor(Operation::new(function, op, expr_idx)),
+        LLIL_CEIL => ExprInfo::Ceil(Operation::new(function, op, expr_idx)),
+        LLIL_FTRUNC => ExprInfo::Ftrunc(Operation::new(function, op, expr_idx)),
+
+        LLIL_FCMP_E => ExprInfo::FcmpE(Operation::new(function, op, expr_idx)),
+        LLIL_FCMP_NE => ExprInfo::FcmpNe(Operation::new(function, op, expr_idx)),
+        LLIL_FCMP_LT => ExprInfo::FcmpLt(Operation::new(function, op, expr_idx)),
+        LLIL_FCMP_LE => ExprInfo::FcmpLe(Operation::new(function, op, expr_idx)),
+        LLIL_FCMP_GE => ExprInfo::FcmpGe(Operation::new(function, op, expr_idx)),
+        LLIL_FCMP_GT => ExprInfo::FcmpGt(Operation::new(function, op, expr_idx)),
+        LLIL_FCMP_O => ExprInfo::FcmpO(Operation::new(function, op, expr_idx)),
+        LLIL_FCMP_UO => ExprInfo::FcmpUo(Operation::new(function, op, expr_idx)),
+
+        LLIL_UNIMPL => ExprInfo::Unimpl(Operation::new(function, op, expr_idx)),
+        LLIL_UNIMPL_MEM => ExprInfo::UnimplMem(Operation::new(function, op, expr_idx)),
 
         // TODO TEST_BIT ADD_OVERFLOW
         _ => {
@@ -154,7 +185,7 @@ where
                 );
             }
 
-            ExprInfo::Undef(Operation::new(function, op))
+            ExprInfo::Undef(Operation::new(function, op, expr_idx))
         }
     }
 }
@@ -180,7 +211,9 @@ where
 
     match *info {
         CmpE(ref op) | CmpNe(ref op) | CmpSlt(ref op) | CmpUlt(ref op) | CmpSle(ref op)
-        | CmpUle(ref op) | CmpSge(ref op) | CmpUge(ref op) | CmpSgt(ref op) | CmpUgt(ref op) => {
+        | CmpUle(ref op) | CmpSge(ref op) | CmpUge(ref op) | CmpSgt(ref op) | CmpUgt(ref op)
+        | FcmpE(ref op) | FcmpNe(ref op) | FcmpLt(ref op) | FcmpLe(ref op) | FcmpGe(ref op)
+        | FcmpGt(ref op) | FcmpO(ref op) | FcmpUo(ref op) => {
             visit!(f, &op.left());
             visit!(f, &op.right());
         }
@@ -193,7 +226,8 @@ where
 
         Add(ref op) | Sub(ref op) | And(ref op) | Or(ref op) | Xor(ref op) | Lsl(ref op)
         | Lsr(ref op) | Asr(ref op) | Rol(ref op) | Ror(ref op) | Mul(ref op) | MulsDp(ref op)
-        | MuluDp(ref op) | Divu(ref op) | Divs(ref op) | Modu(ref op) | Mods(ref op) => {
+        | MuluDp(ref op) | Divu(ref op) | Divs(ref op) | Modu(ref op) | Mods(ref op)
+        | Fadd(ref op) | Fsub(ref op) | Fmul(ref op) | Fdiv(ref op) => {
             visit!(f, &op.left());
             visit!(f, &op.right());
         }
@@ -205,7 +239,9 @@ where
         }
 
         Neg(ref op) | Not(ref op) | Sx(ref op) | Zx(ref op) | LowPart(ref op)
-        | BoolToInt(ref op) => {
+        | BoolToInt(ref op) | Fsqrt(ref op) | Fneg(ref op) | Fabs(ref op) | FloatToInt(ref op)
+        | IntToFloat(ref op) | FloatConv(ref op) | RoundToInt(ref op) | Floor(ref op)
+        | Ceil(ref op) | Ftrunc(ref op) => {
             visit!(f, &op.operand());
         }
 
@@ -232,14 +268,16 @@ where
         use binaryninjacore_sys::BNLowLevelILOperation::*;
 
         match op.operation {
-            LLIL_LOAD => ExprInfo::Load(Operation::new(self.function, op)),
-            LLIL_POP => ExprInfo::Pop(Operation::new(self.function, op)),
-            LLIL_REG => ExprInfo::Reg(Operation::new(self.function, op)),
-            LLIL_FLAG => ExprInfo::Flag(Operation::new(self.function, op)),
-            LLIL_FLAG_BIT => ExprInfo::FlagBit(Operation::new(self.function, op)),
-            LLIL_FLAG_COND => ExprInfo::FlagCond(Operation::new(self.function, op)), // TODO lifted only
-            LLIL_FLAG_GROUP => ExprInfo::FlagGroup(Operation::new(self.function, op)), // TODO lifted only
-            _ => common_info(self.function, op),
+            LLIL_LOAD => ExprInfo::Load(Operation::new(self.function, op, self.expr_idx)),
+            LLIL_POP => ExprInfo::Pop(Operation::new(self.function, op, self.expr_idx)),
+            LLIL_REG => ExprInfo::Reg(Operation::new(self.function, op, self.expr_idx)),
+            LLIL_FLAG => ExprInfo::Flag(Operation::new(self.function, op, self.expr_idx)),
+            LLIL_FLAG_BIT => ExprInfo::FlagBit(Operation::new(self.function, op, self.expr_idx)),
+            LLIL_FLAG_COND => ExprInfo::FlagCond(Operation::new(self.function, op, self.expr_idx)), // TODO lifted only
+            LLIL_FLAG_GROUP => {
+                ExprInfo::FlagGroup(Operation::new(self.function, op, self.expr_idx))
+            } // TODO lifted only
+            _ => common_info(self.function, op, self.expr_idx),
         }
     }
 
@@ -287,11 +325,15 @@ where
         use binaryninjacore_sys::BNLowLevelILOperation::*;
 
         match op.operation {
-            LLIL_LOAD_SSA => ExprInfo::Load(Operation::new(self.function, op)),
-            LLIL_REG_SSA | LLIL_REG_SSA_PARTIAL => ExprInfo::Reg(Operation::new(self.function, op)),
-            LLIL_FLAG_SSA => ExprInfo::Flag(Operation::new(self.function, op)),
-            LLIL_FLAG_BIT_SSA => ExprInfo::FlagBit(Operation::new(self.function, op)),
-            _ => common_info(self.function, op),
+            LLIL_LOAD_SSA => ExprInfo::Load(Operation::new(self.function, op, self.expr_idx)),
+            LLIL_REG_SSA | LLIL_REG_SSA_PARTIAL => {
+                ExprInfo::Reg(Operation::new(self.function, op, self.expr_idx))
+            }
+            LLIL_FLAG_SSA => ExprInfo::Flag(Operation::new(self.function, op, self.expr_idx)),
+            LLIL_FLAG_BIT_SSA => {
+                ExprInfo::FlagBit(Operation::new(self.function, op, self.expr_idx))
+            }
+            _ => common_info(self.function, op, self.expr_idx),
         }
     }
 
@@ -316,8 +358,7 @@ where
         };
 
         match info {
-            // TODO ssa
-            Load(ref _op) => {} //visit!(Self::visit_tree, &op.source_mem_expr(), f),
+            Load(ref op) => visit!(Self::visit_tree, &op.source_mem_expr(), f),
             _ => {
                 let mut fb = |e: &Self| e.visit_tree(f);
                 visit!(common_visit, &info, &mut fb);
@@ -333,7 +374,27 @@ where
     A: 'func + Architecture,
     F: FunctionForm,
 {
-    // TODO possible values
+    pub fn value(&self) -> RegisterValue {
+        use binaryninjacore_sys::BNGetLowLevelILExprValue;
+
+        let value = unsafe { BNGetLowLevelILExprValue(self.function.handle, self.expr_idx) };
+        RegisterValue::from_raw(value)
+    }
+
+    pub fn possible_values(&self) -> PossibleValueSet {
+        use binaryninjacore_sys::BNGetLowLevelILPossibleExprValues;
+
+        unsafe {
+            let values = BNGetLowLevelILPossibleExprValues(
+                self.function.handle,
+                self.expr_idx,
+                ptr::null_mut(),
+                0,
+            );
+
+            PossibleValueSet::from_owned_raw(values)
+        }
+    }
 }
 
 pub enum ExprInfo<'func, A, M, F>
@@ -347,6 +408,7 @@ where
     Reg(Operation<'func, A, M, F, operation::Reg>),
     Const(Operation<'func, A, M, F, operation::Const>),
     ConstPtr(Operation<'func, A, M, F, operation::Const>),
+    FloatConst(Operation<'func, A, M, F, operation::FloatConst>),
     Flag(Operation<'func, A, M, F, operation::Flag>),
     FlagBit(Operation<'func, A, M, F, operation::FlagBit>),
 
@@ -404,6 +466,31 @@ where
     //TestBit(Operation<'func, A, M, F, operation::TestBit>), // TODO
     BoolToInt(Operation<'func, A, M, F, operation::UnaryOp>),
 
+    Fadd(Operation<'func, A, M, F, operation::BinaryOp>),
+    Fsub(Operation<'func, A, M, F, operation::BinaryOp>),
+    Fmul(Operation<'func, A, M, F, operation::BinaryOp>),
+    Fdiv(Operation<'func, A, M, F, operation::BinaryOp>),
+
+    Fsqrt(Operation<'func, A, M, F, operation::UnaryOp>),
+    Fneg(Operation<'func, A, M, F, operation::UnaryOp>),
+    Fabs(Operation<'func, A, M, F, operation::UnaryOp>),
+    FloatToInt(Operation<'func, A, M, F, operation::UnaryOp>),
+    IntToFloat(Operation<'func, A, M, F, operation::UnaryOp>),
+    FloatConv(Operation<'func, A, M, F, operation::UnaryOp>),
+    RoundToInt(Operation<'func, A, M, F, operation::UnaryOp>),
+    Floor(Operation<'func, A, M, F, operation::UnaryOp>),
+    Ceil(Operation<'func, A, M, F, operation::UnaryOp>),
+    Ftrunc(Operation<'func, A, M, F, operation::UnaryOp>),
+
+    FcmpE(Operation<'func, A, M, F, operation::Condition>),
+    FcmpNe(Operation<'func, A, M, F, operation::Condition>),
+    FcmpLt(Operation<'func, A, M, F, operation::Condition>),
+    FcmpLe(Operation<'func, A, M, F, operation::Condition>),
+    FcmpGe(Operation<'func, A, M, F, operation::Condition>),
+    FcmpGt(Operation<'func, A, M, F, operation::Condition>),
+    FcmpO(Operation<'func, A, M, F, operation::Condition>),
+    FcmpUo(Operation<'func, A, M, F, operation::Condition>),
+
     // TODO ADD_OVERFLOW
     Unimpl(Operation<'func, A, M, F, operation::NoArgs>),
     UnimplMem(Operation<'func, A, M, F, operation::UnimplMem>),
@@ -428,9 +515,9 @@ where
             Undef(..) | Unimpl(..) => None,
 
             FlagCond(..) | FlagGroup(..) | CmpE(..) | CmpNe(..) | CmpSlt(..) | CmpUlt(..)
-            | CmpSle(..) | CmpUle(..) | CmpSge(..) | CmpUge(..) | CmpSgt(..) | CmpUgt(..) => {
-                Some(0)
-            }
+            | CmpSle(..) | CmpUle(..) | CmpSge(..) | CmpUge(..) | CmpSgt(..) | CmpUgt(..)
+            | FcmpE(..) | FcmpNe(..) | FcmpLt(..) | FcmpLe(..) | FcmpGe(..) | FcmpGt(..)
+            | FcmpO(..) | FcmpUo(..) => Some(0),
 
             _ => Some(self.raw_struct().size),
             //TestBit(Operation<'func, A, M, F, operation::TestBit>), // TODO
@@ -459,7 +546,8 @@ where
         match *self {
             CmpE(ref op) | CmpNe(ref op) | CmpSlt(ref op) | CmpUlt(ref op) | CmpSle(ref op)
             | CmpUle(ref op) | CmpSge(ref op) | CmpUge(ref op) | CmpSgt(ref op)
-            | CmpUgt(ref op) => Some(op),
+            | CmpUgt(ref op) | FcmpE(ref op) | FcmpNe(ref op) | FcmpLt(ref op) | FcmpLe(ref op)
+            | FcmpGe(ref op) | FcmpGt(ref op) | FcmpO(ref op) | FcmpUo(ref op) => Some(op),
             _ => None,
         }
     }
@@ -471,7 +559,7 @@ where
             Add(ref op) | Sub(ref op) | And(ref op) | Or(ref op) | Xor(ref op) | Lsl(ref op)
             | Lsr(ref op) | Asr(ref op) | Rol(ref op) | Ror(ref op) | Mul(ref op)
             | MulsDp(ref op) | MuluDp(ref op) | Divu(ref op) | Divs(ref op) | Modu(ref op)
-            | Mods(ref op) => Some(op),
+            | Mods(ref op) | Fadd(ref op) | Fsub(ref op) | Fmul(ref op) | Fdiv(ref op) => Some(op),
             _ => None,
         }
     }
@@ -503,7 +591,9 @@ where
 
         match *self {
             Neg(ref op) | Not(ref op) | Sx(ref op) | Zx(ref op) | LowPart(ref op)
-            | BoolToInt(ref op) => Some(op),
+            | BoolToInt(ref op) | Fsqrt(ref op) | Fneg(ref op) | Fabs(ref op)
+            | FloatToInt(ref op) | IntToFloat(ref op) | FloatConv(ref op) | RoundToInt(ref op)
+            | Floor(ref op) | Ceil(ref op) | Ftrunc(ref op) => Some(op),
             _ => None,
         }
     }
@@ -521,7 +611,8 @@ where
 
             CmpE(ref op) | CmpNe(ref op) | CmpSlt(ref op) | CmpUlt(ref op) | CmpSle(ref op)
             | CmpUle(ref op) | CmpSge(ref op) | CmpUge(ref op) | CmpSgt(ref op)
-            | CmpUgt(ref op) => &op.op,
+            | CmpUgt(ref op) | FcmpE(ref op) | FcmpNe(ref op) | FcmpLt(ref op) | FcmpLe(ref op)
+            | FcmpGe(ref op) | FcmpGt(ref op) | FcmpO(ref op) | FcmpUo(ref op) => &op.op,
 
             Load(ref op) => &op.op,
 
@@ -535,17 +626,21 @@ where
 
             Const(ref op) | ConstPtr(ref op) => &op.op,
 
+            FloatConst(ref op) => &op.op,
+
             Adc(ref op) | Sbb(ref op) | Rlc(ref op) | Rrc(ref op) => &op.op,
 
             Add(ref op) | Sub(ref op) | And(ref op) | Or(ref op) | Xor(ref op) | Lsl(ref op)
             | Lsr(ref op) | Asr(ref op) | Rol(ref op) | Ror(ref op) | Mul(ref op)
             | MulsDp(ref op) | MuluDp(ref op) | Divu(ref op) | Divs(ref op) | Modu(ref op)
-            | Mods(ref op) => &op.op,
+            | Mods(ref op) | Fadd(ref op) | Fsub(ref op) | Fmul(ref op) | Fdiv(ref op) => &op.op,
 
             DivuDp(ref op) | DivsDp(ref op) | ModuDp(ref op) | ModsDp(ref op) => &op.op,
 
             Neg(ref op) | Not(ref op) | Sx(ref op) | Zx(ref op) | LowPart(ref op)
-            | BoolToInt(ref op) => &op.op,
+            | BoolToInt(ref op) | Fsqrt(ref op) | Fneg(ref op) | Fabs(ref op)
+            | FloatToInt(ref op) | IntToFloat(ref op) | FloatConv(ref op) | RoundToInt(ref op)
+            | Floor(ref op) | Ceil(ref op) | Ftrunc(ref op) => &op.op,
 
             UnimplMem(ref op) => &op.op,
             //TestBit(Operation<'func, A, M, F, operation::TestBit>), // TODO
@@ -570,7 +665,9 @@ where
 
             CmpE(ref _op) | CmpNe(ref _op) | CmpSlt(ref _op) | CmpUlt(ref _op)
             | CmpSle(ref _op) | CmpUle(ref _op) | CmpSge(ref _op) | CmpUge(ref _op)
-            | CmpSgt(ref _op) | CmpUgt(ref _op) => None,
+            | CmpSgt(ref _op) | CmpUgt(ref _op) | FcmpE(ref _op) | FcmpNe(ref _op)
+            | FcmpLt(ref _op) | FcmpLe(ref _op) | FcmpGe(ref _op) | FcmpGt(ref _op)
+            | FcmpO(ref _op) | FcmpUo(ref _op) => None,
 
             Load(ref op) => op.flag_write(),
 
@@ -584,17 +681,23 @@ where
 
             Const(ref op) | ConstPtr(ref op) => op.flag_write(),
 
+            FloatConst(ref op) => op.flag_write(),
+
             Adc(ref op) | Sbb(ref op) | Rlc(ref op) | Rrc(ref op) => op.flag_write(),
 
             Add(ref op) | Sub(ref op) | And(ref op) | Or(ref op) | Xor(ref op) | Lsl(ref op)
             | Lsr(ref op) | Asr(ref op) | Rol(ref op) | Ror(ref op) | Mul(ref op)
             | MulsDp(ref op) | MuluDp(ref op) | Divu(ref op) | Divs(ref op) | Modu(ref op)
-            | Mods(ref op) => op.flag_write(),
+            | Mods(ref op) | Fadd(ref op) | Fsub(ref op) | Fmul(ref op) | Fdiv(ref op) => {
+                op.flag_write()
+            }
 
             DivuDp(ref op) | DivsDp(ref op) | ModuDp(ref op) | ModsDp(ref op) => op.flag_write(),
 
             Neg(ref op) | Not(ref op) | Sx(ref op) | Zx(ref op) | LowPart(ref op)
-            | BoolToInt(ref op) => op.flag_write(),
+            | BoolToInt(ref op) | Fsqrt(ref op) | Fneg(ref op) | Fabs(ref op)
+            | FloatToInt(ref op) | IntToFloat(ref op) | FloatConv(ref op) | RoundToInt(ref op)
+            | Floor(ref op) | Ceil(ref op) | Ftrunc(ref op) => op.flag_write(),
 
             UnimplMem(ref op) => op.flag_write(),
             //TestBit(Operation<'func, A, M, F, operation::TestBit>), // TODO
@@ -621,7 +724,8 @@ where
 
             CmpE(ref op) | CmpNe(ref op) | CmpSlt(ref op) | CmpUlt(ref op) | CmpSle(ref op)
             | CmpUle(ref op) | CmpSge(ref op) | CmpUge(ref op) | CmpSgt(ref op)
-            | CmpUgt(ref op) => {
+            | CmpUgt(ref op) | FcmpE(ref op) | FcmpNe(ref op) | FcmpLt(ref op) | FcmpLe(ref op)
+            | FcmpGe(ref op) | FcmpGt(ref op) | FcmpO(ref op) | FcmpUo(ref op) => {
                 let left = op.left();
                 let right = op.right();
 
@@ -666,6 +770,11 @@ where
 
             Const(ref op) | ConstPtr(ref op) => write!(f, "0x{:x}", op.value()),
 
+            FloatConst(ref op) => match op.value() {
+                Some(value) => write!(f, "{:?}", value),
+                None => write!(f, "float.{}(0x{:x})", op.size(), op.raw_value()),
+            },
+
             Adc(ref op) | Sbb(ref op) | Rlc(ref op) | Rrc(ref op) => {
                 let left = op.left();
                 let right = op.right();
@@ -685,7 +794,7 @@ where
             Add(ref op) | Sub(ref op) | And(ref op) | Or(ref op) | Xor(ref op) | Lsl(ref op)
             | Lsr(ref op) | Asr(ref op) | Rol(ref op) | Ror(ref op) | Mul(ref op)
             | MulsDp(ref op) | MuluDp(ref op) | Divu(ref op) | Divs(ref op) | Modu(ref op)
-            | Mods(ref op) => {
+            | Mods(ref op) | Fadd(ref op) | Fsub(ref op) | Fmul(ref op) | Fdiv(ref op) => {
                 let left = op.left();
                 let right = op.right();
 
@@ -716,7 +825,9 @@ where
             }
 
             Neg(ref op) | Not(ref op) | Sx(ref op) | Zx(ref op) | LowPart(ref op)
-            | BoolToInt(ref op) => write!(
+            | BoolToInt(ref op) | Fsqrt(ref op) | Fneg(ref op) | Fabs(ref op)
+            | FloatToInt(ref op) | IntToFloat(ref op) | FloatConv(ref op) | RoundToInt(ref op)
+            | Floor(ref op) | Ceil(ref op) | Ftrunc(ref op) => write!(
                 f,
                 "{:?}({}, {:?})",
                 op.op.operation,
@@ -18,9 +18,11 @@ use binaryninjacore_sys::BNNewLowLevelILFunctionReference;
 
 use std::borrow::Borrow;
 use std::marker::PhantomData;
+use std::slice;
 
 use crate::basicblock::BasicBlock;
 use crate::rc::*;
+use crate::types::RegisterValue;
 
 use super::*;
 
@@ -139,6 +141,203 @@ where
             BNGetLowLevelILInstructionCount(self.handle)
         }
     }
+
+    // list operands (phi sources, call outputs and parameters) live
+    // out of line and have to be copied out of the core's buffer
+    pub(crate) fn operand_list(&self, expr_idx: usize, operand: usize) -> Vec<u64> {
+        use binaryninjacore_sys::BNLowLevelILFreeOperandList;
+        use binaryninjacore_sys::BNLowLevelILGetOperandList;
+
+        unsafe {
+            let mut count = 0;
+            let raw = BNLowLevelILGetOperandList(self.handle, expr_idx, operand, &mut count);
+
+            if raw.is_null() {
+                return Vec::new();
+            }
+
+            let list = slice::from_raw_parts(raw, count).to_vec();
+            BNLowLevelILFreeOperandList(raw);
+
+            list
+        }
+    }
+
+    fn instruction_from_raw_idx(&self, instr_idx: usize) -> Option<Instruction<A, M, F>> {
+        if instr_idx >= self.instruction_count() {
+            None
+        } else {
+            Some(Instruction {
+                function: self,
+                instr_idx,
+            })
+        }
+    }
+
+    fn instructions_from_indices(
+        &self,
+        raw: *mut usize,
+        count: usize,
+    ) -> Vec<Instruction<A, M, F>> {
+        use binaryninjacore_sys::BNFreeILInstructionList;
+
+        if raw.is_null() {
+            return Vec::new();
+        }
+
+        unsafe {
+            let res = slice::from_raw_parts(raw, count)
+                .iter()
+                .map(|&instr_idx| Instruction {
+                    function: self,
+                    instr_idx,
+                })
+                .collect();
+
+            BNFreeILInstructionList(raw);
+
+            res
+        }
+    }
+}
+
+impl<A> Function<A, Finalized, NonSSA<RegularNonSSA>>
+where
+    A: Architecture,
+{
+    pub fn ssa_form(&self) -> Result<Ref<Function<A, Finalized, SSA>>, ()> {
+        use binaryninjacore_sys::BNGetLowLevelILSSAForm;
+
+        unsafe {
+            let ssa = BNGetLowLevelILSSAForm(self.handle);
+
+            if ssa.is_null() {
+                return Err(());
+            }
+
+            Ok(Ref::new(Function::from_raw(self.borrower.clone(), ssa)))
+        }
+    }
+}
+
+impl<A> Function<A, Finalized, SSA>
+where
+    A: Architecture,
+{
+    pub fn non_ssa_form(&self) -> Result<Ref<Function<A, Finalized, NonSSA<RegularNonSSA>>>, ()> {
+        use binaryninjacore_sys::BNGetLowLevelILNonSSAForm;
+
+        unsafe {
+            let non_ssa = BNGetLowLevelILNonSSAForm(self.handle);
+
+            if non_ssa.is_null() {
+                return Err(());
+            }
+
+            Ok(Ref::new(Function::from_raw(self.borrower.clone(), non_ssa)))
+        }
+    }
+
+    /// The instruction that defines `reg`, if it is defined in this function
+    pub fn ssa_register_definition(
+        &self,
+        reg: &SSARegister<A::Register>,
+    ) -> Option<Instruction<A, Finalized, SSA>> {
+        use binaryninjacore_sys::BNGetLowLevelILSSARegisterDefinition;
+
+        let instr_idx = unsafe {
+            BNGetLowLevelILSSARegisterDefinition(self.handle, reg.id(), reg.version() as usize)
+        };
+
+        self.instruction_from_raw_idx(instr_idx)
+    }
+
+    pub fn ssa_register_uses(
+        &self,
+        reg: &SSARegister<A::Register>,
+    ) -> Vec<Instruction<A, Finalized, SSA>> {
+        use binaryninjacore_sys::BNGetLowLevelILSSARegisterUses;
+
+        unsafe {
+            let mut count = 0;
+            let raw = BNGetLowLevelILSSARegisterUses(
+                self.handle,
+                reg.id(),
+                reg.version() as usize,
+                &mut count,
+            );
+
+            self.instructions_from_indices(raw, count)
+        }
+    }
+
+    pub fn ssa_flag_definition(
+        &self,
+        flag: &SSAFlag<A::Flag>,
+    ) -> Option<Instruction<A, Finalized, SSA>> {
+        use binaryninjacore_sys::BNGetLowLevelILSSAFlagDefinition;
+
+        let instr_idx = unsafe {
+            BNGetLowLevelILSSAFlagDefinition(self.handle, flag.flag.id(), flag.version as usize)
+        };
+
+        self.instruction_from_raw_idx(instr_idx)
+    }
+
+    pub fn ssa_flag_uses(&self, flag: &SSAFlag<A::Flag>) -> Vec<Instruction<A, Finalized, SSA>> {
+        use binaryninjacore_sys::BNGetLowLevelILSSAFlagUses;
+
+        unsafe {
+            let mut count = 0;
+            let raw = BNGetLowLevelILSSAFlagUses(
+                self.handle,
+                flag.flag.id(),
+                flag.version as usize,
+                &mut count,
+            );
+
+            self.instructions_from_indices(raw, count)
+        }
+    }
+
+    pub fn ssa_register_value(&self, reg: &SSARegister<A::Register>) -> RegisterValue {
+        use binaryninjacore_sys::BNGetLowLevelILSSARegisterValue;
+
+        let value = unsafe {
+            BNGetLowLevelILSSARegisterValue(self.handle, reg.id(), reg.version() as usize)
+        };
+
+        RegisterValue::from_raw(value)
+    }
+
+    pub fn ssa_flag_value(&self, flag: &SSAFlag<A::Flag>) -> RegisterValue {
+        use binaryninjacore_sys::BNGetLowLevelILSSAFlagValue;
+
+        let value = unsafe {
+            BNGetLowLevelILSSAFlagValue(self.handle, flag.flag.id(), flag.version as usize)
+        };
+
+        RegisterValue::from_raw(value)
+    }
+
+    pub fn ssa_memory_definition(&self, version: usize) -> Option<Instruction<A, Finalized, SSA>> {
+        use binaryninjacore_sys::BNGetLowLevelILSSAMemoryDefinition;
+
+        let instr_idx = unsafe { BNGetLowLevelILSSAMemoryDefinition(self.handle, version) };
+
+        self.instruction_from_raw_idx(instr_idx)
+    }
+
+    pub fn ssa_memory_uses(&self, version: usize) -> Vec<Instruction<A, Finalized, SSA>> {
+        use binaryninjacore_sys::BNGetLowLevelILSSAMemoryUses;
+
+        unsafe {
+            let mut count = 0;
+            let raw = BNGetLowLevelILSSAMemoryUses(self.handle, version, &mut count);
+
+            self.instructions_from_indices(raw, count)
+        }
+    }
 }
 
 // LLIL basic blocks are not available until the function object
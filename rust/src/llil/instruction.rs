@@ -17,12 +17,16 @@ use binaryninjacore_sys::BNGetLowLevelILIndexForInstruction;
 use binaryninjacore_sys::BNLowLevelILInstruction;
 
 use std::marker::PhantomData;
+use std::ptr;
 
 use super::operation;
 use super::operation::Operation;
 use super::*;
 
 use crate::architecture::Architecture;
+use crate::architecture::Flag as ArchFlag;
+use crate::architecture::Register as ArchReg;
+use crate::types::{PossibleValueSet, RegisterValue};
 
 pub struct Instruction<'func, A, M, F>
 where
@@ -34,9 +38,198 @@ where
     pub(crate) instr_idx: usize,
 }
 
+impl<'func, A, F> Instruction<'func, A, Finalized, F>
+where
+    A: 'func + Architecture,
+    F: FunctionForm,
+{
+    pub fn register_value_at(&self, reg: A::Register) -> RegisterValue {
+        use binaryninjacore_sys::BNGetLowLevelILRegisterValueAtInstruction;
+
+        let value = unsafe {
+            BNGetLowLevelILRegisterValueAtInstruction(
+                self.function.handle,
+                reg.id(),
+                self.instr_idx,
+            )
+        };
+
+        RegisterValue::from_raw(value)
+    }
+
+    pub fn register_value_after(&self, reg: A::Register) -> RegisterValue {
+        use binaryninjacore_sys::BNGetLowLevelILRegisterValueAfterInstruction;
+
+        let value = unsafe {
+            BNGetLowLevelILRegisterValueAfterInstruction(
+                self.function.handle,
+                reg.id(),
+                self.instr_idx,
+            )
+        };
+
+        RegisterValue::from_raw(value)
+    }
+
+    pub fn possible_register_values_at(&self, reg: A::Register) -> PossibleValueSet {
+        use binaryninjacore_sys::BNGetLowLevelILPossibleRegisterValuesAtInstruction;
+
+        unsafe {
+            let values = BNGetLowLevelILPossibleRegisterValuesAtInstruction(
+                self.function.handle,
+                reg.id(),
+                self.instr_idx,
+                ptr::null_mut(),
+                0,
+            );
+
+            PossibleValueSet::from_owned_raw(values)
+        }
+    }
+
+    pub fn possible_register_values_after(&self, reg: A::Register) -> PossibleValueSet {
+        use binaryninjacore_sys::BNGetLowLevelILPossibleRegisterValuesAfterInstruction;
+
+        unsafe {
+            let values = BNGetLowLevelILPossibleRegisterValuesAfterInstruction(
+                self.function.handle,
+                reg.id(),
+                self.instr_idx,
+                ptr::null_mut(),
+                0,
+            );
+
+            PossibleValueSet::from_owned_raw(values)
+        }
+    }
+
+    pub fn flag_value_at(&self, flag: A::Flag) -> RegisterValue {
+        use binaryninjacore_sys::BNGetLowLevelILFlagValueAtInstruction;
+
+        let value = unsafe {
+            BNGetLowLevelILFlagValueAtInstruction(self.function.handle, flag.id(), self.instr_idx)
+        };
+
+        RegisterValue::from_raw(value)
+    }
+
+    pub fn flag_value_after(&self, flag: A::Flag) -> RegisterValue {
+        use binaryninjacore_sys::BNGetLowLevelILFlagValueAfterInstruction;
+
+        let value = unsafe {
+            BNGetLowLevelILFlagValueAfterInstruction(
+                self.function.handle,
+                flag.id(),
+                self.instr_idx,
+            )
+        };
+
+        RegisterValue::from_raw(value)
+    }
+
+    pub fn possible_flag_values_at(&self, flag: A::Flag) -> PossibleValueSet {
+        use binaryninjacore_sys::BNGetLowLevelILPossibleFlagValuesAtInstruction;
+
+        unsafe {
+            let values = BNGetLowLevelILPossibleFlagValuesAtInstruction(
+                self.function.handle,
+                flag.id(),
+                self.instr_idx,
+                ptr::null_mut(),
+                0,
+            );
+
+            PossibleValueSet::from_owned_raw(values)
+        }
+    }
+
+    pub fn possible_flag_values_after(&self, flag: A::Flag) -> PossibleValueSet {
+        use binaryninjacore_sys::BNGetLowLevelILPossibleFlagValuesAfterInstruction;
+
+        unsafe {
+            let values = BNGetLowLevelILPossibleFlagValuesAfterInstruction(
+                self.function.handle,
+                flag.id(),
+                self.instr_idx,
+                ptr::null_mut(),
+                0,
+            );
+
+            PossibleValueSet::from_owned_raw(values)
+        }
+    }
+
+    /// Contents of `size` bytes at `offset` from the stack pointer on
+    /// entry to the function
+    pub fn stack_contents_at(&self, offset: i64, size: usize) -> RegisterValue {
+        use binaryninjacore_sys::BNGetLowLevelILStackContentsAtInstruction;
+
+        let value = unsafe {
+            BNGetLowLevelILStackContentsAtInstruction(
+                self.function.handle,
+                offset,
+                size,
+                self.instr_idx,
+            )
+        };
+
+        RegisterValue::from_raw(value)
+    }
+
+    pub fn stack_contents_after(&self, offset: i64, size: usize) -> RegisterValue {
+        use binaryninjacore_sys::BNGetLowLevelILStackContentsAfterInstruction;
+
+        let value = unsafe {
+            BNGetLowLevelILStackContentsAfterInstruction(
+                self.function.handle,
+                offset,
+                size,
+                self.instr_idx,
+            )
+        };
+
+        RegisterValue::from_raw(value)
+    }
+
+    pub fn possible_stack_contents_at(&self, offset: i64, size: usize) -> PossibleValueSet {
+        use binaryninjacore_sys::BNGetLowLevelILPossibleStackContentsAtInstruction;
+
+        unsafe {
+            let values = BNGetLowLevelILPossibleStackContentsAtInstruction(
+                self.function.handle,
+                offset,
+                size,
+                self.instr_idx,
+                ptr::null_mut(),
+                0,
+            );
+
+            PossibleValueSet::from_owned_raw(values)
+        }
+    }
+
+    pub fn possible_stack_contents_after(&self, offset: i64, size: usize) -> PossibleValueSet {
+        use binaryninjacore_sys::BNGetLowLevelILPossibleStackContentsAfterInstruction;
+
+        unsafe {
+            let values = BNGetLowLevelILPossibleStackContentsAfterInstruction(
+                self.function.handle,
+                offset,
+                size,
+                self.instr_idx,
+                ptr::null_mut(),
+                0,
+            );
+
+            PossibleValueSet::from_owned_raw(values)
+        }
+    }
+}
+
 fn common_info<'func, A, M, F>(
     function: &'func Function<A, M, F>,
     op: BNLowLevelILInstruction,
+    expr_idx: usize,
 ) -> Option<InstrInfo<'func, A, M, F>>
 where
     A: 'func + Architecture,
@@ -46,16 +239,16 @@ where
     use binaryninjacore_sys::BNLowLevelILOperation::*;
 
     match op.operation {
-        LLIL_NOP => InstrInfo::Nop(Operation::new(function, op)).into(),
-        LLIL_JUMP => InstrInfo::Jump(Operation::new(function, op)).into(),
-        LLIL_JUMP_TO => InstrInfo::JumpTo(Operation::new(function, op)).into(),
-        LLIL_RET => InstrInfo::Ret(Operation::new(function, op)).into(),
-        LLIL_NORET => InstrInfo::NoRet(Operation::new(function, op)).into(),
-        LLIL_IF => InstrInfo::If(Operation::new(function, op)).into(),
-        LLIL_GOTO => InstrInfo::Goto(Operation::new(function, op)).into(),
-        LLIL_BP => InstrInfo::Bp(Operation::new(function, op)).into(),
-        LLIL_TRAP => InstrInfo::Trap(Operation::new(function, op)).into(),
-        LLIL_UNDEF => InstrInfo::Undef(Operation::new(function, op)).into(),
+        LLIL_NOP => InstrInfo::Nop(Operation::new(function, op, expr_idx)).into(),
+        LLIL_JUMP => InstrInfo::Jump(Operation::new(function, op, expr_idx)).into(),
+        LLIL_JUMP_TO => InstrInfo::JumpTo(Operation::new(function, op, expr_idx)).into(),
+        LLIL_RET => InstrInfo::Ret(Operation::new(function, op, expr_idx)).into(),
+        LLIL_NORET => InstrInfo::NoRet(Operation::new(function, op, expr_idx)).into(),
+        LLIL_IF => InstrInfo::If(Operation::new(function, op, expr_idx)).into(),
+        LLIL_GOTO => InstrInfo::Goto(Operation::new(function, op, expr_idx)).into(),
+        LLIL_BP => InstrInfo::Bp(Operation::new(function, op, expr_idx)).into(),
+        LLIL_TRAP => InstrInfo::Trap(Operation::new(function, op, expr_idx)).into(),
+        LLIL_UNDEF => InstrInfo::Undef(Operation::new(function, op, expr_idx)).into(),
         _ => None,
     }
 }
@@ -105,17 +298,19 @@ where
         let op = unsafe { BNGetLowLevelILByIndex(self.function.handle, expr_idx) };
 
         match op.operation {
-            LLIL_SET_REG => InstrInfo::SetReg(Operation::new(self.function, op)),
-            LLIL_SET_REG_SPLIT => InstrInfo::SetRegSplit(Operation::new(self.function, op)),
-            LLIL_SET_FLAG => InstrInfo::SetFlag(Operation::new(self.function, op)),
-            LLIL_STORE => InstrInfo::Store(Operation::new(self.function, op)),
-            LLIL_PUSH => InstrInfo::Push(Operation::new(self.function, op)),
+            LLIL_SET_REG => InstrInfo::SetReg(Operation::new(self.function, op, expr_idx)),
+            LLIL_SET_REG_SPLIT => {
+                InstrInfo::SetRegSplit(Operation::new(self.function, op, expr_idx))
+            }
+            LLIL_SET_FLAG => InstrInfo::SetFlag(Operation::new(self.function, op, expr_idx)),
+            LLIL_STORE => InstrInfo::Store(Operation::new(self.function, op, expr_idx)),
+            LLIL_PUSH => InstrInfo::Push(Operation::new(self.function, op, expr_idx)),
             LLIL_CALL | LLIL_CALL_STACK_ADJUST => {
-                InstrInfo::Call(Operation::new(self.function, op))
+                InstrInfo::Call(Operation::new(self.function, op, expr_idx))
             }
-            LLIL_SYSCALL => InstrInfo::Syscall(Operation::new(self.function, op)),
+            LLIL_SYSCALL => InstrInfo::Syscall(Operation::new(self.function, op, expr_idx)),
             _ => {
-                common_info(self.function, op).unwrap_or_else(|| {
+                common_info(self.function, op, expr_idx).unwrap_or_else(|| {
                     // Hopefully this is a bare value. If it isn't (expression
                     // from wrong function form or similar) it won't really cause
                     // any problems as it'll come back as undefined when queried.
@@ -162,6 +357,87 @@ where
     }
 }
 
+impl<'func, A, M> Instruction<'func, A, M, SSA>
+where
+    A: 'func + Architecture,
+    M: FunctionMutability,
+{
+    pub fn info(&self) -> InstrInfo<'func, A, M, SSA> {
+        use binaryninjacore_sys::BNLowLevelILOperation::*;
+
+        let expr_idx =
+            unsafe { BNGetLowLevelILIndexForInstruction(self.function.handle, self.instr_idx) };
+        let op = unsafe { BNGetLowLevelILByIndex(self.function.handle, expr_idx) };
+
+        match op.operation {
+            LLIL_SET_REG_SSA | LLIL_SET_REG_SSA_PARTIAL => {
+                InstrInfo::SetReg(Operation::new(self.function, op, expr_idx))
+            }
+            LLIL_SET_REG_SPLIT_SSA => {
+                InstrInfo::SetRegSplit(Operation::new(self.function, op, expr_idx))
+            }
+            LLIL_SET_FLAG_SSA => InstrInfo::SetFlag(Operation::new(self.function, op, expr_idx)),
+            LLIL_STORE_SSA => InstrInfo::Store(Operation::new(self.function, op, expr_idx)),
+            LLIL_CALL_SSA | LLIL_TAILCALL_SSA => {
+                InstrInfo::Call(Operation::new(self.function, op, expr_idx))
+            }
+            LLIL_SYSCALL_SSA => InstrInfo::Syscall(Operation::new(self.function, op, expr_idx)),
+            LLIL_REG_PHI => InstrInfo::RegPhi(Operation::new(self.function, op, expr_idx)),
+            LLIL_FLAG_PHI => InstrInfo::FlagPhi(Operation::new(self.function, op, expr_idx)),
+            LLIL_MEM_PHI => InstrInfo::MemPhi(Operation::new(self.function, op, expr_idx)),
+            _ => common_info(self.function, op, expr_idx).unwrap_or_else(|| {
+                let expr = Expression {
+                    function: self.function,
+                    expr_idx,
+                    _ty: PhantomData,
+                };
+
+                let info = unsafe { expr.info_from_op(op) };
+
+                InstrInfo::Value(expr, info)
+            }),
+        }
+    }
+
+    pub fn visit_tree<F>(&self, f: &mut F) -> VisitorAction
+    where
+        F: FnMut(
+            &Expression<'func, A, M, SSA, ValueExpr>,
+            &ExprInfo<'func, A, M, SSA>,
+        ) -> VisitorAction,
+    {
+        use self::InstrInfo::*;
+        let info = self.info();
+
+        let fb = &mut |e: &Expression<'func, A, M, SSA, ValueExpr>| e.visit_tree(f);
+
+        match info {
+            SetReg(ref op) => visit!(fb, &op.source_expr()),
+            SetRegSplit(ref op) => visit!(fb, &op.source_expr()),
+            SetFlag(ref op) => visit!(fb, &op.source_expr()),
+            Store(ref op) => {
+                visit!(fb, &op.dest_mem_expr());
+                visit!(fb, &op.source_expr());
+            }
+            Call(ref op) => {
+                visit!(fb, &op.target());
+
+                for param in &op.params() {
+                    visit!(fb, param);
+                }
+            }
+            Syscall(ref op) => {
+                for param in &op.params() {
+                    visit!(fb, param);
+                }
+            }
+            _ => visit!(common_visit, &info, fb),
+        }
+
+        VisitorAction::Sibling
+    }
+}
+
 pub enum InstrInfo<'func, A, M, F>
 where
     A: 'func + Architecture,
@@ -191,6 +467,10 @@ where
     Trap(Operation<'func, A, M, F, operation::Trap>),
     Undef(Operation<'func, A, M, F, operation::NoArgs>),
 
+    RegPhi(Operation<'func, A, M, F, operation::RegPhi>),
+    FlagPhi(Operation<'func, A, M, F, operation::FlagPhi>),
+    MemPhi(Operation<'func, A, M, F, operation::MemPhi>),
+
     Value(
         Expression<'func, A, M, F, ValueExpr>,
         ExprInfo<'func, A, M, F>,
@@ -16,7 +16,9 @@ use std::marker::PhantomData;
 use std::mem;
 
 use crate::architecture::Architecture;
+use crate::architecture::Intrinsic as ArchIntrinsic;
 use crate::architecture::Register as ArchReg;
+use crate::architecture::RegisterStack as ArchRegStack;
 use crate::architecture::{Flag, FlagClass, FlagCondition, FlagGroup, FlagRole, FlagWrite};
 
 use super::*;
@@ -136,7 +138,7 @@ pub enum FlagWriteOp<R: ArchReg> {
     ),
 
     Pop(usize),
-    // TODO: floating point stuff, llil comparison ops that set flags, intrinsics
+    // TODO: floating point stuff, llil comparison ops that set flags
 }
 
 impl<R: ArchReg> FlagWriteOp<R> {
@@ -951,6 +953,39 @@ where
         }
     }
 
+    /// Emits a floating point constant from the raw bits of a `size` byte IEEE 754 value
+    pub fn float_const_raw(
+        &self,
+        size: usize,
+        bits: u64,
+    ) -> Expression<A, Mutable, NonSSA<LiftedNonSSA>, ValueExpr> {
+        use binaryninjacore_sys::BNLowLevelILAddExpr;
+        use binaryninjacore_sys::BNLowLevelILOperation::LLIL_FLOAT_CONST;
+
+        let expr_idx =
+            unsafe { BNLowLevelILAddExpr(self.handle, LLIL_FLOAT_CONST, size, 0, bits, 0, 0, 0) };
+
+        Expression {
+            function: self,
+            expr_idx: expr_idx,
+            _ty: PhantomData,
+        }
+    }
+
+    pub fn float_const_single(
+        &self,
+        val: f32,
+    ) -> Expression<A, Mutable, NonSSA<LiftedNonSSA>, ValueExpr> {
+        self.float_const_raw(4, val.to_bits() as u64)
+    }
+
+    pub fn float_const_double(
+        &self,
+        val: f64,
+    ) -> Expression<A, Mutable, NonSSA<LiftedNonSSA>, ValueExpr> {
+        self.float_const_raw(8, val.to_bits())
+    }
+
     pub fn const_ptr_sized(
         &self,
         size: usize,
@@ -1141,6 +1176,108 @@ where
         }
     }
 
+    /// Reads the stack entry `entry` positions below the top of `reg_stack`
+    pub fn reg_stack_top_relative<'a, E>(
+        &'a self,
+        size: usize,
+        reg_stack: A::RegisterStack,
+        entry: E,
+    ) -> ExpressionBuilder<'a, A, ValueExpr>
+    where
+        E: Liftable<'a, A, Result = ValueExpr>,
+    {
+        use binaryninjacore_sys::BNLowLevelILOperation::LLIL_REG_STACK_REL;
+
+        let entry = E::lift(self, entry);
+
+        ExpressionBuilder {
+            function: self,
+            op: LLIL_REG_STACK_REL,
+            size: size,
+            flags: 0,
+            op1: reg_stack.id() as u64,
+            op2: entry.expr_idx as u64,
+            op3: 0,
+            op4: 0,
+            _ty: PhantomData,
+        }
+    }
+
+    pub fn set_reg_stack_top_relative<'a, E, V>(
+        &'a self,
+        size: usize,
+        reg_stack: A::RegisterStack,
+        entry: E,
+        value: V,
+    ) -> ExpressionBuilder<'a, A, VoidExpr>
+    where
+        E: Liftable<'a, A, Result = ValueExpr>,
+        V: LiftableWithSize<'a, A>,
+    {
+        use binaryninjacore_sys::BNLowLevelILOperation::LLIL_SET_REG_STACK_REL;
+
+        let entry = E::lift(self, entry);
+        let value = V::lift_with_size(self, value, size);
+
+        ExpressionBuilder {
+            function: self,
+            op: LLIL_SET_REG_STACK_REL,
+            size: size,
+            flags: 0,
+            op1: reg_stack.id() as u64,
+            op2: entry.expr_idx as u64,
+            op3: value.expr_idx as u64,
+            op4: 0,
+            _ty: PhantomData,
+        }
+    }
+
+    pub fn reg_stack_push<'a, E>(
+        &'a self,
+        size: usize,
+        reg_stack: A::RegisterStack,
+        value: E,
+    ) -> ExpressionBuilder<'a, A, VoidExpr>
+    where
+        E: LiftableWithSize<'a, A>,
+    {
+        use binaryninjacore_sys::BNLowLevelILOperation::LLIL_REG_STACK_PUSH;
+
+        let value = E::lift_with_size(self, value, size);
+
+        ExpressionBuilder {
+            function: self,
+            op: LLIL_REG_STACK_PUSH,
+            size: size,
+            flags: 0,
+            op1: reg_stack.id() as u64,
+            op2: value.expr_idx as u64,
+            op3: 0,
+            op4: 0,
+            _ty: PhantomData,
+        }
+    }
+
+    pub fn reg_stack_pop(
+        &self,
+        size: usize,
+        reg_stack: A::RegisterStack,
+    ) -> ExpressionBuilder<A, ValueExpr> {
+        use binaryninjacore_sys::BNLowLevelILOperation::LLIL_REG_STACK_POP;
+
+        ExpressionBuilder {
+            function: self,
+            op: LLIL_REG_STACK_POP,
+            size: size,
+            flags: 0,
+            op1: reg_stack.id() as u64,
+            op2: 0,
+            op3: 0,
+            op4: 0,
+            _ty: PhantomData,
+        }
+    }
+
     pub fn flag(&self, flag: A::Flag) -> Expression<A, Mutable, NonSSA<LiftedNonSSA>, ValueExpr> {
         use binaryninjacore_sys::BNLowLevelILAddExpr;
         use binaryninjacore_sys::BNLowLevelILOperation::LLIL_FLAG;
@@ -1283,6 +1420,66 @@ where
         }
     }
 
+    /// Emits a call to an architecture intrinsic, writing its results to `outputs`
+    pub fn intrinsic<'a, O, R, P, E>(
+        &'a self,
+        outputs: O,
+        intrinsic: A::Intrinsic,
+        inputs: P,
+    ) -> ExpressionBuilder<'a, A, VoidExpr>
+    where
+        O: IntoIterator<Item = R>,
+        R: Into<Register<A::Register>>,
+        P: IntoIterator<Item = E>,
+        E: Liftable<'a, A, Result = ValueExpr>,
+    {
+        use binaryninjacore_sys::BNLowLevelILAddExpr;
+        use binaryninjacore_sys::BNLowLevelILAddOperandList;
+        use binaryninjacore_sys::BNLowLevelILOperation::{LLIL_CALL_PARAM, LLIL_INTRINSIC};
+
+        // TODO verify valid ids
+        let mut outputs: Vec<u64> = outputs
+            .into_iter()
+            .map(|reg| reg.into().id() as u64)
+            .collect();
+
+        let mut inputs: Vec<u64> = inputs
+            .into_iter()
+            .map(|input| E::lift(self, input).expr_idx as u64)
+            .collect();
+
+        let (output_list, params) = unsafe {
+            let output_list =
+                BNLowLevelILAddOperandList(self.handle, outputs.as_mut_ptr(), outputs.len());
+            let input_list =
+                BNLowLevelILAddOperandList(self.handle, inputs.as_mut_ptr(), inputs.len());
+            let params = BNLowLevelILAddExpr(
+                self.handle,
+                LLIL_CALL_PARAM,
+                0,
+                0,
+                inputs.len() as u64,
+                input_list as u64,
+                0,
+                0,
+            );
+
+            (output_list, params)
+        };
+
+        ExpressionBuilder {
+            function: self,
+            op: LLIL_INTRINSIC,
+            size: 0,
+            flags: 0,
+            op1: outputs.len() as u64,
+            op2: output_list as u64,
+            op3: intrinsic.id() as u64,
+            op4: params as u64,
+            _ty: PhantomData,
+        }
+    }
+
     sized_unary_op_lifter!(push, LLIL_PUSH, VoidExpr);
     sized_no_arg_lifter!(pop, LLIL_POP, ValueExpr);
 
@@ -1344,6 +1541,32 @@ where
     // TODO no flags
     size_changing_unary_op_lifter!(bool_to_int, LLIL_BOOL_TO_INT, ValueExpr);
 
+    binary_op_lifter!(fadd, LLIL_FADD);
+    binary_op_lifter!(fsub, LLIL_FSUB);
+    binary_op_lifter!(fmul, LLIL_FMUL);
+    binary_op_lifter!(fdiv, LLIL_FDIV);
+
+    sized_unary_op_lifter!(fsqrt, LLIL_FSQRT, ValueExpr);
+    sized_unary_op_lifter!(fneg, LLIL_FNEG, ValueExpr);
+    sized_unary_op_lifter!(fabs, LLIL_FABS, ValueExpr);
+    sized_unary_op_lifter!(round_to_int, LLIL_ROUND_TO_INT, ValueExpr);
+    sized_unary_op_lifter!(floor, LLIL_FLOOR, ValueExpr);
+    sized_unary_op_lifter!(ceil, LLIL_CEIL, ValueExpr);
+    sized_unary_op_lifter!(ftrunc, LLIL_FTRUNC, ValueExpr);
+
+    size_changing_unary_op_lifter!(float_to_int, LLIL_FLOAT_TO_INT, ValueExpr);
+    size_changing_unary_op_lifter!(int_to_float, LLIL_INT_TO_FLOAT, ValueExpr);
+    size_changing_unary_op_lifter!(float_conv, LLIL_FLOAT_CONV, ValueExpr);
+
+    binary_op_lifter!(fcmp_e, LLIL_FCMP_E);
+    binary_op_lifter!(fcmp_ne, LLIL_FCMP_NE);
+    binary_op_lifter!(fcmp_lt, LLIL_FCMP_LT);
+    binary_op_lifter!(fcmp_le, LLIL_FCMP_LE);
+    binary_op_lifter!(fcmp_ge, LLIL_FCMP_GE);
+    binary_op_lifter!(fcmp_gt, LLIL_FCMP_GT);
+    binary_op_lifter!(fcmp_o, LLIL_FCMP_O);
+    binary_op_lifter!(fcmp_uo, LLIL_FCMP_UO);
+
     pub fn current_address(&self) -> u64 {
         use binaryninjacore_sys::BNLowLevelILGetCurrentAddress;
         unsafe { BNLowLevelILGetCurrentAddress(self.handle) }
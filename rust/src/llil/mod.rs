@@ -21,6 +21,7 @@ use std::fmt;
 // can reg/set_reg be used with sizes that differ from what is in BNRegisterInfo?
 
 use crate::architecture::Architecture;
+use crate::architecture::Flag as ArchFlag;
 use crate::architecture::Register as ArchReg;
 use crate::function::Location;
 
@@ -85,6 +86,26 @@ impl<R: ArchReg> SSARegister<R> {
             SSARegister::Full(_, ver) | SSARegister::Partial(_, ver, _) => ver,
         }
     }
+
+    // SSA versions are tracked on the full width register
+    fn id(&self) -> u32 {
+        match *self {
+            SSARegister::Full(ref reg, _) => reg.id(),
+            SSARegister::Partial(ref full, _, _) => full.id(),
+        }
+    }
+}
+
+#[derive(Copy, Clone, Debug)]
+pub struct SSAFlag<F: ArchFlag> {
+    pub flag: F,
+    pub version: u32,
+}
+
+impl<F: ArchFlag> SSAFlag<F> {
+    pub fn new(flag: F, version: u32) -> Self {
+        Self { flag, version }
+    }
 }
 
 pub enum VisitorAction {
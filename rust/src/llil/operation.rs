@@ -28,6 +28,7 @@ where
 {
     pub(crate) function: &'func Function<A, M, F>,
     pub(crate) op: BNLowLevelILInstruction,
+    pub(crate) expr_idx: usize,
     _args: PhantomData<O>,
 }
 
@@ -38,10 +39,15 @@ where
     F: FunctionForm,
     O: OperationArguments,
 {
-    pub(crate) fn new(function: &'func Function<A, M, F>, op: BNLowLevelILInstruction) -> Self {
+    pub(crate) fn new(
+        function: &'func Function<A, M, F>,
+        op: BNLowLevelILInstruction,
+        expr_idx: usize,
+    ) -> Self {
         Self {
             function: function,
             op: op,
+            expr_idx,
             _args: PhantomData,
         }
     }
@@ -65,6 +71,85 @@ where
     }
 }
 
+impl<'func, A, M, O> Operation<'func, A, M, SSA, O>
+where
+    A: 'func + Architecture,
+    M: FunctionMutability,
+    O: OperationArguments,
+{
+    fn register(&self, raw_id: u32) -> Register<A::Register> {
+        if raw_id >= 0x8000_0000 {
+            Register::Temp(raw_id & 0x7fff_ffff)
+        } else {
+            self.function
+                .arch()
+                .register_from_id(raw_id)
+                .map(Register::ArchReg)
+                .unwrap_or_else(|| {
+                    error!(
+                        "got garbage register from {:?} @ 0x{:x}",
+                        self.op.operation, self.op.address
+                    );
+
+                    Register::Temp(0)
+                })
+        }
+    }
+
+    fn ssa_reg(&self, reg: u64, version: u64) -> SSARegister<A::Register> {
+        SSARegister::Full(self.register(reg as u32), version as u32)
+    }
+
+    // LLIL_SET_REG_SSA_PARTIAL and LLIL_REG_SSA_PARTIAL are
+    // [full reg, full reg version, partial reg, ...]
+    fn ssa_reg_partial(&self) -> SSARegister<A::Register> {
+        let version = self.op.operands[1] as u32;
+
+        match (
+            self.register(self.op.operands[0] as u32),
+            self.register(self.op.operands[2] as u32),
+        ) {
+            (Register::ArchReg(full), Register::ArchReg(partial)) => {
+                SSARegister::Partial(full, version, partial)
+            }
+            (full, _) => SSARegister::Full(full, version),
+        }
+    }
+
+    /// Yields `None` for temporary or unknown flag ids, which have no `A::Flag`
+    fn ssa_flag(&self, flag: u64, version: u64) -> Option<SSAFlag<A::Flag>> {
+        self.function
+            .arch()
+            .flag_from_id(flag as u32)
+            .map(|flag| SSAFlag::new(flag, version as u32))
+    }
+
+    fn ssa_reg_list(&self, expr_idx: usize, operand: usize) -> Vec<SSARegister<A::Register>> {
+        self.function
+            .operand_list(expr_idx, operand)
+            .chunks(2)
+            .map(|pair| self.ssa_reg(pair[0], pair[1]))
+            .collect()
+    }
+
+    fn sub_op(&self, operand: usize) -> (usize, BNLowLevelILInstruction) {
+        use binaryninjacore_sys::BNGetLowLevelILByIndex;
+
+        let expr_idx = self.op.operands[operand] as usize;
+        let op = unsafe { BNGetLowLevelILByIndex(self.function.handle, expr_idx) };
+
+        (expr_idx, op)
+    }
+
+    fn expr(&self, operand: usize) -> Expression<'func, A, M, SSA, ValueExpr> {
+        Expression {
+            function: self.function,
+            expr_idx: self.op.operands[operand] as usize,
+            _ty: PhantomData,
+        }
+    }
+}
+
 // LLIL_NOP, LLIL_NORET, LLIL_BP, LLIL_UNDEF, LLIL_UNIMPL
 pub struct NoArgs;
 
@@ -85,6 +170,47 @@ where
 // LLIL_SYSCALL, LLIL_SYSCALL_SSA
 pub struct Syscall;
 
+impl<'func, A, M> Operation<'func, A, M, SSA, Syscall>
+where
+    A: 'func + Architecture,
+    M: FunctionMutability,
+{
+    // same layout as LLIL_CALL_SSA, minus the target
+    pub fn output(&self) -> Vec<SSARegister<A::Register>> {
+        let (output_idx, _) = self.sub_op(0);
+        self.ssa_reg_list(output_idx, 1)
+    }
+
+    pub fn dest_memory_version(&self) -> usize {
+        let (_, output) = self.sub_op(0);
+        output.operands[0] as usize
+    }
+
+    pub fn stack_reg(&self) -> SSARegister<A::Register> {
+        let (_, stack) = self.sub_op(1);
+        self.ssa_reg(stack.operands[0], stack.operands[1])
+    }
+
+    pub fn source_memory_version(&self) -> usize {
+        let (_, stack) = self.sub_op(1);
+        stack.operands[2] as usize
+    }
+
+    pub fn params(&self) -> Vec<Expression<'func, A, M, SSA, ValueExpr>> {
+        let (params_idx, _) = self.sub_op(2);
+
+        self.function
+            .operand_list(params_idx, 0)
+            .into_iter()
+            .map(|expr_idx| Expression {
+                function: self.function,
+                expr_idx: expr_idx as usize,
+                _ty: PhantomData,
+            })
+            .collect()
+    }
+}
+
 // LLIL_SET_REG, LLIL_SET_REG_SSA, LLIL_SET_REG_PARTIAL_SSA
 pub struct SetReg;
 
@@ -128,6 +254,36 @@ where
     }
 }
 
+impl<'func, A, M> Operation<'func, A, M, SSA, SetReg>
+where
+    A: 'func + Architecture,
+    M: FunctionMutability,
+{
+    pub fn size(&self) -> usize {
+        self.op.size
+    }
+
+    pub fn dest_reg(&self) -> SSARegister<A::Register> {
+        use binaryninjacore_sys::BNLowLevelILOperation::LLIL_SET_REG_SSA_PARTIAL;
+
+        if self.op.operation == LLIL_SET_REG_SSA_PARTIAL {
+            self.ssa_reg_partial()
+        } else {
+            self.ssa_reg(self.op.operands[0], self.op.operands[1])
+        }
+    }
+
+    pub fn source_expr(&self) -> Expression<'func, A, M, SSA, ValueExpr> {
+        use binaryninjacore_sys::BNLowLevelILOperation::LLIL_SET_REG_SSA_PARTIAL;
+
+        if self.op.operation == LLIL_SET_REG_SSA_PARTIAL {
+            self.expr(3)
+        } else {
+            self.expr(2)
+        }
+    }
+}
+
 // LLIL_SET_REG_SPLIT, LLIL_SET_REG_SPLIT_SSA
 pub struct SetRegSplit;
 
@@ -192,6 +348,31 @@ where
     }
 }
 
+impl<'func, A, M> Operation<'func, A, M, SSA, SetRegSplit>
+where
+    A: 'func + Architecture,
+    M: FunctionMutability,
+{
+    pub fn size(&self) -> usize {
+        self.op.size
+    }
+
+    // the destinations are LLIL_REG_SPLIT_DEST_SSA subexpressions
+    pub fn dest_reg_high(&self) -> SSARegister<A::Register> {
+        let (_, dest) = self.sub_op(0);
+        self.ssa_reg(dest.operands[0], dest.operands[1])
+    }
+
+    pub fn dest_reg_low(&self) -> SSARegister<A::Register> {
+        let (_, dest) = self.sub_op(1);
+        self.ssa_reg(dest.operands[0], dest.operands[1])
+    }
+
+    pub fn source_expr(&self) -> Expression<'func, A, M, SSA, ValueExpr> {
+        self.expr(2)
+    }
+}
+
 // LLIL_SET_FLAG, LLIL_SET_FLAG_SSA
 pub struct SetFlag;
 
@@ -210,6 +391,20 @@ where
     }
 }
 
+impl<'func, A, M> Operation<'func, A, M, SSA, SetFlag>
+where
+    A: 'func + Architecture,
+    M: FunctionMutability,
+{
+    pub fn dest_flag(&self) -> Option<SSAFlag<A::Flag>> {
+        self.ssa_flag(self.op.operands[0], self.op.operands[1])
+    }
+
+    pub fn source_expr(&self) -> Expression<'func, A, M, SSA, ValueExpr> {
+        self.expr(2)
+    }
+}
+
 // LLIL_LOAD, LLIL_LOAD_SSA
 pub struct Load;
 
@@ -232,6 +427,24 @@ where
     }
 }
 
+impl<'func, A, M> Operation<'func, A, M, SSA, Load>
+where
+    A: 'func + Architecture,
+    M: FunctionMutability,
+{
+    pub fn size(&self) -> usize {
+        self.op.size
+    }
+
+    pub fn source_mem_expr(&self) -> Expression<'func, A, M, SSA, ValueExpr> {
+        self.expr(0)
+    }
+
+    pub fn source_memory_version(&self) -> usize {
+        self.op.operands[1] as usize
+    }
+}
+
 // LLIL_STORE, LLIL_STORE_SSA
 pub struct Store;
 
@@ -262,6 +475,32 @@ where
     }
 }
 
+impl<'func, A, M> Operation<'func, A, M, SSA, Store>
+where
+    A: 'func + Architecture,
+    M: FunctionMutability,
+{
+    pub fn size(&self) -> usize {
+        self.op.size
+    }
+
+    pub fn dest_mem_expr(&self) -> Expression<'func, A, M, SSA, ValueExpr> {
+        self.expr(0)
+    }
+
+    pub fn dest_memory_version(&self) -> usize {
+        self.op.operands[1] as usize
+    }
+
+    pub fn source_memory_version(&self) -> usize {
+        self.op.operands[2] as usize
+    }
+
+    pub fn source_expr(&self) -> Expression<'func, A, M, SSA, ValueExpr> {
+        self.expr(3)
+    }
+}
+
 // LLIL_REG, LLIL_REG_SSA, LLIL_REG_SSA_PARTIAL
 pub struct Reg;
 
@@ -297,12 +536,56 @@ where
     }
 }
 
+impl<'func, A, M> Operation<'func, A, M, SSA, Reg>
+where
+    A: 'func + Architecture,
+    M: FunctionMutability,
+{
+    pub fn size(&self) -> usize {
+        self.op.size
+    }
+
+    pub fn source_reg(&self) -> SSARegister<A::Register> {
+        use binaryninjacore_sys::BNLowLevelILOperation::LLIL_REG_SSA_PARTIAL;
+
+        if self.op.operation == LLIL_REG_SSA_PARTIAL {
+            self.ssa_reg_partial()
+        } else {
+            self.ssa_reg(self.op.operands[0], self.op.operands[1])
+        }
+    }
+}
+
 // LLIL_FLAG, LLIL_FLAG_SSA
 pub struct Flag;
 
+impl<'func, A, M> Operation<'func, A, M, SSA, Flag>
+where
+    A: 'func + Architecture,
+    M: FunctionMutability,
+{
+    pub fn source_flag(&self) -> Option<SSAFlag<A::Flag>> {
+        self.ssa_flag(self.op.operands[0], self.op.operands[1])
+    }
+}
+
 // LLIL_FLAG_BIT, LLIL_FLAG_BIT_SSA
 pub struct FlagBit;
 
+impl<'func, A, M> Operation<'func, A, M, SSA, FlagBit>
+where
+    A: 'func + Architecture,
+    M: FunctionMutability,
+{
+    pub fn source_flag(&self) -> Option<SSAFlag<A::Flag>> {
+        self.ssa_flag(self.op.operands[0], self.op.operands[1])
+    }
+
+    pub fn bit_index(&self) -> u64 {
+        self.op.operands[2]
+    }
+}
+
 // LLIL_JUMP
 pub struct Jump;
 
@@ -368,6 +651,53 @@ where
     }
 }
 
+impl<'func, A, M> Operation<'func, A, M, SSA, Call>
+where
+    A: 'func + Architecture,
+    M: FunctionMutability,
+{
+    // LLIL_CALL_SSA is [output, target, stack, params], with the output,
+    // stack and params held in LLIL_CALL_OUTPUT_SSA, LLIL_CALL_STACK_SSA
+    // and LLIL_CALL_PARAM subexpressions
+    pub fn output(&self) -> Vec<SSARegister<A::Register>> {
+        let (output_idx, _) = self.sub_op(0);
+        self.ssa_reg_list(output_idx, 1)
+    }
+
+    pub fn dest_memory_version(&self) -> usize {
+        let (_, output) = self.sub_op(0);
+        output.operands[0] as usize
+    }
+
+    pub fn target(&self) -> Expression<'func, A, M, SSA, ValueExpr> {
+        self.expr(1)
+    }
+
+    pub fn stack_reg(&self) -> SSARegister<A::Register> {
+        let (_, stack) = self.sub_op(2);
+        self.ssa_reg(stack.operands[0], stack.operands[1])
+    }
+
+    pub fn source_memory_version(&self) -> usize {
+        let (_, stack) = self.sub_op(2);
+        stack.operands[2] as usize
+    }
+
+    pub fn params(&self) -> Vec<Expression<'func, A, M, SSA, ValueExpr>> {
+        let (params_idx, _) = self.sub_op(3);
+
+        self.function
+            .operand_list(params_idx, 0)
+            .into_iter()
+            .map(|expr_idx| Expression {
+                function: self.function,
+                expr_idx: expr_idx as usize,
+                _ty: PhantomData,
+            })
+            .collect()
+    }
+}
+
 // LLIL_RET
 pub struct Ret;
 
@@ -469,12 +799,63 @@ where
 // LLIL_REG_PHI
 pub struct RegPhi;
 
+impl<'func, A, M> Operation<'func, A, M, SSA, RegPhi>
+where
+    A: 'func + Architecture,
+    M: FunctionMutability,
+{
+    pub fn dest_reg(&self) -> SSARegister<A::Register> {
+        self.ssa_reg(self.op.operands[0], self.op.operands[1])
+    }
+
+    pub fn source_regs(&self) -> Vec<SSARegister<A::Register>> {
+        self.ssa_reg_list(self.expr_idx, 2)
+    }
+}
+
 // LLIL_FLAG_PHI
 pub struct FlagPhi;
 
+impl<'func, A, M> Operation<'func, A, M, SSA, FlagPhi>
+where
+    A: 'func + Architecture,
+    M: FunctionMutability,
+{
+    pub fn dest_flag(&self) -> Option<SSAFlag<A::Flag>> {
+        self.ssa_flag(self.op.operands[0], self.op.operands[1])
+    }
+
+    /// Sources without an `A::Flag`, such as temporary flags, are left out
+    pub fn source_flags(&self) -> Vec<SSAFlag<A::Flag>> {
+        self.function
+            .operand_list(self.expr_idx, 2)
+            .chunks(2)
+            .filter_map(|pair| self.ssa_flag(pair[0], pair[1]))
+            .collect()
+    }
+}
+
 // LLIL_MEM_PHI
 pub struct MemPhi;
 
+impl<'func, A, M> Operation<'func, A, M, SSA, MemPhi>
+where
+    A: 'func + Architecture,
+    M: FunctionMutability,
+{
+    pub fn dest_memory_version(&self) -> usize {
+        self.op.operands[0] as usize
+    }
+
+    pub fn source_memory_versions(&self) -> Vec<usize> {
+        self.function
+            .operand_list(self.expr_idx, 1)
+            .into_iter()
+            .map(|version| version as usize)
+            .collect()
+    }
+}
+
 // LLIL_CONST, LLIL_CONST_PTR
 pub struct Const;
 
@@ -517,11 +898,40 @@ where
     }
 }
 
+// LLIL_FLOAT_CONST
+pub struct FloatConst;
+
+impl<'func, A, M, F> Operation<'func, A, M, F, FloatConst>
+where
+    A: 'func + Architecture,
+    M: FunctionMutability,
+    F: FunctionForm,
+{
+    pub fn size(&self) -> usize {
+        self.op.size
+    }
+
+    /// Raw bits of the constant, as a `size` byte IEEE 754 value
+    pub fn raw_value(&self) -> u64 {
+        self.op.operands[0]
+    }
+
+    /// Decodes single and double precision constants; other sizes only have `raw_value`
+    pub fn value(&self) -> Option<f64> {
+        match self.op.size {
+            4 => Some(f32::from_bits(self.op.operands[0] as u32) as f64),
+            8 => Some(f64::from_bits(self.op.operands[0])),
+            _ => None,
+        }
+    }
+}
+
 // LLIL_ADD, LLIL_SUB, LLIL_AND, LLIL_OR
 // LLIL_XOR, LLIL_LSL, LLIL_LSR, LLIL_ASR
 // LLIL_ROL, LLIL_ROR, LLIL_MUL, LLIL_MULU_DP,
 // LLIL_MULS_DP, LLIL_DIVU, LLIL_DIVS, LLIL_MODU,
-// LLIL_MODS
+// LLIL_MODS, LLIL_FADD, LLIL_FSUB, LLIL_FMUL,
+// LLIL_FDIV
 pub struct BinaryOp;
 
 impl<'func, A, M, F> Operation<'func, A, M, F, BinaryOp>
@@ -628,7 +1038,10 @@ where
 }
 
 // LLIL_PUSH, LLIL_NEG, LLIL_NOT, LLIL_SX,
-// LLIL_ZX, LLIL_LOW_PART, LLIL_BOOL_TO_INT, LLIL_UNIMPL_MEM
+// LLIL_ZX, LLIL_LOW_PART, LLIL_BOOL_TO_INT, LLIL_UNIMPL_MEM,
+// LLIL_FSQRT, LLIL_FNEG, LLIL_FABS, LLIL_FLOAT_TO_INT,
+// LLIL_INT_TO_FLOAT, LLIL_FLOAT_CONV, LLIL_ROUND_TO_INT,
+// LLIL_FLOOR, LLIL_CEIL, LLIL_FTRUNC
 pub struct UnaryOp;
 
 impl<'func, A, M, F> Operation<'func, A, M, F, UnaryOp>
@@ -650,7 +1063,7 @@ where
     }
 }
 
-// LLIL_CMP_X
+// LLIL_CMP_X, LLIL_FCMP_X
 pub struct Condition;
 
 impl<'func, A, M, F> Operation<'func, A, M, F, Condition>
@@ -730,6 +1143,7 @@ impl OperationArguments for RegPhi {}
 impl OperationArguments for FlagPhi {}
 impl OperationArguments for MemPhi {}
 impl OperationArguments for Const {}
+impl OperationArguments for FloatConst {}
 impl OperationArguments for BinaryOp {}
 impl OperationArguments for BinaryOpCarry {}
 impl OperationArguments for DoublePrecDivOp {}
@@ -0,0 +1,499 @@
+// Copyright 2021 Vector 35 Inc.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Typed values that can be stored alongside a `BinaryView` and saved in the database.
+//!
+//! Values are created from std types with `Into`, and read back with `TryFrom`:
+//!
+//! ```no_run
+//! # use std::convert::TryFrom;
+//! # use binaryninja::binaryview::BinaryViewExt;
+//! # fn example<BV: BinaryViewExt>(bv: &BV) {
+//! bv.store_metadata("my_plugin.count", 42u64, false);
+//!
+//! let count = bv
+//!     .query_metadata("my_plugin.count")
+//!     .and_then(|md| u64::try_from(&*md).ok());
+//! # }
+//! ```
+
+use binaryninjacore_sys::*;
+
+use std::collections::HashMap;
+use std::convert::TryFrom;
+use std::ffi::CStr;
+use std::fmt;
+use std::os::raw::c_char;
+use std::slice;
+
+use crate::rc::*;
+use crate::string::*;
+
+pub type MetadataType = BNMetadataType;
+
+pub struct Metadata {
+    pub(crate) handle: *mut BNMetadata,
+}
+
+impl Metadata {
+    pub(crate) unsafe fn from_raw(handle: *mut BNMetadata) -> Self {
+        debug_assert!(!handle.is_null());
+
+        Self { handle }
+    }
+
+    /// An empty value of the given type, e.g. an empty array or key-value store
+    pub fn new_of_type(t: MetadataType) -> Ref<Self> {
+        unsafe { Ref::new(Self::from_raw(BNCreateMetadataOfType(t))) }
+    }
+
+    pub fn new_raw(data: &[u8]) -> Ref<Self> {
+        unsafe {
+            Ref::new(Self::from_raw(BNCreateMetadataRawData(
+                data.as_ptr(),
+                data.len(),
+            )))
+        }
+    }
+
+    pub fn get_type(&self) -> MetadataType {
+        unsafe { BNMetadataGetType(self.handle) }
+    }
+
+    pub fn get_boolean(&self) -> Result<bool, ()> {
+        match self.get_type() {
+            MetadataType::BooleanDataType => Ok(unsafe { BNMetadataGetBoolean(self.handle) }),
+            _ => Err(()),
+        }
+    }
+
+    pub fn get_unsigned_integer(&self) -> Result<u64, ()> {
+        match self.get_type() {
+            MetadataType::UnsignedIntegerDataType => {
+                Ok(unsafe { BNMetadataGetUnsignedInteger(self.handle) })
+            }
+            _ => Err(()),
+        }
+    }
+
+    pub fn get_signed_integer(&self) -> Result<i64, ()> {
+        match self.get_type() {
+            MetadataType::SignedIntegerDataType => {
+                Ok(unsafe { BNMetadataGetSignedInteger(self.handle) })
+            }
+            _ => Err(()),
+        }
+    }
+
+    pub fn get_double(&self) -> Result<f64, ()> {
+        match self.get_type() {
+            MetadataType::DoubleDataType => Ok(unsafe { BNMetadataGetDouble(self.handle) }),
+            _ => Err(()),
+        }
+    }
+
+    pub fn get_string(&self) -> Result<BnString, ()> {
+        match self.get_type() {
+            MetadataType::StringDataType => unsafe {
+                let raw = BNMetadataGetString(self.handle);
+
+                if raw.is_null() {
+                    return Err(());
+                }
+
+                Ok(BnString::from_raw(raw))
+            },
+            _ => Err(()),
+        }
+    }
+
+    pub fn get_raw(&self) -> Result<Vec<u8>, ()> {
+        match self.get_type() {
+            MetadataType::RawDataType => unsafe {
+                let mut size = 0;
+                let raw = BNMetadataGetRaw(self.handle, &mut size);
+
+                if raw.is_null() {
+                    return Err(());
+                }
+
+                let res = slice::from_raw_parts(raw, size).to_vec();
+                BNFreeMetadataRaw(raw);
+
+                Ok(res)
+            },
+            _ => Err(()),
+        }
+    }
+
+    pub fn get_array(&self) -> Result<Array<Metadata>, ()> {
+        match self.get_type() {
+            MetadataType::ArrayDataType => unsafe {
+                let mut size = 0;
+                let raw = BNMetadataGetArray(self.handle, &mut size);
+
+                if raw.is_null() {
+                    return Err(());
+                }
+
+                Ok(Array::new(raw, size, ()))
+            },
+            _ => Err(()),
+        }
+    }
+
+    pub fn get_value_store(&self) -> Result<HashMap<String, Ref<Metadata>>, ()> {
+        match self.get_type() {
+            MetadataType::KeyValueDataType => unsafe {
+                let raw = BNMetadataGetValueStore(self.handle);
+
+                if raw.is_null() {
+                    return Err(());
+                }
+
+                let keys = slice::from_raw_parts((*raw).keys, (*raw).size);
+                let values = slice::from_raw_parts((*raw).values, (*raw).size);
+
+                let res = keys
+                    .iter()
+                    .zip(values.iter())
+                    .map(|(&key, &value)| {
+                        (
+                            CStr::from_ptr(key).to_string_lossy().into_owned(),
+                            Ref::new(Self::from_raw(BNNewMetadataReference(value))),
+                        )
+                    })
+                    .collect();
+
+                BNFreeMetadataValueStore(raw);
+
+                Ok(res)
+            },
+            _ => Err(()),
+        }
+    }
+
+    /// Number of entries in an array or key-value store
+    pub fn len(&self) -> usize {
+        unsafe { BNMetadataSize(self.handle) }
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    pub fn get_index(&self, index: usize) -> Result<Option<Ref<Metadata>>, ()> {
+        if self.get_type() != MetadataType::ArrayDataType {
+            return Err(());
+        }
+
+        unsafe {
+            let raw = BNMetadataGetForIndex(self.handle, index);
+
+            if raw.is_null() {
+                return Ok(None);
+            }
+
+            Ok(Some(Ref::new(Self::from_raw(raw))))
+        }
+    }
+
+    pub fn get<S: BnStrCompatible>(&self, key: S) -> Result<Option<Ref<Metadata>>, ()> {
+        if self.get_type() != MetadataType::KeyValueDataType {
+            return Err(());
+        }
+
+        let key = key.as_bytes_with_nul();
+
+        unsafe {
+            let raw = BNMetadataGetForKey(self.handle, key.as_ref().as_ptr() as *const c_char);
+
+            if raw.is_null() {
+                return Ok(None);
+            }
+
+            Ok(Some(Ref::new(Self::from_raw(raw))))
+        }
+    }
+
+    pub fn push(&self, value: &Metadata) -> Result<(), ()> {
+        if self.get_type() != MetadataType::ArrayDataType {
+            return Err(());
+        }
+
+        match unsafe { BNMetadataArrayAppend(self.handle, value.handle) } {
+            true => Ok(()),
+            false => Err(()),
+        }
+    }
+
+    pub fn insert<S: BnStrCompatible>(&self, key: S, value: &Metadata) -> Result<(), ()> {
+        if self.get_type() != MetadataType::KeyValueDataType {
+            return Err(());
+        }
+
+        let key = key.as_bytes_with_nul();
+
+        let res = unsafe {
+            BNMetadataSetValueForKey(
+                self.handle,
+                key.as_ref().as_ptr() as *const c_char,
+                value.handle,
+            )
+        };
+
+        match res {
+            true => Ok(()),
+            false => Err(()),
+        }
+    }
+
+    pub fn remove_index(&self, index: usize) -> Result<(), ()> {
+        if self.get_type() != MetadataType::ArrayDataType {
+            return Err(());
+        }
+
+        unsafe { BNMetadataRemoveIndex(self.handle, index) };
+        Ok(())
+    }
+
+    pub fn remove_key<S: BnStrCompatible>(&self, key: S) -> Result<(), ()> {
+        if self.get_type() != MetadataType::KeyValueDataType {
+            return Err(());
+        }
+
+        let key = key.as_bytes_with_nul();
+
+        unsafe { BNMetadataRemoveKey(self.handle, key.as_ref().as_ptr() as *const c_char) };
+        Ok(())
+    }
+}
+
+unsafe impl Send for Metadata {}
+unsafe impl Sync for Metadata {}
+
+impl PartialEq for Metadata {
+    fn eq(&self, other: &Self) -> bool {
+        unsafe { BNMetadataIsEqual(self.handle, other.handle) }
+    }
+}
+
+impl fmt::Debug for Metadata {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "<metadata {:?} (handle: {:?})>",
+            self.get_type(),
+            self.handle
+        )
+    }
+}
+
+impl ToOwned for Metadata {
+    type Owned = Ref<Self>;
+
+    fn to_owned(&self) -> Self::Owned {
+        unsafe { RefCountable::inc_ref(self) }
+    }
+}
+
+unsafe impl RefCountable for Metadata {
+    unsafe fn inc_ref(handle: &Self) -> Ref<Self> {
+        Ref::new(Self {
+            handle: BNNewMetadataReference(handle.handle),
+        })
+    }
+
+    unsafe fn dec_ref(handle: &Self) {
+        BNFreeMetadata(handle.handle);
+    }
+}
+
+unsafe impl CoreOwnedArrayProvider for Metadata {
+    type Raw = *mut BNMetadata;
+    type Context = ();
+
+    unsafe fn free(raw: *mut Self::Raw, _count: usize, _context: &Self::Context) {
+        BNFreeMetadataArray(raw);
+    }
+}
+
+unsafe impl<'a> CoreOwnedArrayWrapper<'a> for Metadata {
+    type Wrapped = Guard<'a, Metadata>;
+
+    unsafe fn wrap_raw(raw: &'a Self::Raw, context: &'a Self::Context) -> Self::Wrapped {
+        Guard::new(Metadata::from_raw(*raw), context)
+    }
+}
+
+impl From<bool> for Ref<Metadata> {
+    fn from(value: bool) -> Self {
+        unsafe { Ref::new(Metadata::from_raw(BNCreateMetadataBooleanData(value))) }
+    }
+}
+
+impl From<u64> for Ref<Metadata> {
+    fn from(value: u64) -> Self {
+        unsafe {
+            Ref::new(Metadata::from_raw(BNCreateMetadataUnsignedIntegerData(
+                value,
+            )))
+        }
+    }
+}
+
+impl From<i64> for Ref<Metadata> {
+    fn from(value: i64) -> Self {
+        unsafe { Ref::new(Metadata::from_raw(BNCreateMetadataSignedIntegerData(value))) }
+    }
+}
+
+impl From<f64> for Ref<Metadata> {
+    fn from(value: f64) -> Self {
+        unsafe { Ref::new(Metadata::from_raw(BNCreateMetadataDoubleData(value))) }
+    }
+}
+
+impl From<&str> for Ref<Metadata> {
+    fn from(value: &str) -> Self {
+        let value = value.as_bytes_with_nul();
+
+        unsafe {
+            Ref::new(Metadata::from_raw(BNCreateMetadataStringData(
+                value.as_ptr() as *const c_char,
+            )))
+        }
+    }
+}
+
+impl From<String> for Ref<Metadata> {
+    fn from(value: String) -> Self {
+        value.as_str().into()
+    }
+}
+
+impl From<&[u8]> for Ref<Metadata> {
+    fn from(value: &[u8]) -> Self {
+        Metadata::new_raw(value)
+    }
+}
+
+impl From<&Metadata> for Ref<Metadata> {
+    fn from(value: &Metadata) -> Self {
+        value.to_owned()
+    }
+}
+
+impl<T: Into<Ref<Metadata>>> From<Vec<T>> for Ref<Metadata> {
+    fn from(value: Vec<T>) -> Self {
+        let values: Vec<Ref<Metadata>> = value.into_iter().map(Into::into).collect();
+        let mut raw: Vec<*mut BNMetadata> = values.iter().map(|md| md.handle).collect();
+
+        unsafe {
+            Ref::new(Metadata::from_raw(BNCreateMetadataArray(
+                raw.as_mut_ptr(),
+                raw.len(),
+            )))
+        }
+    }
+}
+
+impl<S: BnStrCompatible, T: Into<Ref<Metadata>>> From<HashMap<S, T>> for Ref<Metadata> {
+    fn from(value: HashMap<S, T>) -> Self {
+        let (keys, values): (Vec<_>, Vec<Ref<Metadata>>) = value
+            .into_iter()
+            .map(|(k, v)| (k.as_bytes_with_nul(), v.into()))
+            .unzip();
+
+        let mut raw_keys: Vec<*const c_char> = keys
+            .iter()
+            .map(|k| k.as_ref().as_ptr() as *const c_char)
+            .collect();
+        let mut raw_values: Vec<*mut BNMetadata> = values.iter().map(|md| md.handle).collect();
+
+        unsafe {
+            Ref::new(Metadata::from_raw(BNCreateMetadataValueStore(
+                raw_keys.as_mut_ptr(),
+                raw_values.as_mut_ptr(),
+                raw_keys.len(),
+            )))
+        }
+    }
+}
+
+impl TryFrom<&Metadata> for bool {
+    type Error = ();
+
+    fn try_from(value: &Metadata) -> Result<Self, ()> {
+        value.get_boolean()
+    }
+}
+
+impl TryFrom<&Metadata> for u64 {
+    type Error = ();
+
+    fn try_from(value: &Metadata) -> Result<Self, ()> {
+        value.get_unsigned_integer()
+    }
+}
+
+impl TryFrom<&Metadata> for i64 {
+    type Error = ();
+
+    fn try_from(value: &Metadata) -> Result<Self, ()> {
+        value.get_signed_integer()
+    }
+}
+
+impl TryFrom<&Metadata> for f64 {
+    type Error = ();
+
+    fn try_from(value: &Metadata) -> Result<Self, ()> {
+        value.get_double()
+    }
+}
+
+impl TryFrom<&Metadata> for String {
+    type Error = ();
+
+    fn try_from(value: &Metadata) -> Result<Self, ()> {
+        value.get_string().map(|s| s.as_str().to_owned())
+    }
+}
+
+impl TryFrom<&Metadata> for Vec<u8> {
+    type Error = ();
+
+    fn try_from(value: &Metadata) -> Result<Self, ()> {
+        value.get_raw()
+    }
+}
+
+impl TryFrom<&Metadata> for Vec<Ref<Metadata>> {
+    type Error = ();
+
+    fn try_from(value: &Metadata) -> Result<Self, ()> {
+        value
+            .get_array()
+            .map(|array| array.iter().map(|md| md.to_owned()).collect())
+    }
+}
+
+impl TryFrom<&Metadata> for HashMap<String, Ref<Metadata>> {
+    type Error = ();
+
+    fn try_from(value: &Metadata) -> Result<Self, ()> {
+        value.get_value_store()
+    }
+}
@@ -0,0 +1,72 @@
+// Copyright 2021 Vector 35 Inc.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::ops::Range;
+
+use crate::basicblock::{BasicBlock, BlockContext};
+
+use super::*;
+
+pub struct BlockIter<'func, F: FunctionForm> {
+    function: &'func Function<F>,
+    range: Range<u64>,
+}
+
+impl<'func, F: FunctionForm> Iterator for BlockIter<'func, F> {
+    type Item = Instruction<'func, F>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.range.next().map(|i| Instruction {
+            function: self.function,
+            instr_idx: i as usize,
+        })
+    }
+}
+
+pub struct Block<'func, F: FunctionForm> {
+    pub(crate) function: &'func Function<F>,
+}
+
+impl<'func, F: FunctionForm> fmt::Debug for Block<'func, F> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "mlil_bb {:?}", self.function)
+    }
+}
+
+impl<'func, F: FunctionForm> BlockContext for Block<'func, F> {
+    type Iter = BlockIter<'func, F>;
+    type Instruction = Instruction<'func, F>;
+
+    fn start(&self, block: &BasicBlock<Self>) -> Instruction<'func, F> {
+        Instruction {
+            function: self.function,
+            instr_idx: block.raw_start() as usize,
+        }
+    }
+
+    fn iter(&self, block: &BasicBlock<Self>) -> BlockIter<'func, F> {
+        BlockIter {
+            function: self.function,
+            range: block.raw_start()..block.raw_end(),
+        }
+    }
+}
+
+impl<'func, F: FunctionForm> Clone for Block<'func, F> {
+    fn clone(&self) -> Self {
+        Block {
+            function: self.function,
+        }
+    }
+}
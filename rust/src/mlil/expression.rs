@@ -0,0 +1,445 @@
+// Copyright 2021 Vector 35 Inc.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use binaryninjacore_sys::BNGetMediumLevelILByIndex;
+use binaryninjacore_sys::BNMediumLevelILInstruction;
+
+use super::operation;
+use super::operation::Operation;
+use super::*;
+
+macro_rules! visit {
+    ($f:expr, $($e:expr),*) => {
+        if let VisitorAction::Halt = $f($($e,)*) {
+            return VisitorAction::Halt;
+        }
+    }
+}
+
+pub struct Expression<'func, F>
+where
+    F: FunctionForm,
+{
+    pub(crate) function: &'func Function<F>,
+    pub(crate) expr_idx: usize,
+}
+
+impl<'func, F> Expression<'func, F>
+where
+    F: FunctionForm,
+{
+    pub fn index(&self) -> usize {
+        self.expr_idx
+    }
+
+    pub(crate) unsafe fn info_from_op(&self, op: BNMediumLevelILInstruction) -> ExprInfo<'func, F> {
+        use binaryninjacore_sys::BNMediumLevelILOperation::*;
+
+        let function = self.function;
+        let expr_idx = self.expr_idx;
+
+        match op.operation {
+            MLIL_LOAD | MLIL_LOAD_SSA => ExprInfo::Load(Operation::new(function, op, expr_idx)),
+            MLIL_LOAD_STRUCT | MLIL_LOAD_STRUCT_SSA => {
+                ExprInfo::LoadStruct(Operation::new(function, op, expr_idx))
+            }
+
+            MLIL_VAR | MLIL_VAR_SSA => ExprInfo::Var(Operation::new(function, op, expr_idx)),
+            MLIL_VAR_FIELD | MLIL_VAR_SSA_FIELD => {
+                ExprInfo::VarField(Operation::new(function, op, expr_idx))
+            }
+            MLIL_VAR_SPLIT | MLIL_VAR_SPLIT_SSA => {
+                ExprInfo::VarSplit(Operation::new(function, op, expr_idx))
+            }
+            MLIL_VAR_ALIASED => ExprInfo::VarAliased(Operation::new(function, op, expr_idx)),
+            MLIL_VAR_ALIASED_FIELD => {
+                ExprInfo::VarAliasedField(Operation::new(function, op, expr_idx))
+            }
+            MLIL_ADDRESS_OF => ExprInfo::AddressOf(Operation::new(function, op, expr_idx)),
+            MLIL_ADDRESS_OF_FIELD => {
+                ExprInfo::AddressOfField(Operation::new(function, op, expr_idx))
+            }
+
+            MLIL_CONST => ExprInfo::Const(Operation::new(function, op, expr_idx)),
+            MLIL_CONST_PTR => ExprInfo::ConstPtr(Operation::new(function, op, expr_idx)),
+            MLIL_EXTERN_PTR => ExprInfo::ExternPtr(Operation::new(function, op, expr_idx)),
+            MLIL_FLOAT_CONST => ExprInfo::FloatConst(Operation::new(function, op, expr_idx)),
+            MLIL_IMPORT => ExprInfo::Import(Operation::new(function, op, expr_idx)),
+
+            MLIL_ADD => ExprInfo::Add(Operation::new(function, op, expr_idx)),
+            MLIL_ADC => ExprInfo::Adc(Operation::new(function, op, expr_idx)),
+            MLIL_SUB => ExprInfo::Sub(Operation::new(function, op, expr_idx)),
+            MLIL_SBB => ExprInfo::Sbb(Operation::new(function, op, expr_idx)),
+            MLIL_AND => ExprInfo::And(Operation::new(function, op, expr_idx)),
+            MLIL_OR => ExprInfo::Or(Operation::new(function, op, expr_idx)),
+            MLIL_XOR => ExprInfo::Xor(Operation::new(function, op, expr_idx)),
+            MLIL_LSL => ExprInfo::Lsl(Operation::new(function, op, expr_idx)),
+            MLIL_LSR => ExprInfo::Lsr(Operation::new(function, op, expr_idx)),
+            MLIL_ASR => ExprInfo::Asr(Operation::new(function, op, expr_idx)),
+            MLIL_ROL => ExprInfo::Rol(Operation::new(function, op, expr_idx)),
+            MLIL_RLC => ExprInfo::Rlc(Operation::new(function, op, expr_idx)),
+            MLIL_ROR => ExprInfo::Ror(Operation::new(function, op, expr_idx)),
+            MLIL_RRC => ExprInfo::Rrc(Operation::new(function, op, expr_idx)),
+            MLIL_MUL => ExprInfo::Mul(Operation::new(function, op, expr_idx)),
+            MLIL_MULU_DP => ExprInfo::MuluDp(Operation::new(function, op, expr_idx)),
+            MLIL_MULS_DP => ExprInfo::MulsDp(Operation::new(function, op, expr_idx)),
+            MLIL_DIVU => ExprInfo::Divu(Operation::new(function, op, expr_idx)),
+            MLIL_DIVU_DP => ExprInfo::DivuDp(Operation::new(function, op, expr_idx)),
+            MLIL_DIVS => ExprInfo::Divs(Operation::new(function, op, expr_idx)),
+            MLIL_DIVS_DP => ExprInfo::DivsDp(Operation::new(function, op, expr_idx)),
+            MLIL_MODU => ExprInfo::Modu(Operation::new(function, op, expr_idx)),
+            MLIL_MODU_DP => ExprInfo::ModuDp(Operation::new(function, op, expr_idx)),
+            MLIL_MODS => ExprInfo::Mods(Operation::new(function, op, expr_idx)),
+            MLIL_MODS_DP => ExprInfo::ModsDp(Operation::new(function, op, expr_idx)),
+
+            MLIL_NEG => ExprInfo::Neg(Operation::new(function, op, expr_idx)),
+            MLIL_NOT => ExprInfo::Not(Operation::new(function, op, expr_idx)),
+            MLIL_SX => ExprInfo::Sx(Operation::new(function, op, expr_idx)),
+            MLIL_ZX => ExprInfo::Zx(Operation::new(function, op, expr_idx)),
+            MLIL_LOW_PART => ExprInfo::LowPart(Operation::new(function, op, expr_idx)),
+
+            MLIL_CMP_E => ExprInfo::CmpE(Operation::new(function, op, expr_idx)),
+            MLIL_CMP_NE => ExprInfo::CmpNe(Operation::new(function, op, expr_idx)),
+            MLIL_CMP_SLT => ExprInfo::CmpSlt(Operation::new(function, op, expr_idx)),
+            MLIL_CMP_ULT => ExprInfo::CmpUlt(Operation::new(function, op, expr_idx)),
+            MLIL_CMP_SLE => ExprInfo::CmpSle(Operation::new(function, op, expr_idx)),
+            MLIL_CMP_ULE => ExprInfo::CmpUle(Operation::new(function, op, expr_idx)),
+            MLIL_CMP_SGE => ExprInfo::CmpSge(Operation::new(function, op, expr_idx)),
+            MLIL_CMP_UGE => ExprInfo::CmpUge(Operation::new(function, op, expr_idx)),
+            MLIL_CMP_SGT => ExprInfo::CmpSgt(Operation::new(function, op, expr_idx)),
+            MLIL_CMP_UGT => ExprInfo::CmpUgt(Operation::new(function, op, expr_idx)),
+            MLIL_TEST_BIT => ExprInfo::TestBit(Operation::new(function, op, expr_idx)),
+            MLIL_BOOL_TO_INT => ExprInfo::BoolToInt(Operation::new(function, op, expr_idx)),
+            MLIL_ADD_OVERFLOW => ExprInfo::AddOverflow(Operation::new(function, op, expr_idx)),
+
+            MLIL_FADD => ExprInfo::Fadd(Operation::new(function, op, expr_idx)),
+            MLIL_FSUB => ExprInfo::Fsub(Operation::new(function, op, expr_idx)),
+            MLIL_FMUL => ExprInfo::Fmul(Operation::new(function, op, expr_idx)),
+            MLIL_FDIV => ExprInfo::Fdiv(Operation::new(function, op, expr_idx)),
+            MLIL_FSQRT => ExprInfo::Fsqrt(Operation::new(function, op, expr_idx)),
+            MLIL_FNEG => ExprInfo::Fneg(Operation::new(function, op, expr_idx)),
+            MLIL_FABS => ExprInfo::Fabs(Operation::new(function, op, expr_idx)),
+            MLIL_FLOAT_TO_INT => ExprInfo::FloatToInt(Operation::new(function, op, expr_idx)),
+            MLIL_INT_TO_FLOAT => ExprInfo::IntToFloat(Operation::new(function, op, expr_idx)),
+            MLIL_FLOAT_CONV => ExprInfo::FloatConv(Operation::new(function, op, expr_idx)),
+            MLIL_ROUND_TO_INT => ExprInfo::RoundToInt(Operation::new(function, op, expr_idx)),
+            MLIL_FLOOR => ExprInfo::Floor(Operation::new(function, op, expr_idx)),
+            MLIL_CEIL => ExprInfo::Ceil(Operation::new(function, op, expr_idx)),
+            MLIL_FTRUNC => ExprInfo::Ftrunc(Operation::new(function, op, expr_idx)),
+
+            MLIL_FCMP_E => ExprInfo::FcmpE(Operation::new(function, op, expr_idx)),
+            MLIL_FCMP_NE => ExprInfo::FcmpNe(Operation::new(function, op, expr_idx)),
+            MLIL_FCMP_LT => ExprInfo::FcmpLt(Operation::new(function, op, expr_idx)),
+            MLIL_FCMP_LE => ExprInfo::FcmpLe(Operation::new(function, op, expr_idx)),
+            MLIL_FCMP_GE => ExprInfo::FcmpGe(Operation::new(function, op, expr_idx)),
+            MLIL_FCMP_GT => ExprInfo::FcmpGt(Operation::new(function, op, expr_idx)),
+            MLIL_FCMP_O => ExprInfo::FcmpO(Operation::new(function, op, expr_idx)),
+            MLIL_FCMP_UO => ExprInfo::FcmpUo(Operation::new(function, op, expr_idx)),
+
+            MLIL_UNIMPL => ExprInfo::Unimpl(Operation::new(function, op, expr_idx)),
+            MLIL_UNIMPL_MEM => ExprInfo::UnimplMem(Operation::new(function, op, expr_idx)),
+
+            _ => {
+                #[cfg(debug_assertions)]
+                {
+                    error!(
+                        "Got unexpected operation {:?} in value expr at 0x{:x}",
+                        op.operation, op.address
+                    );
+                }
+
+                ExprInfo::Undef(Operation::new(function, op, expr_idx))
+            }
+        }
+    }
+
+    pub fn info(&self) -> ExprInfo<'func, F> {
+        unsafe {
+            let op = BNGetMediumLevelILByIndex(self.function.handle, self.expr_idx);
+            self.info_from_op(op)
+        }
+    }
+
+    pub fn visit_tree<CB>(&self, f: &mut CB) -> VisitorAction
+    where
+        CB: FnMut(&Expression<'func, F>, &ExprInfo<'func, F>) -> VisitorAction,
+    {
+        use self::ExprInfo::*;
+
+        let info = self.info();
+
+        match f(self, &info) {
+            VisitorAction::Descend => {}
+            action => return action,
+        };
+
+        let fb = &mut |e: &Expression<'func, F>| e.visit_tree(f);
+
+        match info {
+            Load(ref op) => visit!(fb, &op.source_mem_expr()),
+            LoadStruct(ref op) => visit!(fb, &op.source_mem_expr()),
+            UnimplMem(ref op) => visit!(fb, &op.mem_expr()),
+            _ => {
+                if let Some(op) = info.as_cmp_op() {
+                    visit!(fb, &op.left());
+                    visit!(fb, &op.right());
+                } else if let Some(op) = info.as_binary_op() {
+                    visit!(fb, &op.left());
+                    visit!(fb, &op.right());
+                } else if let Some(op) = info.as_binary_op_carry() {
+                    visit!(fb, &op.left());
+                    visit!(fb, &op.right());
+                    visit!(fb, &op.carry());
+                } else if let Some(op) = info.as_unary_op() {
+                    visit!(fb, &op.operand());
+                }
+            }
+        }
+
+        VisitorAction::Sibling
+    }
+}
+
+impl<'func, F> fmt::Debug for Expression<'func, F>
+where
+    F: FunctionForm,
+{
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let info = self.info();
+        write!(
+            f,
+            "<expr {}: {:?}>",
+            self.expr_idx,
+            info.raw_struct().operation
+        )
+    }
+}
+
+pub enum ExprInfo<'func, F>
+where
+    F: FunctionForm,
+{
+    Load(Operation<'func, F, operation::Load>),
+    LoadStruct(Operation<'func, F, operation::LoadStruct>),
+
+    Var(Operation<'func, F, operation::Var>),
+    VarField(Operation<'func, F, operation::VarField>),
+    VarSplit(Operation<'func, F, operation::VarSplit>),
+    VarAliased(Operation<'func, F, operation::Var>),
+    VarAliasedField(Operation<'func, F, operation::VarField>),
+    AddressOf(Operation<'func, F, operation::AddressOf>),
+    AddressOfField(Operation<'func, F, operation::AddressOfField>),
+
+    Const(Operation<'func, F, operation::Const>),
+    ConstPtr(Operation<'func, F, operation::Const>),
+    ExternPtr(Operation<'func, F, operation::ExternPtr>),
+    FloatConst(Operation<'func, F, operation::FloatConst>),
+    Import(Operation<'func, F, operation::Const>),
+
+    Add(Operation<'func, F, operation::BinaryOp>),
+    Adc(Operation<'func, F, operation::BinaryOpCarry>),
+    Sub(Operation<'func, F, operation::BinaryOp>),
+    Sbb(Operation<'func, F, operation::BinaryOpCarry>),
+    And(Operation<'func, F, operation::BinaryOp>),
+    Or(Operation<'func, F, operation::BinaryOp>),
+    Xor(Operation<'func, F, operation::BinaryOp>),
+    Lsl(Operation<'func, F, operation::BinaryOp>),
+    Lsr(Operation<'func, F, operation::BinaryOp>),
+    Asr(Operation<'func, F, operation::BinaryOp>),
+    Rol(Operation<'func, F, operation::BinaryOp>),
+    Rlc(Operation<'func, F, operation::BinaryOpCarry>),
+    Ror(Operation<'func, F, operation::BinaryOp>),
+    Rrc(Operation<'func, F, operation::BinaryOpCarry>),
+    Mul(Operation<'func, F, operation::BinaryOp>),
+
+    MuluDp(Operation<'func, F, operation::BinaryOp>),
+    MulsDp(Operation<'func, F, operation::BinaryOp>),
+
+    Divu(Operation<'func, F, operation::BinaryOp>),
+    DivuDp(Operation<'func, F, operation::BinaryOp>),
+    Divs(Operation<'func, F, operation::BinaryOp>),
+    DivsDp(Operation<'func, F, operation::BinaryOp>),
+
+    Modu(Operation<'func, F, operation::BinaryOp>),
+    ModuDp(Operation<'func, F, operation::BinaryOp>),
+    Mods(Operation<'func, F, operation::BinaryOp>),
+    ModsDp(Operation<'func, F, operation::BinaryOp>),
+
+    Neg(Operation<'func, F, operation::UnaryOp>),
+    Not(Operation<'func, F, operation::UnaryOp>),
+    Sx(Operation<'func, F, operation::UnaryOp>),
+    Zx(Operation<'func, F, operation::UnaryOp>),
+    LowPart(Operation<'func, F, operation::UnaryOp>),
+
+    CmpE(Operation<'func, F, operation::Condition>),
+    CmpNe(Operation<'func, F, operation::Condition>),
+    CmpSlt(Operation<'func, F, operation::Condition>),
+    CmpUlt(Operation<'func, F, operation::Condition>),
+    CmpSle(Operation<'func, F, operation::Condition>),
+    CmpUle(Operation<'func, F, operation::Condition>),
+    CmpSge(Operation<'func, F, operation::Condition>),
+    CmpUge(Operation<'func, F, operation::Condition>),
+    CmpSgt(Operation<'func, F, operation::Condition>),
+    CmpUgt(Operation<'func, F, operation::Condition>),
+
+    TestBit(Operation<'func, F, operation::BinaryOp>),
+    BoolToInt(Operation<'func, F, operation::UnaryOp>),
+    AddOverflow(Operation<'func, F, operation::BinaryOp>),
+
+    Fadd(Operation<'func, F, operation::BinaryOp>),
+    Fsub(Operation<'func, F, operation::BinaryOp>),
+    Fmul(Operation<'func, F, operation::BinaryOp>),
+    Fdiv(Operation<'func, F, operation::BinaryOp>),
+    Fsqrt(Operation<'func, F, operation::UnaryOp>),
+    Fneg(Operation<'func, F, operation::UnaryOp>),
+    Fabs(Operation<'func, F, operation::UnaryOp>),
+    FloatToInt(Operation<'func, F, operation::UnaryOp>),
+    IntToFloat(Operation<'func, F, operation::UnaryOp>),
+    FloatConv(Operation<'func, F, operation::UnaryOp>),
+    RoundToInt(Operation<'func, F, operation::UnaryOp>),
+    Floor(Operation<'func, F, operation::UnaryOp>),
+    Ceil(Operation<'func, F, operation::UnaryOp>),
+    Ftrunc(Operation<'func, F, operation::UnaryOp>),
+
+    FcmpE(Operation<'func, F, operation::Condition>),
+    FcmpNe(Operation<'func, F, operation::Condition>),
+    FcmpLt(Operation<'func, F, operation::Condition>),
+    FcmpLe(Operation<'func, F, operation::Condition>),
+    FcmpGe(Operation<'func, F, operation::Condition>),
+    FcmpGt(Operation<'func, F, operation::Condition>),
+    FcmpO(Operation<'func, F, operation::Condition>),
+    FcmpUo(Operation<'func, F, operation::Condition>),
+
+    Unimpl(Operation<'func, F, operation::NoArgs>),
+    UnimplMem(Operation<'func, F, operation::UnimplMem>),
+
+    Undef(Operation<'func, F, operation::NoArgs>),
+}
+
+impl<'func, F> ExprInfo<'func, F>
+where
+    F: FunctionForm,
+{
+    /// Returns the size of the result of this expression
+    ///
+    /// If the expression is malformed or is `Unimpl` there
+    /// is no meaningful size associated with the result.
+    pub fn size(&self) -> Option<usize> {
+        use self::ExprInfo::*;
+
+        match *self {
+            Undef(..) | Unimpl(..) => None,
+            _ => Some(self.raw_struct().size),
+        }
+    }
+
+    pub fn address(&self) -> u64 {
+        self.raw_struct().address
+    }
+
+    /// Determines if the expressions represent the same operation
+    ///
+    /// It does not examine the operands for equality.
+    pub fn is_same_op_as(&self, other: &Self) -> bool {
+        self.raw_struct().operation == other.raw_struct().operation
+    }
+
+    pub fn as_cmp_op(&self) -> Option<&Operation<'func, F, operation::Condition>> {
+        use self::ExprInfo::*;
+
+        match *self {
+            CmpE(ref op) | CmpNe(ref op) | CmpSlt(ref op) | CmpUlt(ref op) | CmpSle(ref op)
+            | CmpUle(ref op) | CmpSge(ref op) | CmpUge(ref op) | CmpSgt(ref op)
+            | CmpUgt(ref op) | FcmpE(ref op) | FcmpNe(ref op) | FcmpLt(ref op) | FcmpLe(ref op)
+            | FcmpGe(ref op) | FcmpGt(ref op) | FcmpO(ref op) | FcmpUo(ref op) => Some(op),
+            _ => None,
+        }
+    }
+
+    pub fn as_binary_op(&self) -> Option<&Operation<'func, F, operation::BinaryOp>> {
+        use self::ExprInfo::*;
+
+        match *self {
+            Add(ref op) | Sub(ref op) | And(ref op) | Or(ref op) | Xor(ref op) | Lsl(ref op)
+            | Lsr(ref op) | Asr(ref op) | Rol(ref op) | Ror(ref op) | Mul(ref op)
+            | MuluDp(ref op) | MulsDp(ref op) | Divu(ref op) | DivuDp(ref op) | Divs(ref op)
+            | DivsDp(ref op) | Modu(ref op) | ModuDp(ref op) | Mods(ref op) | ModsDp(ref op)
+            | TestBit(ref op) | AddOverflow(ref op) | Fadd(ref op) | Fsub(ref op)
+            | Fmul(ref op) | Fdiv(ref op) => Some(op),
+            _ => None,
+        }
+    }
+
+    pub fn as_binary_op_carry(&self) -> Option<&Operation<'func, F, operation::BinaryOpCarry>> {
+        use self::ExprInfo::*;
+
+        match *self {
+            Adc(ref op) | Sbb(ref op) | Rlc(ref op) | Rrc(ref op) => Some(op),
+            _ => None,
+        }
+    }
+
+    pub fn as_unary_op(&self) -> Option<&Operation<'func, F, operation::UnaryOp>> {
+        use self::ExprInfo::*;
+
+        match *self {
+            Neg(ref op) | Not(ref op) | Sx(ref op) | Zx(ref op) | LowPart(ref op)
+            | BoolToInt(ref op) | Fsqrt(ref op) | Fneg(ref op) | Fabs(ref op)
+            | FloatToInt(ref op) | IntToFloat(ref op) | FloatConv(ref op) | RoundToInt(ref op)
+            | Floor(ref op) | Ceil(ref op) | Ftrunc(ref op) => Some(op),
+            _ => None,
+        }
+    }
+
+    pub(crate) fn raw_struct(&self) -> &BNMediumLevelILInstruction {
+        use self::ExprInfo::*;
+
+        if let Some(op) = self.as_cmp_op() {
+            return &op.op;
+        }
+
+        if let Some(op) = self.as_binary_op() {
+            return &op.op;
+        }
+
+        if let Some(op) = self.as_binary_op_carry() {
+            return &op.op;
+        }
+
+        if let Some(op) = self.as_unary_op() {
+            return &op.op;
+        }
+
+        match *self {
+            Load(ref op) => &op.op,
+            LoadStruct(ref op) => &op.op,
+
+            Var(ref op) | VarAliased(ref op) => &op.op,
+            VarField(ref op) | VarAliasedField(ref op) => &op.op,
+            VarSplit(ref op) => &op.op,
+            AddressOf(ref op) => &op.op,
+            AddressOfField(ref op) => &op.op,
+
+            Const(ref op) | ConstPtr(ref op) | Import(ref op) => &op.op,
+            ExternPtr(ref op) => &op.op,
+            FloatConst(ref op) => &op.op,
+
+            Unimpl(ref op) | Undef(ref op) => &op.op,
+            UnimplMem(ref op) => &op.op,
+
+            // handled by the as_*_op helpers above
+            _ => unreachable!(),
+        }
+    }
+}
@@ -0,0 +1,333 @@
+// Copyright 2021 Vector 35 Inc.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use binaryninjacore_sys::BNFreeMediumLevelILFunction;
+use binaryninjacore_sys::BNMediumLevelILFunction;
+use binaryninjacore_sys::BNNewMediumLevelILFunctionReference;
+
+use std::marker::PhantomData;
+use std::slice;
+
+use crate::basicblock::BasicBlock;
+use crate::function::Function as NativeFunction;
+use crate::rc::*;
+
+use super::*;
+
+#[derive(Copy, Clone, Debug)]
+pub struct SSA;
+#[derive(Copy, Clone, Debug)]
+pub struct NonSSA;
+
+pub trait FunctionForm: 'static {}
+impl FunctionForm for SSA {}
+impl FunctionForm for NonSSA {}
+
+pub struct Function<F: FunctionForm> {
+    pub(crate) handle: *mut BNMediumLevelILFunction,
+    _form: PhantomData<F>,
+}
+
+unsafe impl<F: FunctionForm> Send for Function<F> {}
+unsafe impl<F: FunctionForm> Sync for Function<F> {}
+
+impl<F: FunctionForm> Eq for Function<F> {}
+impl<F: FunctionForm> PartialEq for Function<F> {
+    fn eq(&self, rhs: &Self) -> bool {
+        self.handle == rhs.handle
+    }
+}
+
+use std::hash::{Hash, Hasher};
+impl<F: FunctionForm> Hash for Function<F> {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.handle.hash(state);
+    }
+}
+
+impl<F: FunctionForm> Function<F> {
+    pub(crate) unsafe fn from_raw(handle: *mut BNMediumLevelILFunction) -> Self {
+        debug_assert!(!handle.is_null());
+
+        Self {
+            handle,
+            _form: PhantomData,
+        }
+    }
+
+    pub fn owner_function(&self) -> Ref<NativeFunction> {
+        use binaryninjacore_sys::BNGetMediumLevelILOwnerFunction;
+
+        unsafe { NativeFunction::from_raw(BNGetMediumLevelILOwnerFunction(self.handle)) }
+    }
+
+    pub fn instruction_at<L: Into<Location>>(&self, loc: L) -> Option<Instruction<F>> {
+        use binaryninjacore_sys::BNGetMediumLevelILInstructionCount;
+        use binaryninjacore_sys::BNMediumLevelILGetInstructionStart;
+
+        let loc: Location = loc.into();
+        let arch = loc.arch.unwrap_or_else(|| self.owner_function().arch());
+
+        unsafe {
+            let instr_idx = BNMediumLevelILGetInstructionStart(self.handle, arch.0, loc.addr);
+
+            if instr_idx >= BNGetMediumLevelILInstructionCount(self.handle) {
+                None
+            } else {
+                Some(Instruction {
+                    function: self,
+                    instr_idx,
+                })
+            }
+        }
+    }
+
+    pub fn instruction_from_idx(&self, instr_idx: usize) -> Instruction<F> {
+        unsafe {
+            use binaryninjacore_sys::BNGetMediumLevelILInstructionCount;
+            if instr_idx >= BNGetMediumLevelILInstructionCount(self.handle) {
+                panic!("instruction index {} out of bounds", instr_idx);
+            }
+
+            Instruction {
+                function: self,
+                instr_idx,
+            }
+        }
+    }
+
+    pub fn instruction_count(&self) -> usize {
+        unsafe {
+            use binaryninjacore_sys::BNGetMediumLevelILInstructionCount;
+            BNGetMediumLevelILInstructionCount(self.handle)
+        }
+    }
+
+    pub fn expression_count(&self) -> usize {
+        unsafe {
+            use binaryninjacore_sys::BNGetMediumLevelILExprCount;
+            BNGetMediumLevelILExprCount(self.handle)
+        }
+    }
+
+    pub fn basic_blocks(&self) -> Array<BasicBlock<MediumLevelBlock<F>>> {
+        use binaryninjacore_sys::BNGetMediumLevelILBasicBlockList;
+
+        unsafe {
+            let mut count = 0;
+            let blocks = BNGetMediumLevelILBasicBlockList(self.handle, &mut count);
+            let context = MediumLevelBlock { function: self };
+
+            Array::new(blocks, count, context)
+        }
+    }
+
+    // MLIL stores list operands (call parameters, phi sources, jump
+    // tables...) out of line, so copy them out of the core's buffer
+    pub(crate) fn operand_list(&self, expr_idx: usize, operand: usize) -> Vec<u64> {
+        use binaryninjacore_sys::BNMediumLevelILFreeOperandList;
+        use binaryninjacore_sys::BNMediumLevelILGetOperandList;
+
+        unsafe {
+            let mut count = 0;
+            let raw = BNMediumLevelILGetOperandList(self.handle, expr_idx, operand, &mut count);
+
+            if raw.is_null() {
+                return Vec::new();
+            }
+
+            let list = slice::from_raw_parts(raw, count).to_vec();
+            BNMediumLevelILFreeOperandList(raw);
+
+            list
+        }
+    }
+
+    pub(crate) fn instructions_from_indices(
+        &self,
+        raw: *mut usize,
+        count: usize,
+    ) -> Vec<Instruction<F>> {
+        use binaryninjacore_sys::BNFreeILInstructionList;
+
+        if raw.is_null() {
+            return Vec::new();
+        }
+
+        unsafe {
+            let res = slice::from_raw_parts(raw, count)
+                .iter()
+                .map(|&instr_idx| Instruction {
+                    function: self,
+                    instr_idx,
+                })
+                .collect();
+
+            BNFreeILInstructionList(raw);
+
+            res
+        }
+    }
+}
+
+impl Function<NonSSA> {
+    pub fn ssa_form(&self) -> Result<Ref<Function<SSA>>, ()> {
+        use binaryninjacore_sys::BNGetMediumLevelILSSAForm;
+
+        unsafe {
+            let ssa = BNGetMediumLevelILSSAForm(self.handle);
+
+            if ssa.is_null() {
+                return Err(());
+            }
+
+            Ok(Ref::new(Function::from_raw(ssa)))
+        }
+    }
+
+    /// Instructions that write to `var`
+    pub fn variable_definitions(&self, var: &Variable) -> Vec<Instruction<NonSSA>> {
+        use binaryninjacore_sys::BNGetMediumLevelILVariableDefinitions;
+
+        unsafe {
+            let mut count = 0;
+            let raw_var = var.into_raw();
+            let raw = BNGetMediumLevelILVariableDefinitions(self.handle, &raw_var, &mut count);
+
+            self.instructions_from_indices(raw, count)
+        }
+    }
+
+    /// Instructions that read from `var`
+    pub fn variable_uses(&self, var: &Variable) -> Vec<Instruction<NonSSA>> {
+        use binaryninjacore_sys::BNGetMediumLevelILVariableUses;
+
+        unsafe {
+            let mut count = 0;
+            let raw_var = var.into_raw();
+            let raw = BNGetMediumLevelILVariableUses(self.handle, &raw_var, &mut count);
+
+            self.instructions_from_indices(raw, count)
+        }
+    }
+}
+
+impl Function<SSA> {
+    pub fn non_ssa_form(&self) -> Result<Ref<Function<NonSSA>>, ()> {
+        use binaryninjacore_sys::BNGetMediumLevelILNonSSAForm;
+
+        unsafe {
+            let non_ssa = BNGetMediumLevelILNonSSAForm(self.handle);
+
+            if non_ssa.is_null() {
+                return Err(());
+            }
+
+            Ok(Ref::new(Function::from_raw(non_ssa)))
+        }
+    }
+
+    /// The instruction that defines `var`, if it is defined in this function
+    pub fn ssa_variable_definition(&self, var: &SSAVariable) -> Option<Instruction<SSA>> {
+        use binaryninjacore_sys::BNGetMediumLevelILSSAVarDefinition;
+
+        unsafe {
+            let raw_var = var.variable.into_raw();
+            let instr_idx = BNGetMediumLevelILSSAVarDefinition(self.handle, &raw_var, var.version);
+
+            if instr_idx >= self.instruction_count() {
+                None
+            } else {
+                Some(Instruction {
+                    function: self,
+                    instr_idx,
+                })
+            }
+        }
+    }
+
+    pub fn ssa_variable_uses(&self, var: &SSAVariable) -> Vec<Instruction<SSA>> {
+        use binaryninjacore_sys::BNGetMediumLevelILSSAVarUses;
+
+        unsafe {
+            let mut count = 0;
+            let raw_var = var.variable.into_raw();
+            let raw = BNGetMediumLevelILSSAVarUses(self.handle, &raw_var, var.version, &mut count);
+
+            self.instructions_from_indices(raw, count)
+        }
+    }
+
+    pub fn ssa_memory_definition(&self, version: usize) -> Option<Instruction<SSA>> {
+        use binaryninjacore_sys::BNGetMediumLevelILSSAMemoryDefinition;
+
+        let instr_idx = unsafe { BNGetMediumLevelILSSAMemoryDefinition(self.handle, version) };
+
+        if instr_idx >= self.instruction_count() {
+            None
+        } else {
+            Some(Instruction {
+                function: self,
+                instr_idx,
+            })
+        }
+    }
+
+    pub fn ssa_memory_uses(&self, version: usize) -> Vec<Instruction<SSA>> {
+        use binaryninjacore_sys::BNGetMediumLevelILSSAMemoryUses;
+
+        unsafe {
+            let mut count = 0;
+            let raw = BNGetMediumLevelILSSAMemoryUses(self.handle, version, &mut count);
+
+            self.instructions_from_indices(raw, count)
+        }
+    }
+
+    pub fn is_ssa_variable_live(&self, var: &SSAVariable) -> bool {
+        use binaryninjacore_sys::BNIsMediumLevelILSSAVarLive;
+
+        unsafe {
+            let raw_var = var.variable.into_raw();
+            BNIsMediumLevelILSSAVarLive(self.handle, &raw_var, var.version)
+        }
+    }
+}
+
+impl<F: FunctionForm> ToOwned for Function<F> {
+    type Owned = Ref<Self>;
+
+    fn to_owned(&self) -> Self::Owned {
+        unsafe { RefCountable::inc_ref(self) }
+    }
+}
+
+unsafe impl<F: FunctionForm> RefCountable for Function<F> {
+    unsafe fn inc_ref(handle: &Self) -> Ref<Self> {
+        Ref::new(Self {
+            handle: BNNewMediumLevelILFunctionReference(handle.handle),
+            _form: PhantomData,
+        })
+    }
+
+    unsafe fn dec_ref(handle: &Self) {
+        BNFreeMediumLevelILFunction(handle.handle);
+    }
+}
+
+impl<F: FunctionForm> fmt::Debug for Function<F> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "<mlil func handle {:p}>", self.handle)
+    }
+}
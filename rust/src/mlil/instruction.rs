@@ -0,0 +1,304 @@
+// Copyright 2021 Vector 35 Inc.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use binaryninjacore_sys::BNGetMediumLevelILByIndex;
+use binaryninjacore_sys::BNGetMediumLevelILIndexForInstruction;
+
+use super::operation;
+use super::operation::Operation;
+use super::*;
+
+pub struct Instruction<'func, F>
+where
+    F: FunctionForm,
+{
+    pub(crate) function: &'func Function<F>,
+    pub(crate) instr_idx: usize,
+}
+
+macro_rules! visit {
+    ($f:expr, $($e:expr),*) => {
+        if let VisitorAction::Halt = $f($($e,)*) {
+            return VisitorAction::Halt;
+        }
+    }
+}
+
+fn common_visit<'func, F, CB>(info: &InstrInfo<'func, F>, f: &mut CB) -> VisitorAction
+where
+    F: FunctionForm,
+    CB: FnMut(&Expression<'func, F>) -> VisitorAction,
+{
+    use self::InstrInfo::*;
+
+    match *info {
+        Jump(ref op) | RetHint(ref op) => visit!(f, &op.target()),
+        JumpTo(ref op) => visit!(f, &op.target()),
+        Ret(ref op) => {
+            for src in op.sources() {
+                visit!(f, &src);
+            }
+        }
+        If(ref op) => visit!(f, &op.condition()),
+        Value(ref e, _) => visit!(f, e),
+        _ => {}
+    };
+
+    VisitorAction::Sibling
+}
+
+impl<'func, F> Instruction<'func, F>
+where
+    F: FunctionForm,
+{
+    pub fn index(&self) -> usize {
+        self.instr_idx
+    }
+
+    pub fn expression(&self) -> Expression<'func, F> {
+        let expr_idx =
+            unsafe { BNGetMediumLevelILIndexForInstruction(self.function.handle, self.instr_idx) };
+
+        Expression {
+            function: self.function,
+            expr_idx,
+        }
+    }
+
+    pub fn address(&self) -> u64 {
+        let expr_idx =
+            unsafe { BNGetMediumLevelILIndexForInstruction(self.function.handle, self.instr_idx) };
+
+        unsafe { BNGetMediumLevelILByIndex(self.function.handle, expr_idx).address }
+    }
+
+    pub fn info(&self) -> InstrInfo<'func, F> {
+        use binaryninjacore_sys::BNMediumLevelILOperation::*;
+
+        let function = self.function;
+        let expr_idx =
+            unsafe { BNGetMediumLevelILIndexForInstruction(function.handle, self.instr_idx) };
+        let op = unsafe { BNGetMediumLevelILByIndex(function.handle, expr_idx) };
+
+        match op.operation {
+            MLIL_NOP => InstrInfo::Nop(Operation::new(function, op, expr_idx)),
+            MLIL_SET_VAR | MLIL_SET_VAR_SSA => {
+                InstrInfo::SetVar(Operation::new(function, op, expr_idx))
+            }
+            MLIL_SET_VAR_FIELD | MLIL_SET_VAR_SSA_FIELD => {
+                InstrInfo::SetVarField(Operation::new(function, op, expr_idx))
+            }
+            MLIL_SET_VAR_SPLIT | MLIL_SET_VAR_SPLIT_SSA => {
+                InstrInfo::SetVarSplit(Operation::new(function, op, expr_idx))
+            }
+            MLIL_SET_VAR_ALIASED => {
+                InstrInfo::SetVarAliased(Operation::new(function, op, expr_idx))
+            }
+            MLIL_SET_VAR_ALIASED_FIELD => {
+                InstrInfo::SetVarAliasedField(Operation::new(function, op, expr_idx))
+            }
+            MLIL_STORE | MLIL_STORE_SSA => InstrInfo::Store(Operation::new(function, op, expr_idx)),
+            MLIL_STORE_STRUCT | MLIL_STORE_STRUCT_SSA => {
+                InstrInfo::StoreStruct(Operation::new(function, op, expr_idx))
+            }
+            MLIL_JUMP => InstrInfo::Jump(Operation::new(function, op, expr_idx)),
+            MLIL_JUMP_TO => InstrInfo::JumpTo(Operation::new(function, op, expr_idx)),
+            MLIL_RET_HINT => InstrInfo::RetHint(Operation::new(function, op, expr_idx)),
+            MLIL_CALL | MLIL_CALL_SSA => InstrInfo::Call(Operation::new(function, op, expr_idx)),
+            MLIL_TAILCALL | MLIL_TAILCALL_SSA => {
+                InstrInfo::Tailcall(Operation::new(function, op, expr_idx))
+            }
+            MLIL_CALL_UNTYPED | MLIL_CALL_UNTYPED_SSA => {
+                InstrInfo::CallUntyped(Operation::new(function, op, expr_idx))
+            }
+            MLIL_TAILCALL_UNTYPED | MLIL_TAILCALL_UNTYPED_SSA => {
+                InstrInfo::TailcallUntyped(Operation::new(function, op, expr_idx))
+            }
+            MLIL_SYSCALL | MLIL_SYSCALL_SSA => {
+                InstrInfo::Syscall(Operation::new(function, op, expr_idx))
+            }
+            MLIL_SYSCALL_UNTYPED | MLIL_SYSCALL_UNTYPED_SSA => {
+                InstrInfo::SyscallUntyped(Operation::new(function, op, expr_idx))
+            }
+            MLIL_RET => InstrInfo::Ret(Operation::new(function, op, expr_idx)),
+            MLIL_NORET => InstrInfo::NoRet(Operation::new(function, op, expr_idx)),
+            MLIL_IF => InstrInfo::If(Operation::new(function, op, expr_idx)),
+            MLIL_GOTO => InstrInfo::Goto(Operation::new(function, op, expr_idx)),
+            MLIL_INTRINSIC | MLIL_INTRINSIC_SSA => {
+                InstrInfo::Intrinsic(Operation::new(function, op, expr_idx))
+            }
+            MLIL_FREE_VAR_SLOT | MLIL_FREE_VAR_SLOT_SSA => {
+                InstrInfo::FreeVarSlot(Operation::new(function, op, expr_idx))
+            }
+            MLIL_VAR_PHI => InstrInfo::VarPhi(Operation::new(function, op, expr_idx)),
+            MLIL_MEM_PHI => InstrInfo::MemPhi(Operation::new(function, op, expr_idx)),
+            MLIL_BP => InstrInfo::Bp(Operation::new(function, op, expr_idx)),
+            MLIL_TRAP => InstrInfo::Trap(Operation::new(function, op, expr_idx)),
+            MLIL_UNDEF => InstrInfo::Undef(Operation::new(function, op, expr_idx)),
+            _ => {
+                // Hopefully this is a bare value. If it isn't it'll come
+                // back as undefined when queried.
+                let expr = Expression { function, expr_idx };
+
+                let info = unsafe { expr.info_from_op(op) };
+
+                InstrInfo::Value(expr, info)
+            }
+        }
+    }
+}
+
+impl<'func> Instruction<'func, NonSSA> {
+    pub fn visit_tree<CB>(&self, f: &mut CB) -> VisitorAction
+    where
+        CB: FnMut(&Expression<'func, NonSSA>, &ExprInfo<'func, NonSSA>) -> VisitorAction,
+    {
+        use self::InstrInfo::*;
+        let info = self.info();
+
+        let fb = &mut |e: &Expression<'func, NonSSA>| e.visit_tree(f);
+
+        match info {
+            SetVar(ref op) => visit!(fb, &op.source_expr()),
+            SetVarField(ref op) => visit!(fb, &op.source_expr()),
+            SetVarSplit(ref op) => visit!(fb, &op.source_expr()),
+            Store(ref op) => {
+                visit!(fb, &op.dest_mem_expr());
+                visit!(fb, &op.source_expr());
+            }
+            StoreStruct(ref op) => {
+                visit!(fb, &op.dest_mem_expr());
+                visit!(fb, &op.source_expr());
+            }
+            Call(ref op) | Tailcall(ref op) => {
+                visit!(fb, &op.target());
+                for param in op.params() {
+                    visit!(fb, &param);
+                }
+            }
+            CallUntyped(ref op) | TailcallUntyped(ref op) => {
+                visit!(fb, &op.target());
+                visit!(fb, &op.stack());
+            }
+            Syscall(ref op) => {
+                for param in op.params() {
+                    visit!(fb, &param);
+                }
+            }
+            SyscallUntyped(ref op) => visit!(fb, &op.stack()),
+            Intrinsic(ref op) => {
+                for param in op.params() {
+                    visit!(fb, &param);
+                }
+            }
+            _ => visit!(common_visit, &info, fb),
+        }
+
+        VisitorAction::Sibling
+    }
+}
+
+impl<'func> Instruction<'func, SSA> {
+    pub fn visit_tree<CB>(&self, f: &mut CB) -> VisitorAction
+    where
+        CB: FnMut(&Expression<'func, SSA>, &ExprInfo<'func, SSA>) -> VisitorAction,
+    {
+        use self::InstrInfo::*;
+        let info = self.info();
+
+        let fb = &mut |e: &Expression<'func, SSA>| e.visit_tree(f);
+
+        match info {
+            SetVar(ref op) => visit!(fb, &op.source_expr()),
+            SetVarField(ref op) | SetVarAliasedField(ref op) => visit!(fb, &op.source_expr()),
+            SetVarSplit(ref op) => visit!(fb, &op.source_expr()),
+            SetVarAliased(ref op) => visit!(fb, &op.source_expr()),
+            Store(ref op) => {
+                visit!(fb, &op.dest_mem_expr());
+                visit!(fb, &op.source_expr());
+            }
+            StoreStruct(ref op) => {
+                visit!(fb, &op.dest_mem_expr());
+                visit!(fb, &op.source_expr());
+            }
+            Call(ref op) | Tailcall(ref op) => {
+                visit!(fb, &op.target());
+                for param in op.params() {
+                    visit!(fb, &param);
+                }
+            }
+            CallUntyped(ref op) | TailcallUntyped(ref op) => {
+                visit!(fb, &op.target());
+                visit!(fb, &op.stack());
+            }
+            Syscall(ref op) => {
+                for param in op.params() {
+                    visit!(fb, &param);
+                }
+            }
+            SyscallUntyped(ref op) => visit!(fb, &op.stack()),
+            Intrinsic(ref op) => {
+                for param in op.params() {
+                    visit!(fb, &param);
+                }
+            }
+            _ => visit!(common_visit, &info, fb),
+        }
+
+        VisitorAction::Sibling
+    }
+}
+
+pub enum InstrInfo<'func, F>
+where
+    F: FunctionForm,
+{
+    Nop(Operation<'func, F, operation::NoArgs>),
+    SetVar(Operation<'func, F, operation::SetVar>),
+    SetVarField(Operation<'func, F, operation::SetVarField>),
+    SetVarSplit(Operation<'func, F, operation::SetVarSplit>),
+    SetVarAliased(Operation<'func, F, operation::SetVarAliased>),
+    SetVarAliasedField(Operation<'func, F, operation::SetVarField>),
+    Store(Operation<'func, F, operation::Store>),
+    StoreStruct(Operation<'func, F, operation::StoreStruct>),
+
+    Jump(Operation<'func, F, operation::Jump>),
+    JumpTo(Operation<'func, F, operation::JumpTo>),
+    RetHint(Operation<'func, F, operation::Jump>),
+
+    Call(Operation<'func, F, operation::Call>),
+    Tailcall(Operation<'func, F, operation::Call>),
+    CallUntyped(Operation<'func, F, operation::CallUntyped>),
+    TailcallUntyped(Operation<'func, F, operation::CallUntyped>),
+    Syscall(Operation<'func, F, operation::Syscall>),
+    SyscallUntyped(Operation<'func, F, operation::SyscallUntyped>),
+
+    Ret(Operation<'func, F, operation::Ret>),
+    NoRet(Operation<'func, F, operation::NoArgs>),
+
+    If(Operation<'func, F, operation::If>),
+    Goto(Operation<'func, F, operation::Goto>),
+
+    Intrinsic(Operation<'func, F, operation::Intrinsic>),
+    FreeVarSlot(Operation<'func, F, operation::FreeVarSlot>),
+
+    VarPhi(Operation<'func, F, operation::VarPhi>),
+    MemPhi(Operation<'func, F, operation::MemPhi>),
+
+    Bp(Operation<'func, F, operation::NoArgs>),
+    Trap(Operation<'func, F, operation::Trap>),
+    Undef(Operation<'func, F, operation::NoArgs>),
+
+    Value(Expression<'func, F>, ExprInfo<'func, F>),
+}
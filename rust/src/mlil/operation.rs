@@ -0,0 +1,1060 @@
+// Copyright 2021 Vector 35 Inc.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use binaryninjacore_sys::BNGetMediumLevelILByIndex;
+use binaryninjacore_sys::BNMediumLevelILInstruction;
+
+use std::marker::PhantomData;
+
+use super::*;
+
+pub struct Operation<'func, F, O>
+where
+    F: FunctionForm,
+    O: OperationArguments,
+{
+    pub(crate) function: &'func Function<F>,
+    pub(crate) op: BNMediumLevelILInstruction,
+    pub(crate) expr_idx: usize,
+    _args: PhantomData<O>,
+}
+
+impl<'func, F, O> Operation<'func, F, O>
+where
+    F: FunctionForm,
+    O: OperationArguments,
+{
+    pub(crate) fn new(
+        function: &'func Function<F>,
+        op: BNMediumLevelILInstruction,
+        expr_idx: usize,
+    ) -> Self {
+        Self {
+            function,
+            op,
+            expr_idx,
+            _args: PhantomData,
+        }
+    }
+
+    pub fn address(&self) -> u64 {
+        self.op.address
+    }
+
+    fn expr(&self, operand: usize) -> Expression<'func, F> {
+        Expression {
+            function: self.function,
+            expr_idx: self.op.operands[operand] as usize,
+        }
+    }
+
+    fn instr(&self, operand: usize) -> Instruction<'func, F> {
+        Instruction {
+            function: self.function,
+            instr_idx: self.op.operands[operand] as usize,
+        }
+    }
+
+    fn var(&self, operand: usize) -> Variable {
+        Variable::from_identifier(self.op.operands[operand])
+    }
+
+    fn ssa_var(&self, var_operand: usize, version_operand: usize) -> SSAVariable {
+        SSAVariable::new(
+            self.var(var_operand),
+            self.op.operands[version_operand] as usize,
+        )
+    }
+
+    fn expr_list(&self, operand: usize) -> Vec<Expression<'func, F>> {
+        self.function
+            .operand_list(self.expr_idx, operand)
+            .into_iter()
+            .map(|expr_idx| Expression {
+                function: self.function,
+                expr_idx: expr_idx as usize,
+            })
+            .collect()
+    }
+
+    // the untyped call variants and all of the SSA call variants keep their
+    // output and parameter lists in a MLIL_CALL_OUTPUT(_SSA)/MLIL_CALL_PARAM(_SSA)
+    // subexpression rather than inline
+    fn sub_expr(&self, operand: usize) -> (usize, BNMediumLevelILInstruction) {
+        let expr_idx = self.op.operands[operand] as usize;
+        let op = unsafe { BNGetMediumLevelILByIndex(self.function.handle, expr_idx) };
+
+        (expr_idx, op)
+    }
+}
+
+fn var_list<F: FunctionForm>(
+    function: &Function<F>,
+    expr_idx: usize,
+    operand: usize,
+) -> Vec<Variable> {
+    function
+        .operand_list(expr_idx, operand)
+        .into_iter()
+        .map(Variable::from_identifier)
+        .collect()
+}
+
+fn ssa_var_list<F: FunctionForm>(
+    function: &Function<F>,
+    expr_idx: usize,
+    operand: usize,
+) -> Vec<SSAVariable> {
+    function
+        .operand_list(expr_idx, operand)
+        .chunks(2)
+        .map(|pair| SSAVariable::new(Variable::from_identifier(pair[0]), pair[1] as usize))
+        .collect()
+}
+
+// MLIL_NOP, MLIL_NORET, MLIL_BP, MLIL_UNDEF, MLIL_UNIMPL
+pub struct NoArgs;
+
+// MLIL_SET_VAR, MLIL_SET_VAR_SSA
+pub struct SetVar;
+
+impl<'func, F> Operation<'func, F, SetVar>
+where
+    F: FunctionForm,
+{
+    pub fn size(&self) -> usize {
+        self.op.size
+    }
+}
+
+impl<'func> Operation<'func, NonSSA, SetVar> {
+    pub fn dest(&self) -> Variable {
+        self.var(0)
+    }
+
+    pub fn source_expr(&self) -> Expression<'func, NonSSA> {
+        self.expr(1)
+    }
+}
+
+impl<'func> Operation<'func, SSA, SetVar> {
+    pub fn dest(&self) -> SSAVariable {
+        self.ssa_var(0, 1)
+    }
+
+    pub fn source_expr(&self) -> Expression<'func, SSA> {
+        self.expr(2)
+    }
+}
+
+// MLIL_SET_VAR_FIELD, MLIL_SET_VAR_SSA_FIELD, MLIL_SET_VAR_ALIASED_FIELD
+pub struct SetVarField;
+
+impl<'func, F> Operation<'func, F, SetVarField>
+where
+    F: FunctionForm,
+{
+    pub fn size(&self) -> usize {
+        self.op.size
+    }
+}
+
+impl<'func> Operation<'func, NonSSA, SetVarField> {
+    pub fn dest(&self) -> Variable {
+        self.var(0)
+    }
+
+    pub fn offset(&self) -> u64 {
+        self.op.operands[1]
+    }
+
+    pub fn source_expr(&self) -> Expression<'func, NonSSA> {
+        self.expr(2)
+    }
+}
+
+impl<'func> Operation<'func, SSA, SetVarField> {
+    pub fn dest(&self) -> SSAVariable {
+        self.ssa_var(0, 1)
+    }
+
+    /// The version of `dest` whose untouched bytes flow into the new version
+    pub fn prev(&self) -> SSAVariable {
+        self.ssa_var(0, 2)
+    }
+
+    pub fn offset(&self) -> u64 {
+        self.op.operands[3]
+    }
+
+    pub fn source_expr(&self) -> Expression<'func, SSA> {
+        self.expr(4)
+    }
+}
+
+// MLIL_SET_VAR_ALIASED
+pub struct SetVarAliased;
+
+impl<'func> Operation<'func, SSA, SetVarAliased> {
+    pub fn size(&self) -> usize {
+        self.op.size
+    }
+
+    pub fn dest(&self) -> SSAVariable {
+        self.ssa_var(0, 1)
+    }
+
+    pub fn prev(&self) -> SSAVariable {
+        self.ssa_var(0, 2)
+    }
+
+    pub fn source_expr(&self) -> Expression<'func, SSA> {
+        self.expr(3)
+    }
+}
+
+// MLIL_SET_VAR_SPLIT, MLIL_SET_VAR_SPLIT_SSA
+pub struct SetVarSplit;
+
+impl<'func, F> Operation<'func, F, SetVarSplit>
+where
+    F: FunctionForm,
+{
+    pub fn size(&self) -> usize {
+        self.op.size
+    }
+}
+
+impl<'func> Operation<'func, NonSSA, SetVarSplit> {
+    pub fn dest_high(&self) -> Variable {
+        self.var(0)
+    }
+
+    pub fn dest_low(&self) -> Variable {
+        self.var(1)
+    }
+
+    pub fn source_expr(&self) -> Expression<'func, NonSSA> {
+        self.expr(2)
+    }
+}
+
+impl<'func> Operation<'func, SSA, SetVarSplit> {
+    pub fn dest_high(&self) -> SSAVariable {
+        self.ssa_var(0, 1)
+    }
+
+    pub fn dest_low(&self) -> SSAVariable {
+        self.ssa_var(2, 3)
+    }
+
+    pub fn source_expr(&self) -> Expression<'func, SSA> {
+        self.expr(4)
+    }
+}
+
+// MLIL_LOAD, MLIL_LOAD_SSA
+pub struct Load;
+
+impl<'func, F> Operation<'func, F, Load>
+where
+    F: FunctionForm,
+{
+    pub fn size(&self) -> usize {
+        self.op.size
+    }
+
+    pub fn source_mem_expr(&self) -> Expression<'func, F> {
+        self.expr(0)
+    }
+}
+
+impl<'func> Operation<'func, SSA, Load> {
+    pub fn source_memory_version(&self) -> usize {
+        self.op.operands[1] as usize
+    }
+}
+
+// MLIL_LOAD_STRUCT, MLIL_LOAD_STRUCT_SSA
+pub struct LoadStruct;
+
+impl<'func, F> Operation<'func, F, LoadStruct>
+where
+    F: FunctionForm,
+{
+    pub fn size(&self) -> usize {
+        self.op.size
+    }
+
+    pub fn source_mem_expr(&self) -> Expression<'func, F> {
+        self.expr(0)
+    }
+
+    pub fn offset(&self) -> u64 {
+        self.op.operands[1]
+    }
+}
+
+impl<'func> Operation<'func, SSA, LoadStruct> {
+    pub fn source_memory_version(&self) -> usize {
+        self.op.operands[2] as usize
+    }
+}
+
+// MLIL_STORE, MLIL_STORE_SSA
+pub struct Store;
+
+impl<'func, F> Operation<'func, F, Store>
+where
+    F: FunctionForm,
+{
+    pub fn size(&self) -> usize {
+        self.op.size
+    }
+
+    pub fn dest_mem_expr(&self) -> Expression<'func, F> {
+        self.expr(0)
+    }
+}
+
+impl<'func> Operation<'func, NonSSA, Store> {
+    pub fn source_expr(&self) -> Expression<'func, NonSSA> {
+        self.expr(1)
+    }
+}
+
+impl<'func> Operation<'func, SSA, Store> {
+    pub fn dest_memory_version(&self) -> usize {
+        self.op.operands[1] as usize
+    }
+
+    pub fn source_memory_version(&self) -> usize {
+        self.op.operands[2] as usize
+    }
+
+    pub fn source_expr(&self) -> Expression<'func, SSA> {
+        self.expr(3)
+    }
+}
+
+// MLIL_STORE_STRUCT, MLIL_STORE_STRUCT_SSA
+pub struct StoreStruct;
+
+impl<'func, F> Operation<'func, F, StoreStruct>
+where
+    F: FunctionForm,
+{
+    pub fn size(&self) -> usize {
+        self.op.size
+    }
+
+    pub fn dest_mem_expr(&self) -> Expression<'func, F> {
+        self.expr(0)
+    }
+
+    pub fn offset(&self) -> u64 {
+        self.op.operands[1]
+    }
+}
+
+impl<'func> Operation<'func, NonSSA, StoreStruct> {
+    pub fn source_expr(&self) -> Expression<'func, NonSSA> {
+        self.expr(2)
+    }
+}
+
+impl<'func> Operation<'func, SSA, StoreStruct> {
+    pub fn dest_memory_version(&self) -> usize {
+        self.op.operands[2] as usize
+    }
+
+    pub fn source_memory_version(&self) -> usize {
+        self.op.operands[3] as usize
+    }
+
+    pub fn source_expr(&self) -> Expression<'func, SSA> {
+        self.expr(4)
+    }
+}
+
+// MLIL_VAR, MLIL_VAR_SSA, MLIL_VAR_ALIASED
+pub struct Var;
+
+impl<'func, F> Operation<'func, F, Var>
+where
+    F: FunctionForm,
+{
+    pub fn size(&self) -> usize {
+        self.op.size
+    }
+}
+
+impl<'func> Operation<'func, NonSSA, Var> {
+    pub fn source_var(&self) -> Variable {
+        self.var(0)
+    }
+}
+
+impl<'func> Operation<'func, SSA, Var> {
+    pub fn source_var(&self) -> SSAVariable {
+        self.ssa_var(0, 1)
+    }
+}
+
+// MLIL_VAR_FIELD, MLIL_VAR_SSA_FIELD, MLIL_VAR_ALIASED_FIELD
+pub struct VarField;
+
+impl<'func, F> Operation<'func, F, VarField>
+where
+    F: FunctionForm,
+{
+    pub fn size(&self) -> usize {
+        self.op.size
+    }
+}
+
+impl<'func> Operation<'func, NonSSA, VarField> {
+    pub fn source_var(&self) -> Variable {
+        self.var(0)
+    }
+
+    pub fn offset(&self) -> u64 {
+        self.op.operands[1]
+    }
+}
+
+impl<'func> Operation<'func, SSA, VarField> {
+    pub fn source_var(&self) -> SSAVariable {
+        self.ssa_var(0, 1)
+    }
+
+    pub fn offset(&self) -> u64 {
+        self.op.operands[2]
+    }
+}
+
+// MLIL_VAR_SPLIT, MLIL_VAR_SPLIT_SSA
+pub struct VarSplit;
+
+impl<'func, F> Operation<'func, F, VarSplit>
+where
+    F: FunctionForm,
+{
+    pub fn size(&self) -> usize {
+        self.op.size
+    }
+}
+
+impl<'func> Operation<'func, NonSSA, VarSplit> {
+    pub fn high(&self) -> Variable {
+        self.var(0)
+    }
+
+    pub fn low(&self) -> Variable {
+        self.var(1)
+    }
+}
+
+impl<'func> Operation<'func, SSA, VarSplit> {
+    pub fn high(&self) -> SSAVariable {
+        self.ssa_var(0, 1)
+    }
+
+    pub fn low(&self) -> SSAVariable {
+        self.ssa_var(2, 3)
+    }
+}
+
+// MLIL_ADDRESS_OF
+pub struct AddressOf;
+
+impl<'func, F> Operation<'func, F, AddressOf>
+where
+    F: FunctionForm,
+{
+    pub fn size(&self) -> usize {
+        self.op.size
+    }
+
+    pub fn source_var(&self) -> Variable {
+        self.var(0)
+    }
+}
+
+// MLIL_ADDRESS_OF_FIELD
+pub struct AddressOfField;
+
+impl<'func, F> Operation<'func, F, AddressOfField>
+where
+    F: FunctionForm,
+{
+    pub fn size(&self) -> usize {
+        self.op.size
+    }
+
+    pub fn source_var(&self) -> Variable {
+        self.var(0)
+    }
+
+    pub fn offset(&self) -> u64 {
+        self.op.operands[1]
+    }
+}
+
+// MLIL_CONST, MLIL_CONST_PTR, MLIL_IMPORT
+pub struct Const;
+
+impl<'func, F> Operation<'func, F, Const>
+where
+    F: FunctionForm,
+{
+    pub fn size(&self) -> usize {
+        self.op.size
+    }
+
+    pub fn value(&self) -> u64 {
+        self.op.operands[0]
+    }
+}
+
+// MLIL_EXTERN_PTR
+pub struct ExternPtr;
+
+impl<'func, F> Operation<'func, F, ExternPtr>
+where
+    F: FunctionForm,
+{
+    pub fn size(&self) -> usize {
+        self.op.size
+    }
+
+    pub fn value(&self) -> u64 {
+        self.op.operands[0]
+    }
+
+    pub fn offset(&self) -> u64 {
+        self.op.operands[1]
+    }
+}
+
+// MLIL_FLOAT_CONST
+pub struct FloatConst;
+
+impl<'func, F> Operation<'func, F, FloatConst>
+where
+    F: FunctionForm,
+{
+    pub fn size(&self) -> usize {
+        self.op.size
+    }
+
+    pub fn raw_value(&self) -> u64 {
+        self.op.operands[0]
+    }
+
+    /// Decodes single and double precision constants; other sizes only have `raw_value`
+    pub fn value(&self) -> Option<f64> {
+        match self.op.size {
+            4 => Some(f32::from_bits(self.op.operands[0] as u32) as f64),
+            8 => Some(f64::from_bits(self.op.operands[0])),
+            _ => None,
+        }
+    }
+}
+
+// MLIL_ADD, MLIL_SUB, MLIL_AND, MLIL_OR, MLIL_XOR, MLIL_LSL,
+// MLIL_LSR, MLIL_ASR, MLIL_ROL, MLIL_ROR, MLIL_MUL, MLIL_MULU_DP,
+// MLIL_MULS_DP, MLIL_DIVU, MLIL_DIVU_DP, MLIL_DIVS, MLIL_DIVS_DP,
+// MLIL_MODU, MLIL_MODU_DP, MLIL_MODS, MLIL_MODS_DP, MLIL_TEST_BIT,
+// MLIL_ADD_OVERFLOW, MLIL_FADD, MLIL_FSUB, MLIL_FMUL, MLIL_FDIV
+pub struct BinaryOp;
+
+impl<'func, F> Operation<'func, F, BinaryOp>
+where
+    F: FunctionForm,
+{
+    pub fn size(&self) -> usize {
+        self.op.size
+    }
+
+    pub fn left(&self) -> Expression<'func, F> {
+        self.expr(0)
+    }
+
+    pub fn right(&self) -> Expression<'func, F> {
+        self.expr(1)
+    }
+}
+
+// MLIL_ADC, MLIL_SBB, MLIL_RLC, MLIL_RRC
+pub struct BinaryOpCarry;
+
+impl<'func, F> Operation<'func, F, BinaryOpCarry>
+where
+    F: FunctionForm,
+{
+    pub fn size(&self) -> usize {
+        self.op.size
+    }
+
+    pub fn left(&self) -> Expression<'func, F> {
+        self.expr(0)
+    }
+
+    pub fn right(&self) -> Expression<'func, F> {
+        self.expr(1)
+    }
+
+    pub fn carry(&self) -> Expression<'func, F> {
+        self.expr(2)
+    }
+}
+
+// MLIL_NEG, MLIL_NOT, MLIL_SX, MLIL_ZX, MLIL_LOW_PART, MLIL_BOOL_TO_INT,
+// MLIL_FSQRT, MLIL_FNEG, MLIL_FABS, MLIL_FLOAT_TO_INT, MLIL_INT_TO_FLOAT,
+// MLIL_FLOAT_CONV, MLIL_ROUND_TO_INT, MLIL_FLOOR, MLIL_CEIL, MLIL_FTRUNC
+pub struct UnaryOp;
+
+impl<'func, F> Operation<'func, F, UnaryOp>
+where
+    F: FunctionForm,
+{
+    pub fn size(&self) -> usize {
+        self.op.size
+    }
+
+    pub fn operand(&self) -> Expression<'func, F> {
+        self.expr(0)
+    }
+}
+
+// MLIL_CMP_X, MLIL_FCMP_X
+pub struct Condition;
+
+impl<'func, F> Operation<'func, F, Condition>
+where
+    F: FunctionForm,
+{
+    pub fn size(&self) -> usize {
+        self.op.size
+    }
+
+    pub fn left(&self) -> Expression<'func, F> {
+        self.expr(0)
+    }
+
+    pub fn right(&self) -> Expression<'func, F> {
+        self.expr(1)
+    }
+}
+
+// MLIL_UNIMPL_MEM
+pub struct UnimplMem;
+
+impl<'func, F> Operation<'func, F, UnimplMem>
+where
+    F: FunctionForm,
+{
+    pub fn size(&self) -> usize {
+        self.op.size
+    }
+
+    pub fn mem_expr(&self) -> Expression<'func, F> {
+        self.expr(0)
+    }
+}
+
+// MLIL_JUMP, MLIL_RET_HINT
+pub struct Jump;
+
+impl<'func, F> Operation<'func, F, Jump>
+where
+    F: FunctionForm,
+{
+    pub fn target(&self) -> Expression<'func, F> {
+        self.expr(0)
+    }
+}
+
+// MLIL_JUMP_TO
+pub struct JumpTo;
+
+impl<'func, F> Operation<'func, F, JumpTo>
+where
+    F: FunctionForm,
+{
+    pub fn target(&self) -> Expression<'func, F> {
+        self.expr(0)
+    }
+
+    /// Pairs of jump table values and the instruction they lead to
+    pub fn targets(&self) -> Vec<(u64, Instruction<'func, F>)> {
+        self.function
+            .operand_list(self.expr_idx, 1)
+            .chunks(2)
+            .map(|pair| {
+                (
+                    pair[0],
+                    Instruction {
+                        function: self.function,
+                        instr_idx: pair[1] as usize,
+                    },
+                )
+            })
+            .collect()
+    }
+}
+
+// MLIL_CALL, MLIL_TAILCALL, MLIL_CALL_SSA, MLIL_TAILCALL_SSA
+pub struct Call;
+
+impl<'func> Operation<'func, NonSSA, Call> {
+    pub fn output(&self) -> Vec<Variable> {
+        var_list(self.function, self.expr_idx, 0)
+    }
+
+    pub fn target(&self) -> Expression<'func, NonSSA> {
+        self.expr(2)
+    }
+
+    pub fn params(&self) -> Vec<Expression<'func, NonSSA>> {
+        self.expr_list(3)
+    }
+}
+
+impl<'func> Operation<'func, SSA, Call> {
+    pub fn output(&self) -> Vec<SSAVariable> {
+        let (output_idx, _) = self.sub_expr(0);
+        ssa_var_list(self.function, output_idx, 1)
+    }
+
+    pub fn dest_memory_version(&self) -> usize {
+        let (_, output) = self.sub_expr(0);
+        output.operands[0] as usize
+    }
+
+    pub fn target(&self) -> Expression<'func, SSA> {
+        self.expr(1)
+    }
+
+    pub fn params(&self) -> Vec<Expression<'func, SSA>> {
+        self.expr_list(2)
+    }
+
+    pub fn source_memory_version(&self) -> usize {
+        self.op.operands[4] as usize
+    }
+}
+
+// MLIL_CALL_UNTYPED, MLIL_TAILCALL_UNTYPED, MLIL_CALL_UNTYPED_SSA,
+// MLIL_TAILCALL_UNTYPED_SSA
+pub struct CallUntyped;
+
+impl<'func, F> Operation<'func, F, CallUntyped>
+where
+    F: FunctionForm,
+{
+    pub fn target(&self) -> Expression<'func, F> {
+        self.expr(1)
+    }
+
+    pub fn stack(&self) -> Expression<'func, F> {
+        self.expr(3)
+    }
+}
+
+impl<'func> Operation<'func, NonSSA, CallUntyped> {
+    pub fn output(&self) -> Vec<Variable> {
+        let (output_idx, _) = self.sub_expr(0);
+        var_list(self.function, output_idx, 0)
+    }
+
+    pub fn params(&self) -> Vec<Variable> {
+        let (params_idx, _) = self.sub_expr(2);
+        var_list(self.function, params_idx, 0)
+    }
+}
+
+impl<'func> Operation<'func, SSA, CallUntyped> {
+    pub fn output(&self) -> Vec<SSAVariable> {
+        let (output_idx, _) = self.sub_expr(0);
+        ssa_var_list(self.function, output_idx, 1)
+    }
+
+    pub fn dest_memory_version(&self) -> usize {
+        let (_, output) = self.sub_expr(0);
+        output.operands[0] as usize
+    }
+
+    pub fn params(&self) -> Vec<SSAVariable> {
+        let (params_idx, _) = self.sub_expr(2);
+        ssa_var_list(self.function, params_idx, 1)
+    }
+
+    pub fn source_memory_version(&self) -> usize {
+        let (_, params) = self.sub_expr(2);
+        params.operands[0] as usize
+    }
+}
+
+// MLIL_SYSCALL, MLIL_SYSCALL_SSA
+pub struct Syscall;
+
+impl<'func> Operation<'func, NonSSA, Syscall> {
+    pub fn output(&self) -> Vec<Variable> {
+        var_list(self.function, self.expr_idx, 0)
+    }
+
+    pub fn params(&self) -> Vec<Expression<'func, NonSSA>> {
+        self.expr_list(2)
+    }
+}
+
+impl<'func> Operation<'func, SSA, Syscall> {
+    pub fn output(&self) -> Vec<SSAVariable> {
+        let (output_idx, _) = self.sub_expr(0);
+        ssa_var_list(self.function, output_idx, 1)
+    }
+
+    pub fn dest_memory_version(&self) -> usize {
+        let (_, output) = self.sub_expr(0);
+        output.operands[0] as usize
+    }
+
+    pub fn params(&self) -> Vec<Expression<'func, SSA>> {
+        self.expr_list(1)
+    }
+
+    pub fn source_memory_version(&self) -> usize {
+        self.op.operands[3] as usize
+    }
+}
+
+// MLIL_SYSCALL_UNTYPED, MLIL_SYSCALL_UNTYPED_SSA
+pub struct SyscallUntyped;
+
+impl<'func, F> Operation<'func, F, SyscallUntyped>
+where
+    F: FunctionForm,
+{
+    pub fn stack(&self) -> Expression<'func, F> {
+        self.expr(2)
+    }
+}
+
+impl<'func> Operation<'func, NonSSA, SyscallUntyped> {
+    pub fn output(&self) -> Vec<Variable> {
+        let (output_idx, _) = self.sub_expr(0);
+        var_list(self.function, output_idx, 0)
+    }
+
+    pub fn params(&self) -> Vec<Variable> {
+        let (params_idx, _) = self.sub_expr(1);
+        var_list(self.function, params_idx, 0)
+    }
+}
+
+impl<'func> Operation<'func, SSA, SyscallUntyped> {
+    pub fn output(&self) -> Vec<SSAVariable> {
+        let (output_idx, _) = self.sub_expr(0);
+        ssa_var_list(self.function, output_idx, 1)
+    }
+
+    pub fn dest_memory_version(&self) -> usize {
+        let (_, output) = self.sub_expr(0);
+        output.operands[0] as usize
+    }
+
+    pub fn params(&self) -> Vec<SSAVariable> {
+        let (params_idx, _) = self.sub_expr(1);
+        ssa_var_list(self.function, params_idx, 1)
+    }
+
+    pub fn source_memory_version(&self) -> usize {
+        let (_, params) = self.sub_expr(1);
+        params.operands[0] as usize
+    }
+}
+
+// MLIL_RET
+pub struct Ret;
+
+impl<'func, F> Operation<'func, F, Ret>
+where
+    F: FunctionForm,
+{
+    pub fn sources(&self) -> Vec<Expression<'func, F>> {
+        self.expr_list(0)
+    }
+}
+
+// MLIL_IF
+pub struct If;
+
+impl<'func, F> Operation<'func, F, If>
+where
+    F: FunctionForm,
+{
+    pub fn condition(&self) -> Expression<'func, F> {
+        self.expr(0)
+    }
+
+    pub fn true_target(&self) -> Instruction<'func, F> {
+        self.instr(1)
+    }
+
+    pub fn false_target(&self) -> Instruction<'func, F> {
+        self.instr(2)
+    }
+}
+
+// MLIL_GOTO
+pub struct Goto;
+
+impl<'func, F> Operation<'func, F, Goto>
+where
+    F: FunctionForm,
+{
+    pub fn target(&self) -> Instruction<'func, F> {
+        self.instr(0)
+    }
+}
+
+// MLIL_INTRINSIC, MLIL_INTRINSIC_SSA
+pub struct Intrinsic;
+
+impl<'func, F> Operation<'func, F, Intrinsic>
+where
+    F: FunctionForm,
+{
+    pub fn intrinsic_id(&self) -> u32 {
+        self.op.operands[2] as u32
+    }
+
+    pub fn params(&self) -> Vec<Expression<'func, F>> {
+        self.expr_list(3)
+    }
+}
+
+impl<'func> Operation<'func, NonSSA, Intrinsic> {
+    pub fn output(&self) -> Vec<Variable> {
+        var_list(self.function, self.expr_idx, 0)
+    }
+}
+
+impl<'func> Operation<'func, SSA, Intrinsic> {
+    pub fn output(&self) -> Vec<SSAVariable> {
+        ssa_var_list(self.function, self.expr_idx, 0)
+    }
+}
+
+// MLIL_FREE_VAR_SLOT, MLIL_FREE_VAR_SLOT_SSA
+pub struct FreeVarSlot;
+
+impl<'func> Operation<'func, NonSSA, FreeVarSlot> {
+    pub fn dest(&self) -> Variable {
+        self.var(0)
+    }
+}
+
+impl<'func> Operation<'func, SSA, FreeVarSlot> {
+    pub fn dest(&self) -> SSAVariable {
+        self.ssa_var(0, 1)
+    }
+
+    pub fn prev(&self) -> SSAVariable {
+        self.ssa_var(0, 2)
+    }
+}
+
+// MLIL_TRAP
+pub struct Trap;
+
+impl<'func, F> Operation<'func, F, Trap>
+where
+    F: FunctionForm,
+{
+    pub fn vector(&self) -> u64 {
+        self.op.operands[0]
+    }
+}
+
+// MLIL_VAR_PHI
+pub struct VarPhi;
+
+impl<'func> Operation<'func, SSA, VarPhi> {
+    pub fn dest(&self) -> SSAVariable {
+        self.ssa_var(0, 1)
+    }
+
+    pub fn sources(&self) -> Vec<SSAVariable> {
+        ssa_var_list(self.function, self.expr_idx, 2)
+    }
+}
+
+// MLIL_MEM_PHI
+pub struct MemPhi;
+
+impl<'func> Operation<'func, SSA, MemPhi> {
+    pub fn dest_memory_version(&self) -> usize {
+        self.op.operands[0] as usize
+    }
+
+    pub fn source_memory_versions(&self) -> Vec<usize> {
+        self.function
+            .operand_list(self.expr_idx, 1)
+            .into_iter()
+            .map(|version| version as usize)
+            .collect()
+    }
+}
+
+pub trait OperationArguments: 'static {}
+
+impl OperationArguments for NoArgs {}
+impl OperationArguments for SetVar {}
+impl OperationArguments for SetVarField {}
+impl OperationArguments for SetVarAliased {}
+impl OperationArguments for SetVarSplit {}
+impl OperationArguments for Load {}
+impl OperationArguments for LoadStruct {}
+impl OperationArguments for Store {}
+impl OperationArguments for StoreStruct {}
+impl OperationArguments for Var {}
+impl OperationArguments for VarField {}
+impl OperationArguments for VarSplit {}
+impl OperationArguments for AddressOf {}
+impl OperationArguments for AddressOfField {}
+impl OperationArguments for Const {}
+impl OperationArguments for ExternPtr {}
+impl OperationArguments for FloatConst {}
+impl OperationArguments for BinaryOp {}
+impl OperationArguments for BinaryOpCarry {}
+impl OperationArguments for UnaryOp {}
+impl OperationArguments for Condition {}
+impl OperationArguments for UnimplMem {}
+impl OperationArguments for Jump {}
+impl OperationArguments for JumpTo {}
+impl OperationArguments for Call {}
+impl OperationArguments for CallUntyped {}
+impl OperationArguments for Syscall {}
+impl OperationArguments for SyscallUntyped {}
+impl OperationArguments for Ret {}
+impl OperationArguments for If {}
+impl OperationArguments for Goto {}
+impl OperationArguments for Intrinsic {}
+impl OperationArguments for FreeVarSlot {}
+impl OperationArguments for Trap {}
+impl OperationArguments for VarPhi {}
+impl OperationArguments for MemPhi {}
@@ -21,6 +21,7 @@ use crate::{
     callingconvention::CallingConvention,
     rc::*,
     string::*,
+    typelibrary::TypeLibrary,
     types::{QualifiedName, QualifiedNameAndType, Type},
 };
 
@@ -230,6 +231,15 @@ impl Platform {
             Array::new(handles, count, ())
         }
     }
+
+    pub fn type_libraries(&self) -> Array<TypeLibrary> {
+        unsafe {
+            let mut count = 0;
+            let handles = BNGetPlatformTypeLibraries(self.handle, &mut count);
+
+            Array::new(handles, count, ())
+        }
+    }
 }
 
 pub trait TypeParser {
@@ -0,0 +1,96 @@
+// Copyright 2021 Vector 35 Inc.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use binaryninjacore_sys::*;
+
+use std::fmt;
+use std::slice;
+
+use crate::architecture::CoreArchitecture;
+use crate::function::Function;
+
+use crate::rc::*;
+
+/// A location in code that references an address
+pub struct CodeReference {
+    pub function: Ref<Function>,
+    pub arch: CoreArchitecture,
+    pub address: u64,
+}
+
+impl CodeReference {
+    pub(crate) unsafe fn from_raw(raw: &BNReferenceSource) -> Self {
+        Self {
+            function: Function::from_raw(BNNewFunctionReference(raw.func)),
+            arch: CoreArchitecture::from_raw(raw.arch),
+            address: raw.addr,
+        }
+    }
+
+    pub(crate) fn as_raw(&self) -> BNReferenceSource {
+        BNReferenceSource {
+            func: self.function.handle,
+            arch: self.arch.0,
+            addr: self.address,
+        }
+    }
+}
+
+impl fmt::Debug for CodeReference {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "<ref {:x} in {:?}>", self.address, *self.function)
+    }
+}
+
+unsafe impl CoreOwnedArrayProvider for CodeReference {
+    type Raw = BNReferenceSource;
+    type Context = ();
+
+    unsafe fn free(raw: *mut BNReferenceSource, count: usize, _context: &()) {
+        BNFreeCodeReferences(raw, count);
+    }
+}
+
+unsafe impl<'a> CoreOwnedArrayWrapper<'a> for CodeReference {
+    type Wrapped = CodeReference;
+
+    unsafe fn wrap_raw(raw: &'a BNReferenceSource, _context: &'a ()) -> CodeReference {
+        CodeReference::from_raw(raw)
+    }
+}
+
+// address lists (call targets, code references from an instruction) and data reference
+// lists are plain `u64` buffers that we copy out so callers don't need to hold core memory
+unsafe fn copy_addresses(
+    raw: *mut u64,
+    count: usize,
+    free: unsafe extern "C" fn(*mut u64),
+) -> Vec<u64> {
+    if raw.is_null() {
+        return Vec::new();
+    }
+
+    let res = slice::from_raw_parts(raw, count).to_vec();
+    free(raw);
+
+    res
+}
+
+pub(crate) unsafe fn address_list(raw: *mut u64, count: usize) -> Vec<u64> {
+    copy_addresses(raw, count, BNFreeAddressList)
+}
+
+pub(crate) unsafe fn data_reference_list(raw: *mut u64, count: usize) -> Vec<u64> {
+    copy_addresses(raw, count, BNFreeDataReferences)
+}
@@ -0,0 +1,628 @@
+// Copyright 2021 Vector 35 Inc.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Relocations and the per-architecture handlers that apply them.
+
+use binaryninjacore_sys::*;
+
+use std::ops;
+use std::os::raw::c_void;
+use std::ptr;
+use std::slice;
+
+use crate::architecture::{Architecture, CoreArchitecture};
+use crate::binaryview::BinaryView;
+use crate::llil::Lifter;
+use crate::symbol::Symbol;
+
+use crate::rc::*;
+use crate::string::*;
+use crate::{BN_AUTOCOERCE_EXTERN_PTR, BN_INVALID_OPERAND, BN_NOCOERCE_EXTERN_PTR};
+
+pub type RelocationType = BNRelocationType;
+
+/// Which operand of an instruction refers to an external relocation
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+pub enum RelocationOperand {
+    /// Let the core pick the operand and treat it as a pointer to the external symbol
+    AutocoerceExternPtr,
+    /// Let the core pick the operand, leaving its value as is
+    NocoerceExternPtr,
+    Invalid,
+    Operand(usize),
+}
+
+impl RelocationOperand {
+    fn from_raw(raw: usize) -> Self {
+        match raw {
+            BN_AUTOCOERCE_EXTERN_PTR => RelocationOperand::AutocoerceExternPtr,
+            BN_NOCOERCE_EXTERN_PTR => RelocationOperand::NocoerceExternPtr,
+            BN_INVALID_OPERAND => RelocationOperand::Invalid,
+            operand => RelocationOperand::Operand(operand),
+        }
+    }
+
+    fn into_raw(self) -> usize {
+        match self {
+            RelocationOperand::AutocoerceExternPtr => BN_AUTOCOERCE_EXTERN_PTR,
+            RelocationOperand::NocoerceExternPtr => BN_NOCOERCE_EXTERN_PTR,
+            RelocationOperand::Invalid => BN_INVALID_OPERAND,
+            RelocationOperand::Operand(operand) => operand,
+        }
+    }
+}
+
+/// Description of a single relocation entry
+///
+/// Views fill these in from the file's relocation tables and hand them to the
+/// architecture's `RelocationHandler` to be refined before they are applied.
+#[derive(Clone, Debug)]
+pub struct RelocationInfo {
+    pub reloc_type: RelocationType,
+    pub pc_relative: bool,
+    pub base_relative: bool,
+    pub base: u64,
+    pub size: usize,
+    pub truncate_size: usize,
+    /// The relocation type as encoded in the file, e.g. `R_X86_64_PC32`
+    pub native_type: u64,
+    pub addend: usize,
+    pub has_sign: bool,
+    pub implicit_addend: bool,
+    pub external: bool,
+    pub symbol_index: usize,
+    pub section_index: usize,
+    pub address: u64,
+    pub target: u64,
+    pub data_relocation: bool,
+    pub relocation_data_cache: [u8; 8],
+}
+
+impl RelocationInfo {
+    pub fn new() -> Self {
+        Self {
+            reloc_type: RelocationType::UnhandledRelocation,
+            pc_relative: false,
+            base_relative: false,
+            base: 0,
+            size: 0,
+            truncate_size: 0,
+            native_type: 0,
+            addend: 0,
+            has_sign: false,
+            implicit_addend: false,
+            external: false,
+            symbol_index: 0,
+            section_index: 0,
+            address: 0,
+            target: 0,
+            data_relocation: false,
+            relocation_data_cache: [0; 8],
+        }
+    }
+
+    pub(crate) fn from_raw(raw: &BNRelocationInfo) -> Self {
+        Self {
+            reloc_type: raw.type_,
+            pc_relative: raw.pcRelative,
+            base_relative: raw.baseRelative,
+            base: raw.base,
+            size: raw.size,
+            truncate_size: raw.truncateSize,
+            native_type: raw.nativeType,
+            addend: raw.addend,
+            has_sign: raw.hasSign,
+            implicit_addend: raw.implicitAddend,
+            external: raw.external,
+            symbol_index: raw.symbolIndex,
+            section_index: raw.sectionIndex,
+            address: raw.address,
+            target: raw.target,
+            data_relocation: raw.dataRelocation,
+            relocation_data_cache: raw.relocationDataCache,
+        }
+    }
+
+    /// Copies the fields back into `raw`, leaving its list links alone
+    pub(crate) fn write_raw(&self, raw: &mut BNRelocationInfo) {
+        raw.type_ = self.reloc_type;
+        raw.pcRelative = self.pc_relative;
+        raw.baseRelative = self.base_relative;
+        raw.base = self.base;
+        raw.size = self.size;
+        raw.truncateSize = self.truncate_size;
+        raw.nativeType = self.native_type;
+        raw.addend = self.addend;
+        raw.hasSign = self.has_sign;
+        raw.implicitAddend = self.implicit_addend;
+        raw.external = self.external;
+        raw.symbolIndex = self.symbol_index;
+        raw.sectionIndex = self.section_index;
+        raw.address = self.address;
+        raw.target = self.target;
+        raw.dataRelocation = self.data_relocation;
+        raw.relocationDataCache = self.relocation_data_cache;
+    }
+
+    pub(crate) fn as_raw(&self) -> BNRelocationInfo {
+        let mut raw = BNRelocationInfo {
+            type_: self.reloc_type,
+            pcRelative: false,
+            baseRelative: false,
+            base: 0,
+            size: 0,
+            truncateSize: 0,
+            nativeType: 0,
+            addend: 0,
+            hasSign: false,
+            implicitAddend: false,
+            external: false,
+            symbolIndex: 0,
+            sectionIndex: 0,
+            address: 0,
+            target: 0,
+            dataRelocation: false,
+            relocationDataCache: [0; 8],
+            prev: ptr::null_mut(),
+            next: ptr::null_mut(),
+        };
+
+        self.write_raw(&mut raw);
+
+        raw
+    }
+}
+
+impl Default for RelocationInfo {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// A relocation that has been defined in a view
+#[derive(PartialEq, Eq, Hash)]
+pub struct Relocation {
+    pub(crate) handle: *mut BNRelocation,
+}
+
+impl Relocation {
+    pub(crate) unsafe fn from_raw(handle: *mut BNRelocation) -> Self {
+        debug_assert!(!handle.is_null());
+
+        Self { handle }
+    }
+
+    pub fn info(&self) -> RelocationInfo {
+        RelocationInfo::from_raw(unsafe { &BNRelocationGetInfo(self.handle) })
+    }
+
+    pub fn arch(&self) -> Option<CoreArchitecture> {
+        unsafe {
+            let arch = BNRelocationGetArchitecture(self.handle);
+
+            if arch.is_null() {
+                return None;
+            }
+
+            Some(CoreArchitecture::from_raw(arch))
+        }
+    }
+
+    pub fn target(&self) -> u64 {
+        unsafe { BNRelocationGetTarget(self.handle) }
+    }
+
+    /// Address the relocation is applied at
+    pub fn address(&self) -> u64 {
+        unsafe { BNRelocationGetReloc(self.handle) }
+    }
+
+    pub fn symbol(&self) -> Option<Ref<Symbol>> {
+        unsafe {
+            let sym = BNRelocationGetSymbol(self.handle);
+
+            if sym.is_null() {
+                return None;
+            }
+
+            Some(Ref::new(Symbol::from_raw(sym)))
+        }
+    }
+}
+
+unsafe impl Send for Relocation {}
+unsafe impl Sync for Relocation {}
+
+impl ToOwned for Relocation {
+    type Owned = Ref<Self>;
+
+    fn to_owned(&self) -> Self::Owned {
+        unsafe { RefCountable::inc_ref(self) }
+    }
+}
+
+unsafe impl RefCountable for Relocation {
+    unsafe fn inc_ref(handle: &Self) -> Ref<Self> {
+        Ref::new(Self {
+            handle: BNNewRelocationReference(handle.handle),
+        })
+    }
+
+    unsafe fn dec_ref(handle: &Self) {
+        BNFreeRelocation(handle.handle);
+    }
+}
+
+/// Collects and frees a range list returned by the core
+pub(crate) unsafe fn relocation_range_list(
+    raw: *mut BNRange,
+    count: usize,
+) -> Vec<ops::Range<u64>> {
+    if raw.is_null() {
+        return Vec::new();
+    }
+
+    let res = slice::from_raw_parts(raw, count)
+        .iter()
+        .map(|r| r.start..r.end)
+        .collect();
+    BNFreeRelocationRanges(raw);
+
+    res
+}
+
+/// Architecture specific relocation support for a type of view
+pub trait RelocationHandler: 'static + Sync {
+    /// Adjusts the relocations a view has parsed before they are defined
+    ///
+    /// `info` holds all relocations of a view in one go, so handlers can look at
+    /// neighbouring entries (e.g. for paired HI/LO relocations).
+    fn get_relocation_info(
+        &self,
+        _view: &BinaryView,
+        _arch: &CoreArchitecture,
+        _info: &mut [RelocationInfo],
+    ) -> bool {
+        false
+    }
+
+    /// Writes the relocated value into `dest`, which holds the original bytes at the relocation
+    ///
+    /// `handle` is this handler as registered with the core; the default implementation
+    /// defers to its standard handling of `StandardRelocationType` relocations.
+    fn apply_relocation(
+        &self,
+        handle: &CoreRelocationHandler,
+        view: &BinaryView,
+        arch: &CoreArchitecture,
+        reloc: &Relocation,
+        dest: &mut [u8],
+    ) -> bool {
+        handle.default_apply_relocation(view, arch, reloc, dest)
+    }
+
+    fn get_operand_for_external_relocation(
+        &self,
+        _data: &[u8],
+        _addr: u64,
+        _il: &Lifter<CoreArchitecture>,
+        _reloc: &Relocation,
+    ) -> RelocationOperand {
+        RelocationOperand::AutocoerceExternPtr
+    }
+}
+
+/// A relocation handler known to the core
+#[derive(PartialEq, Eq, Hash)]
+pub struct CoreRelocationHandler {
+    pub(crate) handle: *mut BNRelocationHandler,
+}
+
+impl CoreRelocationHandler {
+    pub(crate) unsafe fn from_raw(handle: *mut BNRelocationHandler) -> Self {
+        debug_assert!(!handle.is_null());
+
+        Self { handle }
+    }
+
+    /// The handler `arch` uses for views of type `view_name`, e.g. `"ELF"`
+    pub fn for_arch<A, S>(arch: &A, view_name: S) -> Option<Ref<Self>>
+    where
+        A: Architecture,
+        S: BnStrCompatible,
+    {
+        let view_name = view_name.as_bytes_with_nul();
+
+        unsafe {
+            let handle = BNArchitectureGetRelocationHandler(
+                arch.as_ref().0,
+                view_name.as_ref().as_ptr() as *const _,
+            );
+
+            if handle.is_null() {
+                return None;
+            }
+
+            Some(Ref::new(Self::from_raw(handle)))
+        }
+    }
+
+    pub fn get_relocation_info(
+        &self,
+        view: &BinaryView,
+        arch: &CoreArchitecture,
+        info: &mut [RelocationInfo],
+    ) -> bool {
+        let mut raw: Vec<BNRelocationInfo> = info.iter().map(|i| i.as_raw()).collect();
+
+        let res = unsafe {
+            BNRelocationHandlerGetRelocationInfo(
+                self.handle,
+                view.handle,
+                arch.0,
+                raw.as_mut_ptr(),
+                raw.len(),
+            )
+        };
+
+        for (i, r) in info.iter_mut().zip(raw.iter()) {
+            *i = RelocationInfo::from_raw(r);
+        }
+
+        res
+    }
+
+    pub fn apply_relocation(
+        &self,
+        view: &BinaryView,
+        arch: &CoreArchitecture,
+        reloc: &Relocation,
+        dest: &mut [u8],
+    ) -> bool {
+        unsafe {
+            BNRelocationHandlerApplyRelocation(
+                self.handle,
+                view.handle,
+                arch.0,
+                reloc.handle,
+                dest.as_mut_ptr(),
+                dest.len(),
+            )
+        }
+    }
+
+    /// The core's own handling of standard relocations, regardless of what this handler overrides
+    pub fn default_apply_relocation(
+        &self,
+        view: &BinaryView,
+        arch: &CoreArchitecture,
+        reloc: &Relocation,
+        dest: &mut [u8],
+    ) -> bool {
+        unsafe {
+            BNRelocationHandlerDefaultApplyRelocation(
+                self.handle,
+                view.handle,
+                arch.0,
+                reloc.handle,
+                dest.as_mut_ptr(),
+                dest.len(),
+            )
+        }
+    }
+
+    pub fn get_operand_for_external_relocation(
+        &self,
+        data: &[u8],
+        addr: u64,
+        il: &Lifter<CoreArchitecture>,
+        reloc: &Relocation,
+    ) -> RelocationOperand {
+        let res = unsafe {
+            BNRelocationHandlerGetOperandForExternalRelocation(
+                self.handle,
+                data.as_ptr(),
+                addr,
+                data.len(),
+                il.handle,
+                reloc.handle,
+            )
+        };
+
+        RelocationOperand::from_raw(res)
+    }
+}
+
+unsafe impl Send for CoreRelocationHandler {}
+unsafe impl Sync for CoreRelocationHandler {}
+
+impl ToOwned for CoreRelocationHandler {
+    type Owned = Ref<Self>;
+
+    fn to_owned(&self) -> Self::Owned {
+        unsafe { RefCountable::inc_ref(self) }
+    }
+}
+
+unsafe impl RefCountable for CoreRelocationHandler {
+    unsafe fn inc_ref(handle: &Self) -> Ref<Self> {
+        Ref::new(Self {
+            handle: BNNewRelocationHandlerReference(handle.handle),
+        })
+    }
+
+    unsafe fn dec_ref(handle: &Self) {
+        BNFreeRelocationHandler(handle.handle);
+    }
+}
+
+/// Registers `handler` as the relocation handler `arch` uses for views of type `view_name`
+///
+/// `arch` may be a `CoreArchitecture` or a custom `Architecture` registered from Rust.
+pub fn register_relocation_handler<A, S, R>(
+    arch: &A,
+    view_name: S,
+    handler: R,
+) -> Ref<CoreRelocationHandler>
+where
+    A: Architecture,
+    S: BnStrCompatible,
+    R: RelocationHandler,
+{
+    struct RelocationHandlerContext<R>
+    where
+        R: RelocationHandler,
+    {
+        raw_handle: *mut BNRelocationHandler,
+        handler: R,
+    }
+
+    extern "C" fn cb_free<R>(ctxt: *mut c_void)
+    where
+        R: RelocationHandler,
+    {
+        ffi_wrap!("RelocationHandler::free", unsafe {
+            let _ctxt = Box::from_raw(ctxt as *mut RelocationHandlerContext<R>);
+        })
+    }
+
+    extern "C" fn cb_get_relocation_info<R>(
+        ctxt: *mut c_void,
+        view: *mut BNBinaryView,
+        arch: *mut BNArchitecture,
+        result: *mut BNRelocationInfo,
+        count: usize,
+    ) -> bool
+    where
+        R: RelocationHandler,
+    {
+        ffi_wrap!("RelocationHandler::get_relocation_info", unsafe {
+            let ctxt = &*(ctxt as *mut RelocationHandlerContext<R>);
+            let view = BinaryView { handle: view };
+            let arch = CoreArchitecture::from_raw(arch);
+
+            if result.is_null() {
+                return false;
+            }
+
+            let raw = slice::from_raw_parts_mut(result, count);
+            let mut info: Vec<RelocationInfo> = raw.iter().map(RelocationInfo::from_raw).collect();
+
+            let res = ctxt.handler.get_relocation_info(&view, &arch, &mut info);
+
+            for (r, i) in raw.iter_mut().zip(info.iter()) {
+                i.write_raw(r);
+            }
+
+            res
+        })
+    }
+
+    extern "C" fn cb_apply_relocation<R>(
+        ctxt: *mut c_void,
+        view: *mut BNBinaryView,
+        arch: *mut BNArchitecture,
+        reloc: *mut BNRelocation,
+        dest: *mut u8,
+        len: usize,
+    ) -> bool
+    where
+        R: RelocationHandler,
+    {
+        ffi_wrap!("RelocationHandler::apply_relocation", unsafe {
+            let ctxt = &*(ctxt as *mut RelocationHandlerContext<R>);
+            let handle = CoreRelocationHandler::from_raw(ctxt.raw_handle);
+            let view = BinaryView { handle: view };
+            let arch = CoreArchitecture::from_raw(arch);
+            let reloc = Relocation::from_raw(reloc);
+
+            if dest.is_null() {
+                return false;
+            }
+
+            let dest = slice::from_raw_parts_mut(dest, len);
+
+            ctxt.handler
+                .apply_relocation(&handle, &view, &arch, &reloc, dest)
+        })
+    }
+
+    extern "C" fn cb_get_operand_for_external_relocation<R>(
+        ctxt: *mut c_void,
+        data: *const u8,
+        addr: u64,
+        len: usize,
+        il: *mut BNLowLevelILFunction,
+        reloc: *mut BNRelocation,
+    ) -> usize
+    where
+        R: RelocationHandler,
+    {
+        ffi_wrap!(
+            "RelocationHandler::get_operand_for_external_relocation",
+            unsafe {
+                let ctxt = &*(ctxt as *mut RelocationHandlerContext<R>);
+                let reloc = Relocation::from_raw(reloc);
+
+                let arch = match reloc.arch() {
+                    Some(arch) => arch,
+                    None => return BN_INVALID_OPERAND,
+                };
+
+                let data = if data.is_null() {
+                    &[]
+                } else {
+                    slice::from_raw_parts(data, len)
+                };
+
+                let il = Lifter::from_raw(arch, il);
+
+                ctxt.handler
+                    .get_operand_for_external_relocation(data, addr, &il, &reloc)
+                    .into_raw()
+            }
+        )
+    }
+
+    let view_name = view_name.as_bytes_with_nul();
+
+    let raw = Box::into_raw(Box::new(RelocationHandlerContext {
+        raw_handle: ptr::null_mut(),
+        handler,
+    }));
+
+    let mut custom_handler = BNCustomRelocationHandler {
+        context: raw as *mut _,
+        freeObject: Some(cb_free::<R>),
+        getRelocationInfo: Some(cb_get_relocation_info::<R>),
+        applyRelocation: Some(cb_apply_relocation::<R>),
+        getOperandForExternalRelocation: Some(cb_get_operand_for_external_relocation::<R>),
+    };
+
+    unsafe {
+        let handle = BNCreateRelocationHandler(&mut custom_handler);
+
+        assert!(!handle.is_null());
+
+        (*raw).raw_handle = handle;
+
+        BNArchitectureRegisterRelocationHandler(
+            arch.as_ref().0,
+            view_name.as_ref().as_ptr() as *const _,
+            handle,
+        );
+
+        Ref::new(CoreRelocationHandler::from_raw(handle))
+    }
+}
@@ -0,0 +1,299 @@
+// Copyright 2021 Vector 35 Inc.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use binaryninjacore_sys::*;
+
+use std::fmt;
+
+use crate::architecture::CoreArchitecture;
+use crate::binaryview::BinaryView;
+use crate::function::Function;
+
+use crate::rc::*;
+use crate::string::*;
+
+pub type TagTypeType = BNTagTypeType;
+pub type TagReferenceType = BNTagReferenceType;
+
+#[derive(PartialEq, Eq, Hash)]
+pub struct TagType {
+    pub(crate) handle: *mut BNTagType,
+}
+
+impl TagType {
+    pub(crate) unsafe fn from_raw(handle: *mut BNTagType) -> Self {
+        debug_assert!(!handle.is_null());
+
+        Self { handle }
+    }
+
+    /// Creates a tag type that is not yet registered with `view`
+    ///
+    /// Use `BinaryViewExt::create_tag_type` to create and register one in one go.
+    pub fn create<N: BnStrCompatible, I: BnStrCompatible>(
+        view: &BinaryView,
+        name: N,
+        icon: I,
+    ) -> Ref<Self> {
+        let tag_type = unsafe { Ref::new(Self::from_raw(BNCreateTagType(view.handle))) };
+
+        tag_type.set_name(name);
+        tag_type.set_icon(icon);
+
+        tag_type
+    }
+
+    pub fn id(&self) -> BnString {
+        unsafe { BnString::from_raw(BNTagTypeGetId(self.handle)) }
+    }
+
+    pub fn name(&self) -> BnString {
+        unsafe { BnString::from_raw(BNTagTypeGetName(self.handle)) }
+    }
+
+    pub fn set_name<S: BnStrCompatible>(&self, name: S) {
+        let name = name.as_bytes_with_nul();
+
+        unsafe {
+            BNTagTypeSetName(self.handle, name.as_ref().as_ptr() as *mut _);
+        }
+    }
+
+    pub fn icon(&self) -> BnString {
+        unsafe { BnString::from_raw(BNTagTypeGetIcon(self.handle)) }
+    }
+
+    pub fn set_icon<S: BnStrCompatible>(&self, icon: S) {
+        let icon = icon.as_bytes_with_nul();
+
+        unsafe {
+            BNTagTypeSetIcon(self.handle, icon.as_ref().as_ptr() as *mut _);
+        }
+    }
+
+    pub fn visible(&self) -> bool {
+        unsafe { BNTagTypeGetVisible(self.handle) }
+    }
+
+    pub fn set_visible(&self, visible: bool) {
+        unsafe { BNTagTypeSetVisible(self.handle, visible) }
+    }
+
+    pub fn t(&self) -> TagTypeType {
+        unsafe { BNTagTypeGetType(self.handle) }
+    }
+
+    pub fn set_type(&self, t: TagTypeType) {
+        unsafe { BNTagTypeSetType(self.handle, t) }
+    }
+
+    pub fn view(&self) -> Ref<BinaryView> {
+        unsafe { BinaryView::from_raw(BNTagTypeGetView(self.handle)) }
+    }
+}
+
+unsafe impl Send for TagType {}
+unsafe impl Sync for TagType {}
+
+impl fmt::Debug for TagType {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "<tag type {} '{}'>", self.icon(), self.name())
+    }
+}
+
+impl ToOwned for TagType {
+    type Owned = Ref<Self>;
+
+    fn to_owned(&self) -> Self::Owned {
+        unsafe { RefCountable::inc_ref(self) }
+    }
+}
+
+unsafe impl RefCountable for TagType {
+    unsafe fn inc_ref(handle: &Self) -> Ref<Self> {
+        Ref::new(Self {
+            handle: BNNewTagTypeReference(handle.handle),
+        })
+    }
+
+    unsafe fn dec_ref(handle: &Self) {
+        BNFreeTagType(handle.handle);
+    }
+}
+
+unsafe impl CoreOwnedArrayProvider for TagType {
+    type Raw = *mut BNTagType;
+    type Context = ();
+
+    unsafe fn free(raw: *mut Self::Raw, count: usize, _context: &Self::Context) {
+        BNFreeTagTypeList(raw, count);
+    }
+}
+
+unsafe impl<'a> CoreOwnedArrayWrapper<'a> for TagType {
+    type Wrapped = Guard<'a, TagType>;
+
+    unsafe fn wrap_raw(raw: &'a Self::Raw, context: &'a Self::Context) -> Self::Wrapped {
+        Guard::new(TagType::from_raw(*raw), context)
+    }
+}
+
+#[derive(PartialEq, Eq, Hash)]
+pub struct Tag {
+    pub(crate) handle: *mut BNTag,
+}
+
+impl Tag {
+    pub(crate) unsafe fn from_raw(handle: *mut BNTag) -> Self {
+        debug_assert!(!handle.is_null());
+
+        Self { handle }
+    }
+
+    /// Creates a tag that is not yet registered with any view
+    ///
+    /// Use `BinaryViewExt::create_tag` to create and register one in one go.
+    pub fn new<S: BnStrCompatible>(t: &TagType, data: S) -> Ref<Self> {
+        let data = data.as_bytes_with_nul();
+
+        unsafe {
+            let tag = BNCreateTag(t.handle, data.as_ref().as_ptr() as *mut _);
+            Ref::new(Self::from_raw(tag))
+        }
+    }
+
+    pub fn id(&self) -> BnString {
+        unsafe { BnString::from_raw(BNTagGetId(self.handle)) }
+    }
+
+    pub fn data(&self) -> BnString {
+        unsafe { BnString::from_raw(BNTagGetData(self.handle)) }
+    }
+
+    pub fn set_data<S: BnStrCompatible>(&self, data: S) {
+        let data = data.as_bytes_with_nul();
+
+        unsafe {
+            BNTagSetData(self.handle, data.as_ref().as_ptr() as *mut _);
+        }
+    }
+
+    pub fn t(&self) -> Ref<TagType> {
+        unsafe { Ref::new(TagType::from_raw(BNTagGetType(self.handle))) }
+    }
+}
+
+unsafe impl Send for Tag {}
+unsafe impl Sync for Tag {}
+
+impl fmt::Debug for Tag {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "<tag {:?}: '{}'>", *self.t(), self.data())
+    }
+}
+
+impl ToOwned for Tag {
+    type Owned = Ref<Self>;
+
+    fn to_owned(&self) -> Self::Owned {
+        unsafe { RefCountable::inc_ref(self) }
+    }
+}
+
+unsafe impl RefCountable for Tag {
+    unsafe fn inc_ref(handle: &Self) -> Ref<Self> {
+        Ref::new(Self {
+            handle: BNNewTagReference(handle.handle),
+        })
+    }
+
+    unsafe fn dec_ref(handle: &Self) {
+        BNFreeTag(handle.handle);
+    }
+}
+
+unsafe impl CoreOwnedArrayProvider for Tag {
+    type Raw = *mut BNTag;
+    type Context = ();
+
+    unsafe fn free(raw: *mut Self::Raw, count: usize, _context: &Self::Context) {
+        BNFreeTagList(raw, count);
+    }
+}
+
+unsafe impl<'a> CoreOwnedArrayWrapper<'a> for Tag {
+    type Wrapped = Guard<'a, Tag>;
+
+    unsafe fn wrap_raw(raw: &'a Self::Raw, context: &'a Self::Context) -> Self::Wrapped {
+        Guard::new(Tag::from_raw(*raw), context)
+    }
+}
+
+/// Where a tag has been placed
+pub struct TagReference {
+    pub ref_type: TagReferenceType,
+    pub auto_defined: bool,
+    pub tag: Ref<Tag>,
+    pub arch: Option<CoreArchitecture>,
+    pub function: Option<Ref<Function>>,
+    pub address: u64,
+}
+
+impl TagReference {
+    pub(crate) unsafe fn from_raw(raw: &BNTagReference) -> Self {
+        Self {
+            ref_type: raw.refType,
+            auto_defined: raw.autoDefined,
+            tag: Ref::new(Tag::from_raw(BNNewTagReference(raw.tag))),
+            arch: if raw.arch.is_null() {
+                None
+            } else {
+                Some(CoreArchitecture::from_raw(raw.arch))
+            },
+            function: if raw.func.is_null() {
+                None
+            } else {
+                Some(Function::from_raw(BNNewFunctionReference(raw.func)))
+            },
+            address: raw.addr,
+        }
+    }
+}
+
+impl fmt::Debug for TagReference {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "<tag ref {:?} @ {:x}: {:?}>",
+            self.ref_type, self.address, *self.tag
+        )
+    }
+}
+
+unsafe impl CoreOwnedArrayProvider for TagReference {
+    type Raw = BNTagReference;
+    type Context = ();
+
+    unsafe fn free(raw: *mut Self::Raw, count: usize, _context: &Self::Context) {
+        BNFreeTagReferences(raw, count);
+    }
+}
+
+unsafe impl<'a> CoreOwnedArrayWrapper<'a> for TagReference {
+    type Wrapped = TagReference;
+
+    unsafe fn wrap_raw(raw: &'a Self::Raw, _context: &'a Self::Context) -> Self::Wrapped {
+        TagReference::from_raw(raw)
+    }
+}
@@ -0,0 +1,378 @@
+// Copyright 2021 Vector 35 Inc.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Encoders, decoders, compressors and hashes known to the core, and registration of new ones.
+
+use binaryninjacore_sys::*;
+
+use std::collections::HashMap;
+use std::ffi::CStr;
+use std::os::raw::c_void;
+use std::ptr;
+use std::slice;
+
+use crate::databuffer::DataBuffer;
+
+use crate::rc::*;
+use crate::string::*;
+
+pub type TransformType = BNTransformType;
+
+/// A parameter a transform takes, such as the key for an XOR
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct TransformParameter {
+    pub name: String,
+    pub long_name: String,
+    /// Required length of the value in bytes, or 0 if any length is accepted
+    pub fixed_length: usize,
+}
+
+impl TransformParameter {
+    pub fn new<S: Into<String>>(name: S, long_name: S, fixed_length: usize) -> Self {
+        Self {
+            name: name.into(),
+            long_name: long_name.into(),
+            fixed_length,
+        }
+    }
+
+    unsafe fn from_raw(raw: &BNTransformParameterInfo) -> Self {
+        Self {
+            name: raw_to_string(raw.name).unwrap_or_default(),
+            long_name: raw_to_string(raw.longName).unwrap_or_default(),
+            fixed_length: raw.fixedLength,
+        }
+    }
+}
+
+#[derive(Copy, Clone, PartialEq, Eq, Hash)]
+pub struct Transform {
+    pub(crate) handle: *mut BNTransform,
+}
+
+unsafe impl Send for Transform {}
+unsafe impl Sync for Transform {}
+
+impl Transform {
+    pub(crate) unsafe fn from_raw(handle: *mut BNTransform) -> Self {
+        debug_assert!(!handle.is_null());
+
+        Self { handle }
+    }
+
+    pub fn by_name<S: BnStrCompatible>(name: S) -> Option<Transform> {
+        let name = name.as_bytes_with_nul();
+
+        unsafe {
+            let handle = BNGetTransformByName(name.as_ref().as_ptr() as *const _);
+
+            if handle.is_null() {
+                return None;
+            }
+
+            Some(Transform::from_raw(handle))
+        }
+    }
+
+    pub fn list() -> Array<Transform> {
+        unsafe {
+            let mut count = 0;
+            let handles = BNGetTransformTypeList(&mut count);
+
+            Array::new(handles, count, ())
+        }
+    }
+
+    pub fn transform_type(&self) -> TransformType {
+        unsafe { BNGetTransformType(self.handle) }
+    }
+
+    pub fn name(&self) -> BnString {
+        unsafe { BnString::from_raw(BNGetTransformName(self.handle)) }
+    }
+
+    pub fn long_name(&self) -> BnString {
+        unsafe { BnString::from_raw(BNGetTransformLongName(self.handle)) }
+    }
+
+    pub fn group(&self) -> BnString {
+        unsafe { BnString::from_raw(BNGetTransformGroup(self.handle)) }
+    }
+
+    pub fn parameters(&self) -> Vec<TransformParameter> {
+        unsafe {
+            let mut count = 0;
+            let params = BNGetTransformParameterList(self.handle, &mut count);
+
+            if params.is_null() {
+                return Vec::new();
+            }
+
+            let res = slice::from_raw_parts(params, count)
+                .iter()
+                .map(|p| TransformParameter::from_raw(p))
+                .collect();
+
+            BNFreeTransformParameterList(params, count);
+
+            res
+        }
+    }
+
+    pub fn decode(
+        &self,
+        input: &DataBuffer,
+        params: &HashMap<String, DataBuffer>,
+    ) -> Result<DataBuffer, ()> {
+        self.apply(input, params, BNDecode)
+    }
+
+    pub fn encode(
+        &self,
+        input: &DataBuffer,
+        params: &HashMap<String, DataBuffer>,
+    ) -> Result<DataBuffer, ()> {
+        self.apply(input, params, BNEncode)
+    }
+
+    fn apply(
+        &self,
+        input: &DataBuffer,
+        params: &HashMap<String, DataBuffer>,
+        f: unsafe extern "C" fn(
+            *mut BNTransform,
+            *mut BNDataBuffer,
+            *mut BNDataBuffer,
+            *mut BNTransformParameter,
+            usize,
+        ) -> bool,
+    ) -> Result<DataBuffer, ()> {
+        let names: Vec<_> = params
+            .keys()
+            .map(|name| name.as_str().as_bytes_with_nul())
+            .collect();
+        let mut raw_params: Vec<BNTransformParameter> = names
+            .iter()
+            .zip(params.values())
+            .map(|(name, value)| BNTransformParameter {
+                name: name.as_ptr() as *const _,
+                value: value.as_raw(),
+            })
+            .collect();
+
+        let output = DataBuffer::new(&[])?;
+
+        let res = unsafe {
+            f(
+                self.handle,
+                input.as_raw(),
+                output.as_raw(),
+                raw_params.as_mut_ptr(),
+                raw_params.len(),
+            )
+        };
+
+        match res {
+            true => Ok(output),
+            false => Err(()),
+        }
+    }
+}
+
+unsafe impl CoreOwnedArrayProvider for Transform {
+    type Raw = *mut BNTransform;
+    type Context = ();
+
+    unsafe fn free(raw: *mut Self::Raw, _count: usize, _context: &Self::Context) {
+        BNFreeTransformTypeList(raw);
+    }
+}
+
+unsafe impl<'a> CoreOwnedArrayWrapper<'a> for Transform {
+    type Wrapped = Transform;
+
+    unsafe fn wrap_raw(raw: &'a Self::Raw, _context: &'a Self::Context) -> Self::Wrapped {
+        Transform::from_raw(*raw)
+    }
+}
+
+pub trait CustomTransform: 'static + Sync {
+    fn parameters(&self) -> Vec<TransformParameter> {
+        Vec::new()
+    }
+
+    fn decode(&self, input: &[u8], params: &HashMap<String, Vec<u8>>) -> Result<Vec<u8>, ()>;
+
+    /// Transforms that can only be applied in one direction can leave this unimplemented
+    fn encode(&self, _input: &[u8], _params: &HashMap<String, Vec<u8>>) -> Result<Vec<u8>, ()> {
+        Err(())
+    }
+}
+
+pub fn register<S, T>(t: TransformType, name: S, long_name: S, group: S, transform: T) -> Transform
+where
+    S: BnStrCompatible,
+    T: CustomTransform,
+{
+    extern "C" fn cb_get_parameters<T>(
+        ctxt: *mut c_void,
+        count: *mut usize,
+    ) -> *mut BNTransformParameterInfo
+    where
+        T: CustomTransform,
+    {
+        ffi_wrap!("CustomTransform::parameters", unsafe {
+            let transform = &*(ctxt as *const T);
+
+            let params: Box<[BNTransformParameterInfo]> = transform
+                .parameters()
+                .into_iter()
+                .map(|p| BNTransformParameterInfo {
+                    name: BnString::new(p.name).into_raw(),
+                    longName: BnString::new(p.long_name).into_raw(),
+                    fixedLength: p.fixed_length,
+                })
+                .collect();
+
+            *count = params.len();
+
+            if params.is_empty() {
+                return ptr::null_mut();
+            }
+
+            Box::into_raw(params) as *mut BNTransformParameterInfo
+        })
+    }
+
+    extern "C" fn cb_free_parameters(params: *mut BNTransformParameterInfo, count: usize) {
+        ffi_wrap!("CustomTransform::free_parameters", unsafe {
+            if params.is_null() {
+                return;
+            }
+
+            let params = Box::from_raw(ptr::slice_from_raw_parts_mut(params, count));
+
+            for p in params.iter() {
+                drop(BnString::from_raw(p.name));
+                drop(BnString::from_raw(p.longName));
+            }
+        })
+    }
+
+    unsafe fn params_from_raw(
+        params: *mut BNTransformParameter,
+        count: usize,
+    ) -> HashMap<String, Vec<u8>> {
+        if params.is_null() {
+            return HashMap::new();
+        }
+
+        slice::from_raw_parts(params, count)
+            .iter()
+            .map(|p| {
+                let name = CStr::from_ptr(p.name).to_string_lossy().into_owned();
+                let value = buffer_contents(p.value).to_vec();
+
+                (name, value)
+            })
+            .collect()
+    }
+
+    unsafe fn buffer_contents<'a>(buffer: *mut BNDataBuffer) -> &'a [u8] {
+        let len = BNGetDataBufferLength(buffer);
+        let contents = BNGetDataBufferContents(buffer);
+
+        if contents.is_null() || len == 0 {
+            &[]
+        } else {
+            slice::from_raw_parts(contents as *const u8, len)
+        }
+    }
+
+    extern "C" fn cb_decode<T>(
+        ctxt: *mut c_void,
+        input: *mut BNDataBuffer,
+        output: *mut BNDataBuffer,
+        params: *mut BNTransformParameter,
+        param_count: usize,
+    ) -> bool
+    where
+        T: CustomTransform,
+    {
+        ffi_wrap!("CustomTransform::decode", unsafe {
+            let transform = &*(ctxt as *const T);
+            let params = params_from_raw(params, param_count);
+
+            match transform.decode(buffer_contents(input), &params) {
+                Ok(mut data) => {
+                    BNSetDataBufferContents(output, data.as_mut_ptr() as *mut _, data.len());
+                    true
+                }
+                Err(_) => false,
+            }
+        })
+    }
+
+    extern "C" fn cb_encode<T>(
+        ctxt: *mut c_void,
+        input: *mut BNDataBuffer,
+        output: *mut BNDataBuffer,
+        params: *mut BNTransformParameter,
+        param_count: usize,
+    ) -> bool
+    where
+        T: CustomTransform,
+    {
+        ffi_wrap!("CustomTransform::encode", unsafe {
+            let transform = &*(ctxt as *const T);
+            let params = params_from_raw(params, param_count);
+
+            match transform.encode(buffer_contents(input), &params) {
+                Ok(mut data) => {
+                    BNSetDataBufferContents(output, data.as_mut_ptr() as *mut _, data.len());
+                    true
+                }
+                Err(_) => false,
+            }
+        })
+    }
+
+    let name = name.as_bytes_with_nul();
+    let long_name = long_name.as_bytes_with_nul();
+    let group = group.as_bytes_with_nul();
+
+    let ctxt = Box::into_raw(Box::new(transform));
+
+    // the core holds on to this for as long as the transform is registered,
+    // which is the rest of the process
+    let custom = Box::leak(Box::new(BNCustomTransform {
+        context: ctxt as *mut _,
+        getParameters: Some(cb_get_parameters::<T>),
+        freeParameters: Some(cb_free_parameters),
+        decode: Some(cb_decode::<T>),
+        encode: Some(cb_encode::<T>),
+    }));
+
+    unsafe {
+        let handle = BNRegisterTransformType(
+            t,
+            name.as_ref().as_ptr() as *const _,
+            long_name.as_ref().as_ptr() as *const _,
+            group.as_ref().as_ptr() as *const _,
+            custom,
+        );
+
+        Transform::from_raw(handle)
+    }
+}
@@ -0,0 +1,319 @@
+// Copyright 2021 Vector 35 Inc.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Collections of named types and objects (function and data declarations) that can be
+//! shared between views, such as the declarations exported by a system library.
+
+use binaryninjacore_sys::*;
+
+use std::fmt;
+
+use crate::architecture::CoreArchitecture;
+use crate::metadata::Metadata;
+use crate::platform::Platform;
+use crate::types::{QualifiedName, QualifiedNameAndType, Type};
+
+use crate::rc::*;
+use crate::string::*;
+
+#[derive(PartialEq, Eq, Hash)]
+pub struct TypeLibrary {
+    pub(crate) handle: *mut BNTypeLibrary,
+}
+
+impl TypeLibrary {
+    pub(crate) unsafe fn from_raw(handle: *mut BNTypeLibrary) -> Self {
+        debug_assert!(!handle.is_null());
+
+        Self { handle }
+    }
+
+    pub(crate) unsafe fn ref_from_raw(handle: *mut BNTypeLibrary) -> Option<Ref<Self>> {
+        if handle.is_null() {
+            return None;
+        }
+
+        Some(Ref::new(Self::from_raw(handle)))
+    }
+
+    /// Creates an empty type library
+    ///
+    /// The library must be `finalize`d before it is written out or attached to a view.
+    pub fn new<S: BnStrCompatible>(arch: &CoreArchitecture, name: S) -> Ref<Self> {
+        let name = name.as_bytes_with_nul();
+
+        unsafe {
+            let handle = BNNewTypeLibrary(arch.0, name.as_ref().as_ptr() as *const _);
+            Ref::new(Self::from_raw(handle))
+        }
+    }
+
+    pub fn load_from_file<S: BnStrCompatible>(path: S) -> Option<Ref<Self>> {
+        let path = path.as_bytes_with_nul();
+
+        unsafe { Self::ref_from_raw(BNLoadTypeLibraryFromFile(path.as_ref().as_ptr() as *const _)) }
+    }
+
+    pub fn by_name<S: BnStrCompatible>(arch: &CoreArchitecture, name: S) -> Option<Ref<Self>> {
+        let name = name.as_bytes_with_nul();
+
+        unsafe {
+            Self::ref_from_raw(BNLookupTypeLibraryByName(
+                arch.0,
+                name.as_ref().as_ptr() as *const _,
+            ))
+        }
+    }
+
+    pub fn by_guid<S: BnStrCompatible>(arch: &CoreArchitecture, guid: S) -> Option<Ref<Self>> {
+        let guid = guid.as_bytes_with_nul();
+
+        unsafe {
+            Self::ref_from_raw(BNLookupTypeLibraryByGuid(
+                arch.0,
+                guid.as_ref().as_ptr() as *const _,
+            ))
+        }
+    }
+
+    pub fn list_by_arch(arch: &CoreArchitecture) -> Array<TypeLibrary> {
+        unsafe {
+            let mut count = 0;
+            let handles = BNGetArchitectureTypeLibraries(arch.0, &mut count);
+
+            Array::new(handles, count, ())
+        }
+    }
+
+    pub fn duplicate(&self) -> Ref<Self> {
+        unsafe { Ref::new(Self::from_raw(BNDuplicateTypeLibrary(self.handle))) }
+    }
+
+    /// Makes the library available for lookup and ready to be written out
+    pub fn finalize(&self) {
+        unsafe { BNFinalizeTypeLibrary(self.handle) }
+    }
+
+    pub fn write_to_file<S: BnStrCompatible>(&self, path: S) {
+        let path = path.as_bytes_with_nul();
+
+        unsafe { BNWriteTypeLibraryToFile(self.handle, path.as_ref().as_ptr() as *const _) }
+    }
+
+    pub fn arch(&self) -> CoreArchitecture {
+        unsafe { CoreArchitecture::from_raw(BNGetTypeLibraryArchitecture(self.handle)) }
+    }
+
+    pub fn name(&self) -> BnString {
+        unsafe { BnString::from_raw(BNGetTypeLibraryName(self.handle)) }
+    }
+
+    pub fn set_name<S: BnStrCompatible>(&self, name: S) {
+        let name = name.as_bytes_with_nul();
+
+        unsafe { BNSetTypeLibraryName(self.handle, name.as_ref().as_ptr() as *const _) }
+    }
+
+    pub fn alternate_names(&self) -> Array<BnString> {
+        unsafe {
+            let mut count = 0;
+            let names = BNGetTypeLibraryAlternateNames(self.handle, &mut count);
+
+            Array::new(names, count, ())
+        }
+    }
+
+    pub fn add_alternate_name<S: BnStrCompatible>(&self, name: S) {
+        let name = name.as_bytes_with_nul();
+
+        unsafe { BNAddTypeLibraryAlternateName(self.handle, name.as_ref().as_ptr() as *const _) }
+    }
+
+    /// Name used to match this library against the imports of a view, such as `libc.so.6`
+    pub fn dependency_name(&self) -> BnString {
+        unsafe { BnString::from_raw(BNGetTypeLibraryDependencyName(self.handle)) }
+    }
+
+    pub fn set_dependency_name<S: BnStrCompatible>(&self, name: S) {
+        let name = name.as_bytes_with_nul();
+
+        unsafe { BNSetTypeLibraryDependencyName(self.handle, name.as_ref().as_ptr() as *const _) }
+    }
+
+    pub fn guid(&self) -> BnString {
+        unsafe { BnString::from_raw(BNGetTypeLibraryGuid(self.handle)) }
+    }
+
+    pub fn set_guid<S: BnStrCompatible>(&self, guid: S) {
+        let guid = guid.as_bytes_with_nul();
+
+        unsafe { BNSetTypeLibraryGuid(self.handle, guid.as_ref().as_ptr() as *const _) }
+    }
+
+    /// Names of the platforms this library applies to
+    pub fn platform_names(&self) -> Array<BnString> {
+        unsafe {
+            let mut count = 0;
+            let names = BNGetTypeLibraryPlatforms(self.handle, &mut count);
+
+            Array::new(names, count, ())
+        }
+    }
+
+    pub fn add_platform(&self, platform: &Platform) {
+        unsafe { BNAddTypeLibraryPlatform(self.handle, platform.handle) }
+    }
+
+    pub fn clear_platforms(&self) {
+        unsafe { BNClearTypeLibraryPlatforms(self.handle) }
+    }
+
+    pub fn store_metadata<S, V>(&self, key: S, value: V)
+    where
+        S: BnStrCompatible,
+        V: Into<Ref<Metadata>>,
+    {
+        let key = key.as_bytes_with_nul();
+        let value: Ref<Metadata> = value.into();
+
+        unsafe {
+            BNTypeLibraryStoreMetadata(self.handle, key.as_ref().as_ptr() as *const _, value.handle)
+        }
+    }
+
+    pub fn query_metadata<S: BnStrCompatible>(&self, key: S) -> Option<Ref<Metadata>> {
+        let key = key.as_bytes_with_nul();
+
+        unsafe {
+            let value = BNTypeLibraryQueryMetadata(self.handle, key.as_ref().as_ptr() as *const _);
+
+            if value.is_null() {
+                return None;
+            }
+
+            Some(Ref::new(Metadata::from_raw(value)))
+        }
+    }
+
+    pub fn remove_metadata<S: BnStrCompatible>(&self, key: S) {
+        let key = key.as_bytes_with_nul();
+
+        unsafe { BNTypeLibraryRemoveMetadata(self.handle, key.as_ref().as_ptr() as *const _) }
+    }
+
+    /// Adds a function or data variable declaration
+    pub fn add_named_object<N: Into<QualifiedName>>(&self, name: N, t: &Type) {
+        let mut name = name.into();
+
+        unsafe { BNAddTypeLibraryNamedObject(self.handle, &mut name.0, t.handle) }
+    }
+
+    pub fn add_named_type<N: Into<QualifiedName>>(&self, name: N, t: &Type) {
+        let mut name = name.into();
+
+        unsafe { BNAddTypeLibraryNamedType(self.handle, &mut name.0, t.handle) }
+    }
+
+    pub fn get_named_object<N: Into<QualifiedName>>(&self, name: N) -> Option<Ref<Type>> {
+        let mut name = name.into();
+
+        unsafe {
+            let t = BNGetTypeLibraryNamedObject(self.handle, &mut name.0);
+
+            if t.is_null() {
+                return None;
+            }
+
+            Some(Type::ref_from_raw(t))
+        }
+    }
+
+    pub fn get_named_type<N: Into<QualifiedName>>(&self, name: N) -> Option<Ref<Type>> {
+        let mut name = name.into();
+
+        unsafe {
+            let t = BNGetTypeLibraryNamedType(self.handle, &mut name.0);
+
+            if t.is_null() {
+                return None;
+            }
+
+            Some(Type::ref_from_raw(t))
+        }
+    }
+
+    pub fn named_objects(&self) -> Array<QualifiedNameAndType> {
+        unsafe {
+            let mut count = 0;
+            let objects = BNGetTypeLibraryNamedObjects(self.handle, &mut count);
+
+            Array::new(objects, count, ())
+        }
+    }
+
+    pub fn named_types(&self) -> Array<QualifiedNameAndType> {
+        unsafe {
+            let mut count = 0;
+            let types = BNGetTypeLibraryNamedTypes(self.handle, &mut count);
+
+            Array::new(types, count, ())
+        }
+    }
+}
+
+unsafe impl Send for TypeLibrary {}
+unsafe impl Sync for TypeLibrary {}
+
+impl fmt::Debug for TypeLibrary {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "<type library '{}'>", self.name())
+    }
+}
+
+impl ToOwned for TypeLibrary {
+    type Owned = Ref<Self>;
+
+    fn to_owned(&self) -> Self::Owned {
+        unsafe { RefCountable::inc_ref(self) }
+    }
+}
+
+unsafe impl RefCountable for TypeLibrary {
+    unsafe fn inc_ref(handle: &Self) -> Ref<Self> {
+        Ref::new(Self {
+            handle: BNNewTypeLibraryReference(handle.handle),
+        })
+    }
+
+    unsafe fn dec_ref(handle: &Self) {
+        BNFreeTypeLibrary(handle.handle);
+    }
+}
+
+unsafe impl CoreOwnedArrayProvider for TypeLibrary {
+    type Raw = *mut BNTypeLibrary;
+    type Context = ();
+
+    unsafe fn free(raw: *mut Self::Raw, count: usize, _context: &Self::Context) {
+        BNFreeTypeLibraryList(raw, count);
+    }
+}
+
+unsafe impl<'a> CoreOwnedArrayWrapper<'a> for TypeLibrary {
+    type Wrapped = Guard<'a, TypeLibrary>;
+
+    unsafe fn wrap_raw(raw: &'a Self::Raw, context: &'a Self::Context) -> Self::Wrapped {
+        Guard::new(TypeLibrary::from_raw(*raw), context)
+    }
+}
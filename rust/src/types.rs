@@ -17,7 +17,7 @@
 // TODO : Test the get_enumeration and get_structure methods
 
 use binaryninjacore_sys::*;
-use std::{fmt, mem, ptr, result, slice};
+use std::{collections::HashSet, fmt, mem, ptr, result, slice};
 
 use crate::architecture::{Architecture, CoreArchitecture};
 use crate::callingconvention::CallingConvention;
@@ -30,6 +30,7 @@ pub type Result<R> = result::Result<R, ()>;
 pub type ReferenceType = BNReferenceType;
 pub type TypeClass = BNTypeClass;
 pub type NamedTypeReferenceClass = BNNamedTypeReferenceClass;
+pub type RegisterValueType = BNRegisterValueType;
 
 ////////////////
 // Confidence
@@ -984,6 +985,7 @@ impl<'a, S: BnStrCompatible> FunctionParameter<S> {
 //////////////
 // Variable
 
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
 pub struct Variable {
     pub t: BNVariableSourceType,
     pub index: u32,
@@ -995,13 +997,17 @@ impl Variable {
         Self { t, index, storage }
     }
 
-    // pub(crate) unsafe fn from_raw(var: *mut BNVariable) -> Self {
-    //     Self {
-    //         t: (*var).type_,
-    //         index: (*var).index,
-    //         storage: (*var).storage,
-    //     }
-    // }
+    pub(crate) fn from_raw(var: BNVariable) -> Self {
+        Self {
+            t: var.type_,
+            index: var.index,
+            storage: var.storage,
+        }
+    }
+
+    pub(crate) fn from_identifier(identifier: u64) -> Self {
+        unsafe { Self::from_raw(BNFromVariableIdentifier(identifier)) }
+    }
 
     pub(crate) fn into_raw(&self) -> BNVariable {
         BNVariable {
@@ -1012,6 +1018,220 @@ impl Variable {
     }
 }
 
+////////////////////////
+// NamedTypedVariable
+
+pub struct NamedTypedVariable {
+    pub var: Variable,
+    pub t: Conf<Ref<Type>>,
+    pub name: String,
+    pub auto_defined: bool,
+}
+
+impl NamedTypedVariable {
+    pub(crate) fn from_raw(var: &BNVariableNameAndType) -> Self {
+        Self {
+            var: Variable::from_raw(var.var),
+            t: Conf::new(
+                unsafe { Type::ref_from_raw(BNNewTypeReference(var.type_)) },
+                var.typeConfidence,
+            ),
+            name: raw_to_string(var.name).unwrap_or_default(),
+            auto_defined: var.autoDefined,
+        }
+    }
+
+    pub fn type_with_confidence(&self) -> Conf<Ref<Type>> {
+        self.t.clone()
+    }
+}
+
+unsafe impl CoreOwnedArrayProvider for NamedTypedVariable {
+    type Raw = BNVariableNameAndType;
+    type Context = ();
+
+    unsafe fn free(raw: *mut Self::Raw, count: usize, _context: &Self::Context) {
+        BNFreeVariableNameAndTypeList(raw, count);
+    }
+}
+
+unsafe impl<'a> CoreOwnedArrayWrapper<'a> for NamedTypedVariable {
+    type Wrapped = NamedTypedVariable;
+
+    unsafe fn wrap_raw(raw: &'a Self::Raw, _context: &'a Self::Context) -> Self::Wrapped {
+        NamedTypedVariable::from_raw(raw)
+    }
+}
+
+///////////////////
+// RegisterValue
+
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+pub struct RegisterValue {
+    pub state: RegisterValueType,
+    pub value: i64,
+    pub offset: i64,
+}
+
+impl RegisterValue {
+    pub fn new(state: RegisterValueType, value: i64, offset: i64) -> Self {
+        Self {
+            state,
+            value,
+            offset,
+        }
+    }
+
+    pub(crate) fn from_raw(value: BNRegisterValue) -> Self {
+        Self {
+            state: value.state,
+            value: value.value,
+            offset: value.offset,
+        }
+    }
+}
+
+//////////////////////
+// PossibleValueSet
+
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+pub struct ValueRange<T> {
+    pub start: T,
+    pub end: T,
+    pub step: u64,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub struct LookupTableEntry {
+    pub from_values: Vec<i64>,
+    pub to_value: i64,
+}
+
+/// Everything the core's dataflow analysis knows about a value
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum PossibleValueSet {
+    UndeterminedValue,
+    EntryValue {
+        reg: i64,
+    },
+    ConstantValue {
+        value: i64,
+    },
+    ConstantPointerValue {
+        value: i64,
+    },
+    ExternalPointerValue {
+        value: i64,
+        offset: i64,
+    },
+    StackFrameOffset {
+        offset: i64,
+    },
+    ReturnAddressValue,
+    ImportedAddressValue {
+        value: i64,
+    },
+    SignedRangeValue {
+        offset: i64,
+        ranges: Vec<ValueRange<i64>>,
+    },
+    UnsignedRangeValue {
+        offset: i64,
+        ranges: Vec<ValueRange<u64>>,
+    },
+    LookupTableValue {
+        table: Vec<LookupTableEntry>,
+    },
+    InSetOfValues {
+        values: HashSet<i64>,
+    },
+    NotInSetOfValues {
+        values: HashSet<i64>,
+    },
+}
+
+// the core leaves the list pointers null when there is nothing in them
+unsafe fn possible_value_slice<'a, T>(raw: *const T, count: usize) -> &'a [T] {
+    if raw.is_null() {
+        &[]
+    } else {
+        slice::from_raw_parts(raw, count)
+    }
+}
+
+impl PossibleValueSet {
+    pub(crate) unsafe fn from_raw(value: &BNPossibleValueSet) -> Self {
+        use BNRegisterValueType::*;
+
+        match value.state {
+            UndeterminedValue => PossibleValueSet::UndeterminedValue,
+            EntryValue => PossibleValueSet::EntryValue { reg: value.value },
+            ConstantValue => PossibleValueSet::ConstantValue { value: value.value },
+            ConstantPointerValue => PossibleValueSet::ConstantPointerValue { value: value.value },
+            ExternalPointerValue => PossibleValueSet::ExternalPointerValue {
+                value: value.value,
+                offset: value.offset,
+            },
+            StackFrameOffset => PossibleValueSet::StackFrameOffset {
+                offset: value.value,
+            },
+            ReturnAddressValue => PossibleValueSet::ReturnAddressValue,
+            ImportedAddressValue => PossibleValueSet::ImportedAddressValue { value: value.value },
+            SignedRangeValue => PossibleValueSet::SignedRangeValue {
+                offset: value.value,
+                ranges: possible_value_slice(value.ranges, value.count)
+                    .iter()
+                    .map(|range| ValueRange {
+                        start: range.start as i64,
+                        end: range.end as i64,
+                        step: range.step,
+                    })
+                    .collect(),
+            },
+            UnsignedRangeValue => PossibleValueSet::UnsignedRangeValue {
+                offset: value.value,
+                ranges: possible_value_slice(value.ranges, value.count)
+                    .iter()
+                    .map(|range| ValueRange {
+                        start: range.start,
+                        end: range.end,
+                        step: range.step,
+                    })
+                    .collect(),
+            },
+            LookupTableValue => PossibleValueSet::LookupTableValue {
+                table: possible_value_slice(value.table, value.count)
+                    .iter()
+                    .map(|entry| LookupTableEntry {
+                        from_values: possible_value_slice(entry.fromValues, entry.fromCount)
+                            .to_vec(),
+                        to_value: entry.toValue,
+                    })
+                    .collect(),
+            },
+            InSetOfValues => PossibleValueSet::InSetOfValues {
+                values: possible_value_slice(value.valueSet, value.count)
+                    .iter()
+                    .copied()
+                    .collect(),
+            },
+            NotInSetOfValues => PossibleValueSet::NotInSetOfValues {
+                values: possible_value_slice(value.valueSet, value.count)
+                    .iter()
+                    .copied()
+                    .collect(),
+            },
+        }
+    }
+
+    /// Takes ownership of a value set returned by the core, freeing it
+    pub(crate) unsafe fn from_owned_raw(mut value: BNPossibleValueSet) -> Self {
+        let res = Self::from_raw(&value);
+        BNFreePossibleValueSet(&mut value);
+        res
+    }
+}
+
 ////////////////////////
 // EnumerationBuilder
 
@@ -1588,15 +1808,18 @@ pub struct DataVariable {
     pub auto_discovered: bool,
 }
 
-// impl DataVariable {
-//     pub(crate) fn from_raw(var: &BNDataVariable) -> Self {
-//         Self {
-//             address: var.address,
-//             t: Conf::new(unsafe { Type::ref_from_raw(var.type_) }, var.typeConfidence),
-//             auto_discovered: var.autoDiscovered,
-//         }
-//     }
-// }
+impl DataVariable {
+    pub(crate) fn from_raw(var: &BNDataVariable) -> Self {
+        Self {
+            address: var.address,
+            t: Conf::new(
+                unsafe { Type::ref_from_raw(BNNewTypeReference(var.type_)) },
+                var.typeConfidence,
+            ),
+            auto_discovered: var.autoDiscovered,
+        }
+    }
+}
 
 impl DataVariable {
     pub fn type_with_confidence(&self) -> Conf<Ref<Type>> {
@@ -1604,22 +1827,22 @@ impl DataVariable {
     }
 }
 
-// unsafe impl CoreOwnedArrayProvider for DataVariable {
-//     type Raw = BNDataVariable;
-//     type Context = ();
+unsafe impl CoreOwnedArrayProvider for DataVariable {
+    type Raw = BNDataVariable;
+    type Context = ();
 
-//     unsafe fn free(raw: *mut Self::Raw, count: usize, _context: &Self::Context) {
-//         BNFreeDataVariables(raw, count);
-//     }
-// }
+    unsafe fn free(raw: *mut Self::Raw, count: usize, _context: &Self::Context) {
+        BNFreeDataVariables(raw, count);
+    }
+}
 
-// unsafe impl<'a> CoreOwnedArrayWrapper<'a> for DataVariable {
-//     type Wrapped = &'a DataVariable;
+unsafe impl<'a> CoreOwnedArrayWrapper<'a> for DataVariable {
+    type Wrapped = DataVariable;
 
-//     unsafe fn wrap_raw(raw: &'a Self::Raw, _context: &'a Self::Context) -> Self::Wrapped {
-//         mem::transmute(raw)
-//     }
-// }
+    unsafe fn wrap_raw(raw: &'a Self::Raw, _context: &'a Self::Context) -> Self::Wrapped {
+        DataVariable::from_raw(raw)
+    }
+}
 
 /////////////////////////
 // DataVariableAndName